@@ -1,27 +1,1410 @@
 #![allow(clippy::no_effect_underscore_binding)]
 
+use poker_calculator::{
+    compute_equity, compute_equity_async, compute_equity_memoized, compute_range_equity,
+    parse_cards, parse_spot, runout_count, CancelHandle, Card, EquityMethod,
+    EquityRequest as ComputeRequest, EquityRequestBuilder, EquityResult, Hand, MonteCarlo,
+    PokerError, PreflopTable, Range,
+};
+use rocket::fairing::AdHoc;
+use rocket::futures::future::join_all;
+use rocket::http::Status;
+use rocket::response::stream::{Event, EventStream};
+use rocket::response::{self, Responder};
+use rocket::serde::{json::Json, Deserialize, Serialize};
+use rocket::tokio::sync::{watch, Semaphore};
+use rocket::tokio::task::JoinError;
+use rocket::{Request, State};
 use rocket_cors::{AllowedHeaders, AllowedOrigins};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 #[macro_use]
 extern crate rocket;
 
+/// How long `/equity/fast` may compute for when the client doesn't specify.
+const DEFAULT_BUDGET_MS: u64 = 500;
+
+/// How many Monte Carlo samples are run between checks of the clock.
+const BATCH_SIZE: u64 = 1_000;
+
+/// The most scenarios `/equity/batch` accepts when not configured otherwise.
+const DEFAULT_MAX_BATCH_SIZE: usize = 64;
+
+/// How many Monte Carlo samples `/equity/range` runs when the client doesn't
+/// specify.
+const DEFAULT_RANGE_SAMPLES: u64 = 100_000;
+
+/// The most Monte Carlo samples `/equity/fast` and `/equity/range` accept,
+/// since a fixed sample count isn't bounded by any time budget.
+const MAX_SAMPLES: u64 = 10_000_000;
+
+/// The most hand evaluations `/equity` will spend on an exact answer, before
+/// estimating it from `EQUITY_SAMPLES` random runouts instead. This lets
+/// every postflop spot be exact, while multiway preflop spots are sampled.
+const MAX_EXACT_EVALUATIONS: u64 = 2_000_000;
+
+/// How many Monte Carlo samples `/equity` runs when a spot is too large to
+/// enumerate.
+const EQUITY_SAMPLES: u64 = 100_000;
+
+/// The number of standard errors either side of an estimate which give a 95%
+/// confidence interval.
+const CONFIDENCE_95: f64 = 1.96;
+
+/// The most showdowns `/equity/range` will enumerate exhaustively, which keeps
+/// wide preflop ranges from tying up a thread for minutes.
+const MAX_EXHAUSTIVE_SHOWDOWNS: u64 = 20_000_000;
+
+/// How long a finished job's outcome is kept for a listener to stream, after
+/// which it is forgotten even if nobody has.
+const JOB_RETENTION: Duration = Duration::from_mins(5);
+
+/// Limits for `/equity/batch`, which can be set through Rocket's usual
+/// configuration, such as `ROCKET_MAX_BATCH_SIZE=128`.
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct BatchConfig {
+    #[serde(default = "default_max_batch_size")]
+    max_batch_size: usize,
+    #[serde(default = "default_batch_parallelism")]
+    batch_parallelism: usize,
+}
+
+const fn default_max_batch_size() -> usize {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
+fn default_batch_parallelism() -> usize {
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct EquityRequest {
+    #[serde(default)]
+    hands: Vec<String>,
+    #[serde(default)]
+    board: String,
+    #[serde(default)]
+    dead: String,
+    /// The whole scenario on one line, such as `"AsKs QhQd | Jc9c2s | Th"`,
+    /// in place of the other fields.
+    spot: Option<String>,
+}
+
+impl EquityRequest {
+    fn scenario(&self) -> Result<Scenario, String> {
+        let Some(spot) = &self.spot else {
+            return parse_scenario(&self.hands, &self.board, &self.dead);
+        };
+        if !(self.hands.is_empty() && self.board.is_empty() && self.dead.is_empty()) {
+            return Err("a spot can't be combined with hands, board or dead cards".into());
+        }
+        let spot = parse_spot(spot).map_err(|error| format!("invalid spot {spot:?}: {error}"))?;
+        check_scenario((spot.hands, spot.board, spot.dead))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct FastEquityRequest {
+    hands: Vec<String>,
+    #[serde(default)]
+    board: String,
+    #[serde(default)]
+    dead: String,
+    budget_ms: Option<u64>,
+    samples: Option<u64>,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct RangeEquityRequest {
+    hero: String,
+    villain: String,
+    #[serde(default)]
+    board: String,
+    #[serde(default)]
+    dead: String,
+    #[serde(default)]
+    mode: Mode,
+    samples: Option<u64>,
+    seed: Option<u64>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+enum Mode {
+    #[default]
+    Exhaustive,
+    MonteCarlo,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+struct HandEquity {
+    win_count: u64,
+    loss_count: u64,
+    tie_count: u64,
+    equity: f64,
+    standard_error: f64,
+    /// A 95% confidence interval for the chance of winning outright, which
+    /// is just that chance when exhaustive.
+    win_interval: (f64, f64),
+}
+
+/// Whether the equities in an [`EquityResponse`] are exact or an estimate.
+#[derive(Clone, Copy, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+enum Method {
+    Exact,
+    MonteCarlo,
+}
+
+impl From<Mode> for Method {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Exhaustive => Self::Exact,
+            Mode::MonteCarlo => Self::MonteCarlo,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+enum Source {
+    Cache,
+    Computed,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct EquityResponse {
+    source: Source,
+    method: Method,
+    /// The number of runouts counted, which is every runout when exhaustive.
+    samples: u64,
+    /// Half the width of a 95% confidence interval around the least certain
+    /// equity, which is zero when exhaustive.
+    margin_of_error: f64,
+    results: Vec<HandEquity>,
+}
+
+impl EquityResponse {
+    fn new(source: Source, mode: Mode, results: &[EquityResult]) -> Self {
+        let samples = results.first().map_or(0, |result| result.count);
+        let results: Vec<HandEquity> = results
+            .iter()
+            .map(|result| hand_equity(result, mode))
+            .collect();
+        Self {
+            source,
+            method: mode.into(),
+            samples,
+            margin_of_error: results
+                .iter()
+                .map(|result| result.standard_error * CONFIDENCE_95)
+                .fold(0., f64::max),
+            results,
+        }
+    }
+}
+
+/// The outcome of one scenario in a batch. A bad scenario only fails its own
+/// entry rather than the whole batch.
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+enum BatchEntry {
+    Ok(EquityResponse),
+    Error(String),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct FastEquityResponse {
+    method: Method,
+    samples: u64,
+    seed: Option<u64>,
+    results: Vec<HandEquity>,
+}
+
+/// The hero's equity against one class of the villain's range.
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct ClassBreakdown {
+    hand: String,
+    combos: usize,
+    blocked: usize,
+    #[serde(flatten)]
+    equity: HandEquity,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct RangeEquityResponse {
+    method: Method,
+    seed: Option<u64>,
+    result: HandEquity,
+    classes: Vec<ClassBreakdown>,
+}
+
+/// An error response, which is sent as `{ "error": "..." }` so that clients
+/// can show the message. Route handlers can use `?` on any error which
+/// converts into it.
+#[derive(Debug)]
+struct ApiError {
+    status: Status,
+    error: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct ErrorBody {
+    error: String,
+}
+
+impl ApiError {
+    const fn new(status: Status, error: String) -> Self {
+        Self { status, error }
+    }
+}
+
+/// Errors described by the server itself are always about the request.
+impl From<String> for ApiError {
+    fn from(error: String) -> Self {
+        Self::new(Status::BadRequest, error)
+    }
+}
+
+impl From<PokerError> for ApiError {
+    fn from(error: PokerError) -> Self {
+        let status = match error {
+            PokerError::InvalidCard(_)
+            | PokerError::InvalidSuit(_)
+            | PokerError::InvalidNumber(_)
+            | PokerError::InvalidCardIndex(_)
+            | PokerError::DuplicateCard(_)
+            | PokerError::InvalidBoardSize(_)
+            | PokerError::InvalidHandSize { .. }
+            | PokerError::InvalidRange { .. }
+            | PokerError::NotEnoughCards { .. }
+            | PokerError::InvalidHistory { .. }
+            | PokerError::InvalidRequest(_) => Status::BadRequest,
+            // Nothing the client sends cancels a computation it's waiting on.
+            PokerError::Cancelled => Status::InternalServerError,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+/// A computation panicked or was dropped on its blocking thread.
+impl From<JoinError> for ApiError {
+    fn from(error: JoinError) -> Self {
+        Self::new(Status::InternalServerError, error.to_string())
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (self.status, Json(ErrorBody { error: self.error })).respond_to(request)
+    }
+}
+
+/// An error body which points at the offending part of a range, when there
+/// is one.
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct RangeError {
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<usize>,
+}
+
+impl From<String> for RangeError {
+    fn from(error: String) -> Self {
+        Self {
+            error,
+            token: None,
+            position: None,
+        }
+    }
+}
+
+impl From<PokerError> for RangeError {
+    fn from(error: PokerError) -> Self {
+        match error {
+            PokerError::InvalidRange {
+                ref token,
+                position,
+            } => Self {
+                error: error.to_string(),
+                token: Some(token.clone()),
+                position: Some(position),
+            },
+            error => error.to_string().into(),
+        }
+    }
+}
+
+/// The state of a job, as streamed to its listeners. Progress carries the
+/// results so far, so a listener which connects late still sees them.
+#[derive(Clone, Serialize)]
+#[serde(crate = "rocket::serde", tag = "type", rename_all = "snake_case")]
+enum JobStatus {
+    Progress {
+        done: u64,
+        total: u64,
+        results: Vec<HandEquity>,
+    },
+    Finished {
+        results: Vec<HandEquity>,
+    },
+    Failed {
+        error: String,
+    },
+    Cancelled,
+}
+
+impl JobStatus {
+    const fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Progress { .. })
+    }
+}
+
+/// An exhaustive computation running in the background.
+struct Job {
+    status: watch::Sender<JobStatus>,
+    cancel: CancelHandle,
+    /// When the job ended, which is set along with its terminal status.
+    finished: OnceLock<Instant>,
+}
+
+/// The jobs which are still running, or whose outcome hasn't been streamed
+/// yet and is younger than [`JOB_RETENTION`].
+#[derive(Default)]
+struct Jobs {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+}
+
+impl Jobs {
+    /// Forgets the jobs which had finished [`JOB_RETENTION`] before `now`.
+    fn remove_expired(&self, now: Instant) {
+        self.jobs.lock().unwrap().retain(|_, job| {
+            job.finished
+                .get()
+                .is_none_or(|&finished| now.duration_since(finished) < JOB_RETENTION)
+        });
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct JobCreated {
+    id: u64,
+}
+
+fn parse_hand(s: &str) -> Result<[Card; 2], String> {
+    s.parse::<Hand>()
+        .map(Hand::cards)
+        .map_err(|error| format!("invalid hand {s:?}: {error}"))
+}
+
+type Scenario = (Vec<[Card; 2]>, Vec<Card>, Vec<Card>);
+
+/// Rejects scenarios which the library would answer, but which can only be
+/// mistakes when sent to the server.
+fn check_scenario(scenario: Scenario) -> Result<Scenario, String> {
+    let (hands, board, dead) = &scenario;
+    if hands.len() < 2 {
+        return Err(format!(
+            "at least two hands are needed, but found {}",
+            hands.len()
+        ));
+    }
+    runout_count(hands, board, dead).map_err(|error| error.to_string())?;
+    Ok(scenario)
+}
+
+fn parse_scenario(hands: &[String], board: &str, dead: &str) -> Result<Scenario, String> {
+    let hands = hands
+        .iter()
+        .map(|hand| parse_hand(hand))
+        .collect::<Result<Vec<_>, _>>()?;
+    let board = parse_cards(board).map_err(|error| error.to_string())?;
+    let dead = parse_cards(dead).map_err(|error| error.to_string())?;
+    check_scenario((hands, board, dead))
+}
+
+fn hand_equity(result: &EquityResult, mode: Mode) -> HandEquity {
+    let (standard_error, win_interval) = match mode {
+        Mode::Exhaustive => (0., (result.win_fraction(), result.win_fraction())),
+        Mode::MonteCarlo => (
+            result.standard_error(),
+            result.win_confidence_interval(CONFIDENCE_95),
+        ),
+    };
+
+    HandEquity {
+        win_count: result.win_count,
+        loss_count: result.loss_count,
+        tie_count: result.tie_count,
+        equity: result.equity(),
+        standard_error,
+        win_interval,
+    }
+}
+
+/// Rejects sample counts which would occupy a blocking thread for too long.
+fn check_samples(samples: Option<u64>) -> Result<(), ApiError> {
+    match samples {
+        Some(samples) if samples > MAX_SAMPLES => Err(ApiError::new(
+            Status::PayloadTooLarge,
+            format!("at most {MAX_SAMPLES} samples may be requested, but {samples} were"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn compute_fast_equity(request: &FastEquityRequest) -> Result<FastEquityResponse, String> {
+    let start = Instant::now();
+    let (hands, board, dead) = parse_scenario(&request.hands, &request.board, &request.dead)?;
+
+    let runouts = runout_count(&hands, &board, &dead).map_err(|error| error.to_string())?;
+    // Sampling at least as many runouts as there are is both slower and less
+    // accurate than counting each of them once.
+    if request.samples.is_some_and(|samples| samples >= runouts) {
+        return exhaustive_fast_equity(&hands, &board, &dead);
+    }
+
+    let mut simulation =
+        MonteCarlo::new(&hands, &board, &dead, request.seed).map_err(|error| error.to_string())?;
+
+    // A fixed sample count without a budget is a request for a reproducible
+    // answer, so the clock must not influence the result.
+    if let (Some(samples), None) = (request.samples, request.budget_ms) {
+        simulation.run(samples);
+        return Ok(monte_carlo_response(&simulation));
+    }
+
+    let budget = Duration::from_millis(request.budget_ms.unwrap_or(DEFAULT_BUDGET_MS));
+    let deadline = start + budget;
+    let max_samples = request.samples.unwrap_or(u64::MAX);
+
+    // Time a first batch to estimate how quickly this scenario evaluates.
+    let batch_start = Instant::now();
+    simulation.run(BATCH_SIZE.min(max_samples));
+    let mut batch_time = batch_start.elapsed();
+
+    // Prefer the exact answer when enumerating every runout fits in the
+    // remaining time.
+    #[allow(clippy::cast_precision_loss)]
+    let exhaustive_time = batch_time.mul_f64(runouts as f64 / simulation.samples().max(1) as f64);
+    if Instant::now() + exhaustive_time < deadline {
+        return exhaustive_fast_equity(&hands, &board, &dead);
+    }
+
+    // Only start another batch when it should finish before the deadline.
+    while simulation.samples() < max_samples && Instant::now() + batch_time < deadline {
+        let batch_start = Instant::now();
+        simulation.run(BATCH_SIZE.min(max_samples - simulation.samples()));
+        batch_time = batch_start.elapsed();
+    }
+
+    Ok(monte_carlo_response(&simulation))
+}
+
+fn exhaustive_fast_equity(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<FastEquityResponse, String> {
+    let results = compute_equity(hands, board, dead).map_err(|error| error.to_string())?;
+    Ok(FastEquityResponse {
+        method: Method::Exact,
+        samples: results.first().map_or(0, |result| result.count),
+        seed: None,
+        results: results
+            .iter()
+            .map(|result| hand_equity(result, Mode::Exhaustive))
+            .collect(),
+    })
+}
+
+fn monte_carlo_response(simulation: &MonteCarlo) -> FastEquityResponse {
+    FastEquityResponse {
+        method: Method::MonteCarlo,
+        samples: simulation.samples(),
+        seed: Some(simulation.seed()),
+        results: simulation
+            .results()
+            .iter()
+            .map(|result| hand_equity(result, Mode::MonteCarlo))
+            .collect(),
+    }
+}
+
+fn compute_range_scenario(request: &RangeEquityRequest) -> Result<RangeEquityResponse, RangeError> {
+    let hero: Range = request.hero.parse()?;
+    let villain: Range = request.villain.parse()?;
+    let board = parse_cards(&request.board)?;
+    let dead = parse_cards(&request.dead)?;
+
+    let (method, seed) = match request.mode {
+        Mode::Exhaustive => {
+            // Every hero combination meets every villain combination on every
+            // runout, which bounds the work from above.
+            let showdowns = (hero.len() * villain.len()) as u64 * runout_count(&[], &board, &dead)?;
+            if showdowns > MAX_EXHAUSTIVE_SHOWDOWNS {
+                return Err(format!(
+                    "enumerating up to {showdowns} showdowns is too slow, use the montecarlo mode instead"
+                )
+                .into());
+            }
+            (EquityMethod::Exhaustive, None)
+        }
+        Mode::MonteCarlo => {
+            let seed = request.seed.unwrap_or_else(rand::random);
+            let samples = request.samples.unwrap_or(DEFAULT_RANGE_SAMPLES);
+            (
+                EquityMethod::MonteCarlo {
+                    samples,
+                    seed: Some(seed),
+                },
+                Some(seed),
+            )
+        }
+    };
+
+    let equity = compute_range_equity(&hero, &villain, &board, &dead, method)?;
+    Ok(RangeEquityResponse {
+        method: request.mode.into(),
+        seed,
+        result: hand_equity(&equity.result, request.mode),
+        classes: equity
+            .classes
+            .iter()
+            .map(|class| ClassBreakdown {
+                hand: class.hand.to_string(),
+                combos: class.combos,
+                blocked: class.blocked,
+                equity: hand_equity(&class.result, request.mode),
+            })
+            .collect(),
+    })
+}
+
+fn exhaustive_results(results: &[EquityResult]) -> Vec<HandEquity> {
+    results
+        .iter()
+        .map(|result| hand_equity(result, Mode::Exhaustive))
+        .collect()
+}
+
 #[get("/hello")]
 fn hello_get() -> String {
     "Hello, World!".into()
 }
 
+async fn compute_scenario(
+    request: &EquityRequest,
+    preflop_table: &Mutex<PreflopTable>,
+) -> Result<EquityResponse, ApiError> {
+    let (hands, board, dead) = request.scenario()?;
+
+    // Heads-up preflop matchups come from a small space, so they are served
+    // from the table once any suit permutation of them has been computed.
+    let preflop_matchup = match hands[..] {
+        [hero, villain] if board.is_empty() && dead.is_empty() => Some((hero, villain)),
+        _ => None,
+    };
+    let cached = preflop_matchup
+        .and_then(|(hero, villain)| preflop_table.lock().unwrap().get(hero, villain));
+    if let Some(results) = cached {
+        return Ok(EquityResponse::new(
+            Source::Cache,
+            Mode::Exhaustive,
+            &results,
+        ));
+    }
+
+    let (mode, results) = rocket::tokio::task::spawn_blocking(move || {
+        if preflop_matchup.is_some() {
+            return compute_equity_memoized(&hands, &board, &dead)
+                .map(|results| (Mode::Exhaustive, results));
+        }
+
+        let evaluations = runout_count(&hands, &board, &dead)? * hands.len() as u64;
+        if evaluations <= MAX_EXACT_EVALUATIONS {
+            return compute_equity(&hands, &board, &dead)
+                .map(|results| (Mode::Exhaustive, results));
+        }
+        let mut simulation = MonteCarlo::new(&hands, &board, &dead, None)?;
+        simulation.run(EQUITY_SAMPLES);
+        Ok((Mode::MonteCarlo, simulation.results().to_vec()))
+    })
+    .await??;
+
+    if let (Some((hero, villain)), &[hero_result, villain_result]) = (preflop_matchup, &results[..])
+    {
+        preflop_table
+            .lock()
+            .unwrap()
+            .insert(hero, villain, [hero_result, villain_result]);
+    }
+
+    Ok(EquityResponse::new(Source::Computed, mode, &results))
+}
+
+#[post("/equity", format = "json", data = "<request>")]
+async fn equity_post(
+    request: Json<EquityRequest>,
+    preflop_table: &State<Mutex<PreflopTable>>,
+) -> Result<Json<EquityResponse>, ApiError> {
+    compute_scenario(&request, preflop_table).await.map(Json)
+}
+
+#[post("/equity/batch", format = "json", data = "<requests>")]
+async fn equity_batch_post(
+    requests: Json<Vec<EquityRequest>>,
+    preflop_table: &State<Mutex<PreflopTable>>,
+    config: &State<BatchConfig>,
+) -> Result<Json<Vec<BatchEntry>>, ApiError> {
+    if requests.len() > config.max_batch_size {
+        return Err(ApiError::new(
+            Status::PayloadTooLarge,
+            format!(
+                "a batch may contain at most {} scenarios, but {} were sent",
+                config.max_batch_size,
+                requests.len()
+            ),
+        ));
+    }
+
+    // Each scenario computes on a blocking thread, so the semaphore bounds
+    // how many of those threads a single batch can occupy.
+    let permits = Semaphore::new(config.batch_parallelism.max(1));
+    let entries = join_all(requests.iter().map(|request| async {
+        let _permit = permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        match compute_scenario(request, preflop_table).await {
+            Ok(response) => BatchEntry::Ok(response),
+            Err(ApiError { error, .. }) => BatchEntry::Error(error),
+        }
+    }))
+    .await;
+
+    Ok(Json(entries))
+}
+
+#[post("/equity/fast", format = "json", data = "<request>")]
+async fn equity_fast_post(
+    request: Json<FastEquityRequest>,
+) -> Result<Json<FastEquityResponse>, ApiError> {
+    let request = request.into_inner();
+    check_samples(request.samples)?;
+    let response =
+        rocket::tokio::task::spawn_blocking(move || compute_fast_equity(&request)).await??;
+    Ok(Json(response))
+}
+
+#[post("/equity/range", format = "json", data = "<request>")]
+async fn equity_range_post(
+    request: Json<RangeEquityRequest>,
+) -> Result<Json<RangeEquityResponse>, (Status, Json<RangeError>)> {
+    let request = request.into_inner();
+    check_samples(request.samples)
+        .map_err(|ApiError { status, error }| (status, Json(RangeError::from(error))))?;
+    rocket::tokio::task::spawn_blocking(move || compute_range_scenario(&request))
+        .await
+        .map_err(|error| {
+            (
+                Status::InternalServerError,
+                Json(RangeError::from(error.to_string())),
+            )
+        })?
+        .map(Json)
+        .map_err(|error| (Status::BadRequest, Json(error)))
+}
+
+/// Starts computing the exact equity of a scenario in the background. Its
+/// progress can be followed from `/jobs/<id>/events`.
+#[post("/jobs", format = "json", data = "<request>")]
+fn job_post(
+    request: Json<EquityRequest>,
+    jobs: &State<Jobs>,
+) -> Result<Json<JobCreated>, ApiError> {
+    let request = request.into_inner();
+    let (hands, board, dead) = request.scenario()?;
+    let total = runout_count(&hands, &board, &dead)?;
+    let request = hands
+        .into_iter()
+        .fold(ComputeRequest::builder(), EquityRequestBuilder::hand_cards)
+        .board_cards(&board)
+        .dead(&dead)
+        .build()?;
+
+    let (status, _) = watch::channel(JobStatus::Progress {
+        done: 0,
+        total,
+        results: vec![],
+    });
+    let (task, mut progress) = compute_equity_async(request);
+    let job = Arc::new(Job {
+        status,
+        cancel: task.cancel_handle(),
+        finished: OnceLock::new(),
+    });
+    let id = jobs.next_id.fetch_add(1, Ordering::Relaxed);
+    jobs.remove_expired(Instant::now());
+    jobs.jobs.lock().unwrap().insert(id, Arc::clone(&job));
+
+    rocket::tokio::spawn(async move {
+        // The progress channel closes once the computation ends, so every
+        // update has been forwarded before the outcome is sent.
+        while progress.changed().await.is_ok() {
+            let snapshot = progress.borrow_and_update().clone();
+            job.status.send_replace(JobStatus::Progress {
+                done: snapshot.done,
+                total: snapshot.total,
+                results: exhaustive_results(&snapshot.results),
+            });
+        }
+        job.status.send_replace(match task.await {
+            Ok(results) => JobStatus::Finished {
+                results: exhaustive_results(&results),
+            },
+            Err(PokerError::Cancelled) => JobStatus::Cancelled,
+            Err(error) => JobStatus::Failed {
+                error: error.to_string(),
+            },
+        });
+        // Only the worker sets it, and only once.
+        let _ = job.finished.set(Instant::now());
+    });
+
+    Ok(Json(JobCreated { id }))
+}
+
+/// Streams a job's status as server-sent events, ending with its outcome.
+#[get("/jobs/<id>/events")]
+fn job_events(id: u64, jobs: &State<Jobs>) -> Option<EventStream![Event + '_]> {
+    let mut status = jobs.jobs.lock().unwrap().get(&id)?.status.subscribe();
+
+    Some(EventStream! {
+        loop {
+            let current = status.borrow_and_update().clone();
+            yield Event::json(&current);
+            if current.is_terminal() {
+                jobs.jobs.lock().unwrap().remove(&id);
+                break;
+            }
+            if status.changed().await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Cancels a job, which its listeners see as a `cancelled` event.
+#[delete("/jobs/<id>")]
+fn job_delete(id: u64, jobs: &State<Jobs>) -> Status {
+    let job = jobs.jobs.lock().unwrap().remove(&id);
+    job.map_or(Status::NotFound, |job| {
+        job.cancel.cancel();
+        Status::NoContent
+    })
+}
+
 #[launch]
 fn rocket() -> _ {
     let allowed_origins = AllowedOrigins::all();
 
     let cors = rocket_cors::CorsOptions {
         allowed_origins,
-        allowed_headers: AllowedHeaders::some(&["Authorization", "Accept"]),
+        allowed_headers: AllowedHeaders::some(&["Authorization", "Accept", "Content-Type"]),
         allow_credentials: true,
         ..Default::default()
     }
     .to_cors()
     .unwrap();
 
-    rocket::build().mount("/", routes![hello_get]).attach(cors)
+    rocket::build()
+        .mount(
+            "/",
+            routes![
+                hello_get,
+                equity_post,
+                equity_batch_post,
+                equity_fast_post,
+                equity_range_post,
+                job_post,
+                job_events,
+                job_delete
+            ],
+        )
+        .manage(Mutex::new(PreflopTable::new()))
+        .manage(Jobs::default())
+        .attach(AdHoc::config::<BatchConfig>())
+        .attach(cors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rocket, Jobs, DEFAULT_MAX_BATCH_SIZE, EQUITY_SAMPLES, JOB_RETENTION, MAX_SAMPLES};
+    use poker_calculator::{parse_cards, Card, EquityResult, PreflopTable};
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket::serde::json::{json, Value};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    fn post(client: &Client, uri: &'static str, body: &Value) -> (Status, Option<Value>) {
+        let response = client
+            .post(uri)
+            .header(ContentType::JSON)
+            .body(body.to_string())
+            .dispatch();
+        (response.status(), response.into_json())
+    }
+
+    fn post_fast_equity(client: &Client, body: &Value) -> (Status, Option<Value>) {
+        post(client, "/equity/fast", body)
+    }
+
+    fn hand(s: &str) -> [Card; 2] {
+        let cards = parse_cards(s).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn test_preflop_suit_permutation_hits_cache() {
+        let client = Client::tracked(rocket()).unwrap();
+
+        // Seed the table directly, since computing a preflop matchup is slow
+        // in debug builds.
+        let hero = EquityResult {
+            win_count: 7,
+            loss_count: 2,
+            tie_count: 1,
+            count: 10,
+        };
+        let villain = EquityResult {
+            win_count: 2,
+            loss_count: 7,
+            tie_count: 1,
+            count: 10,
+        };
+        client
+            .rocket()
+            .state::<Mutex<PreflopTable>>()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .insert(hand("AsKs"), hand("QdQc"), [hero, villain]);
+
+        let (status, response) = post(&client, "/equity", &json!({ "hands": ["AhKh", "QcQs"] }));
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["source"], "cache");
+        assert_eq!(response["method"], "exact");
+        assert_eq!(response["results"][0]["win_count"], 7);
+        assert_eq!(response["results"][1]["win_count"], 2);
+        assert_eq!(response["results"][0]["equity"], 0.75);
+    }
+
+    #[test]
+    fn test_postflop_is_computed() {
+        let client = Client::tracked(rocket()).unwrap();
+        let body = json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc" });
+
+        for _ in 0..2 {
+            let (status, response) = post(&client, "/equity", &body);
+            let response = response.unwrap();
+            assert_eq!(status, Status::Ok);
+            assert_eq!(response["source"], "computed");
+            assert_eq!(response["method"], "exact");
+            assert_eq!(response["samples"], 44);
+            assert_eq!(response["margin_of_error"], 0.);
+            assert_eq!(response["results"][0]["win_count"], 11);
+            assert_eq!(response["results"][1]["win_count"], 33);
+        }
+    }
+
+    #[test]
+    fn test_spot_is_accepted() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post(
+            &client,
+            "/equity",
+            &json!({ "spot": "AhQc KsKd | 2h7hJhKc" }),
+        );
+        assert_eq!(status, Status::Ok);
+        let (_, expected) = post(
+            &client,
+            "/equity",
+            &json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc" }),
+        );
+        assert_eq!(response.unwrap()["results"], expected.unwrap()["results"]);
+
+        let (status, _) = post(
+            &client,
+            "/equity",
+            &json!({ "spot": "AhQc KsKd | 2h7hJhKc | 2c | 3c" }),
+        );
+        assert_eq!(status, Status::BadRequest);
+        let (status, _) = post(
+            &client,
+            "/equity",
+            &json!({ "spot": "AhQc KsKd", "board": "2h7hJhKc" }),
+        );
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[test]
+    fn test_large_multiway_is_sampled() {
+        let client = Client::tracked(rocket()).unwrap();
+        let body = json!({ "hands": ["AsKs", "QdQc", "JhTh", "9c9s", "7d6d", "AhKd"] });
+        let (status, response) = post(&client, "/equity", &body);
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["method"], "montecarlo");
+        assert_eq!(response["samples"], EQUITY_SAMPLES);
+
+        let margin = response["margin_of_error"].as_f64().unwrap();
+        assert!(margin > 0. && margin < 0.01);
+        let results = response["results"].as_array().unwrap();
+        for result in results {
+            let standard_error = result["standard_error"].as_f64().unwrap();
+            // Allow for the rounding of the floats in the JSON.
+            assert!(standard_error * 1.96 <= margin + 1e-12);
+            let win_fraction = result["win_count"].as_f64().unwrap() / EQUITY_SAMPLES as f64;
+            let lower = result["win_interval"][0].as_f64().unwrap();
+            let upper = result["win_interval"][1].as_f64().unwrap();
+            assert!(lower < win_fraction && win_fraction < upper);
+        }
+    }
+
+    #[test]
+    fn test_fixed_seed_and_samples_is_deterministic() {
+        let client = Client::tracked(rocket()).unwrap();
+        let body = json!({
+            "hands": ["AsKs", "QdQc"],
+            "samples": 5000,
+            "seed": 7,
+        });
+
+        let (status, first) = post_fast_equity(&client, &body);
+        assert_eq!(status, Status::Ok);
+        let (_, second) = post_fast_equity(&client, &body);
+
+        let first = first.unwrap();
+        assert_eq!(first["method"], "montecarlo");
+        assert_eq!(first["samples"], 5000);
+        assert_eq!(first["seed"], 7);
+        assert_eq!(Some(first), second);
+    }
+
+    #[test]
+    fn test_small_problem_is_exhaustive() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post_fast_equity(
+            &client,
+            &json!({
+                "hands": ["AhQc", "KsKd"],
+                "board": "2h7hJhKc",
+                "budget_ms": 500,
+            }),
+        );
+
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["method"], "exact");
+        assert_eq!(response["samples"], 44);
+        assert_eq!(response["results"][0]["win_count"], 11);
+        assert_eq!(response["results"][0]["standard_error"], 0.);
+        assert_eq!(response["results"][0]["win_interval"], json!([0.25, 0.25]));
+    }
+
+    #[test]
+    fn test_budget_is_respected() {
+        let client = Client::tracked(rocket()).unwrap();
+        let start = std::time::Instant::now();
+        let (status, response) = post_fast_equity(
+            &client,
+            &json!({
+                "hands": ["AsKs", "QdQc", "7h6h"],
+                "budget_ms": 100,
+            }),
+        );
+
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["method"], "montecarlo");
+        assert!(response["samples"].as_u64().unwrap() > 0);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_invalid_card_is_rejected() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, _) = post_fast_equity(&client, &json!({ "hands": ["AsKx", "QdQc"] }));
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[test]
+    fn test_too_many_samples_are_rejected() {
+        let client = Client::tracked(rocket()).unwrap();
+        let samples = MAX_SAMPLES + 1;
+
+        let (status, response) = post_fast_equity(
+            &client,
+            &json!({ "hands": ["AsKs", "QdQc"], "samples": samples }),
+        );
+        assert_eq!(status, Status::PayloadTooLarge);
+        assert!(response.unwrap()["error"].is_string());
+
+        let (status, response) = post(
+            &client,
+            "/equity/range",
+            &json!({
+                "hero": "AhKh",
+                "villain": "QQ+",
+                "mode": "montecarlo",
+                "samples": samples,
+            }),
+        );
+        assert_eq!(status, Status::PayloadTooLarge);
+        assert!(response.unwrap()["error"].is_string());
+    }
+
+    #[test]
+    fn test_errors_are_json() {
+        let client = Client::tracked(rocket()).unwrap();
+
+        let duplicate = json!({ "hands": ["AsKs", "AsQd"], "board": "2c7d9h" });
+        let (status, response) = post(&client, "/equity", &duplicate);
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(response.unwrap()["error"], "duplicate card: As");
+
+        let (status, response) = post(&client, "/equity", &json!({ "hands": ["AsKs", "QdQx"] }));
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.unwrap()["error"]
+            .as_str()
+            .unwrap()
+            .contains("\"QdQx\""));
+
+        for uri in ["/equity/fast", "/jobs"] {
+            let (status, response) = post(&client, uri, &duplicate);
+            assert_eq!(status, Status::BadRequest, "{uri}");
+            assert_eq!(response.unwrap()["error"], "duplicate card: As", "{uri}");
+        }
+    }
+
+    #[test]
+    fn test_degenerate_scenarios() {
+        let client = Client::tracked(rocket()).unwrap();
+
+        // A complete board is a single runout.
+        let river = json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc3s" });
+        let (status, response) = post(&client, "/equity", &river);
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response.unwrap()["samples"], 1);
+
+        // Asking for more samples than there are rivers counts each once.
+        let turn = json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc", "samples": 1000 });
+        let (status, response) = post_fast_equity(&client, &turn);
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["method"], "exact");
+        assert_eq!(response["samples"], 44);
+        assert_eq!(response["seed"], Value::Null);
+
+        let (status, _) = post(&client, "/equity", &json!({ "hands": ["AhQc"] }));
+        assert_eq!(status, Status::BadRequest);
+        let (status, _) = post_fast_equity(&client, &json!({ "hands": [] }));
+        assert_eq!(status, Status::BadRequest);
+
+        // 23 hands, a flop and two dead cards leave nothing to deal.
+        let cards = "2h3h4h5h6h7h8h9hThJhQhKhAh2d3d4d5d6d7d8d9dTdJdQdKdAd2c3c4c5c6c7c8c9cTcJcQcKcAc2s3s4s5s6s7s8s9s";
+        let hands: Vec<&str> = (0..23)
+            .map(|index| &cards[index * 4..index * 4 + 4])
+            .collect();
+        let everything = json!({ "hands": hands, "board": "TsJsQs", "dead": "KsAs" });
+        for uri in ["/equity", "/equity/fast", "/jobs"] {
+            let (status, _) = post(&client, uri, &everything);
+            assert_eq!(status, Status::BadRequest, "{uri}");
+        }
+    }
+
+    #[test]
+    fn test_batch_reports_errors_per_scenario() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post(
+            &client,
+            "/equity/batch",
+            &json!([
+                { "hands": ["AsQc", "KhKc"], "board": "AhKd7c7s2h" },
+                { "hands": ["AsQc", "KhKx"], "board": "AhKd7c7s2h" },
+                { "hands": ["AsKs", "AsQd"], "board": "2c7d9h" },
+                { "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc" },
+            ]),
+        );
+
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response.as_array().unwrap().len(), 4);
+        assert_eq!(response[0]["ok"]["results"][1]["win_count"], 1);
+        assert!(response[1]["error"].as_str().unwrap().contains("KhKx"));
+        assert!(response[2]["error"].as_str().unwrap().contains("duplicate"));
+        assert_eq!(response[3]["ok"]["results"][0]["win_count"], 11);
+    }
+
+    #[test]
+    fn test_batch_preserves_order() {
+        let client = Client::tracked(rocket()).unwrap();
+        // The river, and whether it gives the flush draw the win.
+        let rivers = [
+            ("2d", false),
+            ("3h", true),
+            ("4s", false),
+            ("Tc", true),
+            ("8c", false),
+            ("Qh", true),
+            ("Qd", false),
+            ("As", false),
+        ];
+        let batch: Vec<Value> = rivers
+            .iter()
+            .map(|(river, _)| json!({ "hands": ["AhQc", "KsKd"], "board": format!("2h7hJhKc{river}") }))
+            .collect();
+
+        let (status, response) = post(&client, "/equity/batch", &Value::from(batch));
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+
+        for (i, (river, flush_draw_wins)) in rivers.iter().enumerate() {
+            let winner = usize::from(!flush_draw_wins);
+            assert_eq!(
+                response[i]["ok"]["results"][winner]["win_count"], 1,
+                "wrong winner for river {river}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_equity_breakdown() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post(
+            &client,
+            "/equity/range",
+            &json!({ "hero": "AhKh", "villain": "QQ+,AK", "board": "2c7d9s" }),
+        );
+
+        let response = response.unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(response["method"], "exact");
+
+        let classes: Vec<_> = response["classes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|class| {
+                (
+                    class["hand"].as_str().unwrap(),
+                    class["combos"].as_u64().unwrap(),
+                    class["blocked"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            classes,
+            [
+                ("QQ", 6, 0),
+                ("KK", 6, 3),
+                ("AA", 6, 3),
+                ("AKs", 4, 1),
+                ("AKo", 12, 6),
+            ]
+        );
+
+        // Two overcards are behind every pair, and chop with the other AK.
+        let equity = response["result"]["equity"].as_f64().unwrap();
+        assert!(equity > 0.15 && equity < 0.35, "equity was {equity}");
+        assert!(response["classes"][0]["equity"].as_f64().unwrap() < 0.3);
+        assert!(response["classes"][3]["tie_count"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_range_monte_carlo_is_deterministic() {
+        let client = Client::tracked(rocket()).unwrap();
+        let body = json!({
+            "hero": "AhKh",
+            "villain": "QQ+,AK",
+            "board": "2c7d9s",
+            "mode": "montecarlo",
+            "samples": 2000,
+            "seed": 3,
+        });
+
+        let (status, first) = post(&client, "/equity/range", &body);
+        assert_eq!(status, Status::Ok);
+        let (_, second) = post(&client, "/equity/range", &body);
+
+        let first = first.unwrap();
+        assert_eq!(first["seed"], 3);
+        assert_eq!(
+            first["result"]["win_count"].as_u64().unwrap()
+                + first["result"]["loss_count"].as_u64().unwrap()
+                + first["result"]["tie_count"].as_u64().unwrap(),
+            2000
+        );
+        assert_eq!(Some(first), second);
+    }
+
+    #[test]
+    fn test_range_parse_error_points_at_token() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post(
+            &client,
+            "/equity/range",
+            &json!({ "hero": "AhKh", "villain": "QQ+, AXs", "board": "2c7d9s" }),
+        );
+
+        let response = response.unwrap();
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(response["token"], "AXs");
+        assert_eq!(response["position"], 5);
+    }
+
+    #[test]
+    fn test_wide_exhaustive_range_is_rejected() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, response) = post(
+            &client,
+            "/equity/range",
+            &json!({ "hero": "22+", "villain": "22+" }),
+        );
+
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.unwrap()["error"]
+            .as_str()
+            .unwrap()
+            .contains("montecarlo"));
+    }
+
+    #[test]
+    fn test_batch_size_limit() {
+        let client = Client::tracked(rocket()).unwrap();
+        let scenario = json!({ "hands": ["AsQc", "KhKc"], "board": "AhKd7c7s2h" });
+
+        let (status, _) = post(
+            &client,
+            "/equity/batch",
+            &Value::from(vec![scenario.clone(); DEFAULT_MAX_BATCH_SIZE]),
+        );
+        assert_eq!(status, Status::Ok);
+
+        let (status, _) = post(
+            &client,
+            "/equity/batch",
+            &Value::from(vec![scenario; DEFAULT_MAX_BATCH_SIZE + 1]),
+        );
+        assert_eq!(status, Status::PayloadTooLarge);
+    }
+
+    /// Returns the payload of every event in a server-sent event stream.
+    fn events(body: &str) -> Vec<Value> {
+        body.lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| rocket::serde::json::from_str(data).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_job_streams_progress_until_finished() {
+        let client = Client::tracked(rocket()).unwrap();
+        let scenario = json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJh" });
+        let (_, expected) = post(&client, "/equity", &scenario);
+        let (status, response) = post(&client, "/jobs", &scenario);
+        assert_eq!(status, Status::Ok);
+        let id = &response.unwrap()["id"];
+
+        let body = client
+            .get(format!("/jobs/{id}/events"))
+            .dispatch()
+            .into_string()
+            .unwrap();
+        let events = events(&body);
+        let (last, progress) = events.split_last().unwrap();
+        assert!(progress.iter().all(|event| event["type"] == "progress"
+            && event["total"] == 990
+            && event["done"].as_u64().unwrap() <= 990));
+        assert_eq!(last["type"], "finished");
+        assert_eq!(last["results"], expected.unwrap()["results"]);
+
+        // The job is forgotten once its outcome has been streamed.
+        let response = client.get(format!("/jobs/{id}/events")).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_finished_job_without_listener_expires() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (_, response) = post(
+            &client,
+            "/jobs",
+            &json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc" }),
+        );
+        let id = response.unwrap()["id"].as_u64().unwrap();
+
+        // The job's outcome is forwarded on the server's runtime, which only
+        // runs while a request is being handled.
+        let jobs = client.rocket().state::<Jobs>().unwrap();
+        let job = jobs.jobs.lock().unwrap()[&id].clone();
+        while job.finished.get().is_none() {
+            client.get("/hello").dispatch();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        jobs.remove_expired(Instant::now());
+        assert!(jobs.jobs.lock().unwrap().contains_key(&id));
+        jobs.remove_expired(Instant::now() + JOB_RETENTION);
+        assert!(jobs.jobs.lock().unwrap().is_empty());
+
+        // A listener arriving afterwards finds nothing.
+        let response = client.get(format!("/jobs/{id}/events")).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_job_cancellation() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (_, response) = post(
+            &client,
+            "/jobs",
+            &json!({ "hands": ["AhQc", "KsKd"], "board": "2h7hJhKc" }),
+        );
+        let id = &response.unwrap()["id"];
+
+        let response = client.delete(format!("/jobs/{id}")).dispatch();
+        assert_eq!(response.status(), Status::NoContent);
+        let response = client.delete(format!("/jobs/{id}")).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_invalid_job_is_rejected() {
+        let client = Client::tracked(rocket()).unwrap();
+        let (status, _) = post(&client, "/jobs", &json!({ "hands": ["AhAh", "KsKd"] }));
+        assert_eq!(status, Status::BadRequest);
+    }
 }
@@ -0,0 +1,158 @@
+//! Benchmarks of the ways the calculator is used in practice. Inputs are only
+//! built through the public API, so these also check that it stays pleasant
+//! to use for each of these workflows.
+//!
+//! Run them with `cargo bench`, or `cargo bench -- <name>` for just one.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use poker_calculator::{
+    combine, compute_equity, compute_equity_headsup, compute_equity_memoized, compute_range_equity,
+    enumerate_runouts, evaluate_hand, parse_cards, Card, EquityMethod, EquityRequest, EvalState,
+    Hand, MonteCarlo, Range,
+};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const BATCH_SIZE: usize = 1_000_000;
+
+fn hand(hand: &str) -> [Card; 2] {
+    hand.parse::<Hand>().unwrap().cards()
+}
+
+fn board(board: &str) -> Vec<Card> {
+    parse_cards(board).unwrap()
+}
+
+fn evaluation(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut deck = Card::ALL;
+    let hands: Vec<[Card; 7]> = (0..BATCH_SIZE)
+        .map(|_| {
+            let (cards, _) = deck.partial_shuffle(&mut rng, 7);
+            [
+                cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], cards[6],
+            ]
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("evaluation");
+    group.sample_size(10);
+    group.bench_function("batch of 1M seven card hands", |b| {
+        b.iter(|| {
+            hands
+                .iter()
+                .map(|&cards| evaluate_hand(black_box(cards)))
+                .max()
+        });
+    });
+
+    // Every turn and river after a flop, first counting all seven cards for
+    // each runout, then adding the turn and river to the counts of the flop.
+    let hole = hand("AhQc");
+    let flop: [Card; 3] = board("2h7hJh").try_into().unwrap();
+    let runouts: Vec<[Card; 5]> = enumerate_runouts(flop, &hole).collect();
+    group.bench_function("flop runouts, counting every card", |b| {
+        b.iter(|| {
+            runouts
+                .iter()
+                .map(|&runout| evaluate_hand(combine(black_box(hole), runout)))
+                .max()
+        });
+    });
+    group.bench_function("flop runouts, incremental", |b| {
+        b.iter(|| {
+            let mut state = EvalState::new(&[hole[0], hole[1], flop[0], flop[1], flop[2]]);
+            runouts
+                .iter()
+                .map(|runout| {
+                    state.add_card(black_box(runout[3]));
+                    state.add_card(runout[4]);
+                    let evaluation = state.evaluate();
+                    state.remove_card(runout[4]);
+                    state.remove_card(runout[3]);
+                    evaluation
+                })
+                .max()
+        });
+    });
+    group.finish();
+}
+
+fn exhaustive(c: &mut Criterion) {
+    let preflop = [hand("AsKs"), hand("QdQc")];
+    let flop = [hand("AhQc"), hand("KsKd")];
+    let flop_board = board("2h7hJh");
+
+    let mut group = c.benchmark_group("exhaustive");
+    group.sample_size(10);
+    group.bench_function("heads-up preflop", |b| {
+        b.iter(|| compute_equity(black_box(&preflop), &[], &[]));
+    });
+    group.bench_function("heads-up preflop, fast path", |b| {
+        b.iter(|| compute_equity_headsup(black_box(preflop[0]), black_box(preflop[1])));
+    });
+    group.bench_function("heads-up preflop, memoized", |b| {
+        b.iter(|| compute_equity_memoized(black_box(&preflop), &[], &[]));
+    });
+    group.bench_function("heads-up flop", |b| {
+        b.iter(|| compute_equity(black_box(&flop), &flop_board, &[]));
+    });
+    group.bench_function("three-way flop", |b| {
+        let hands = [hand("AhQc"), hand("KsKd"), hand("9h8h")];
+        b.iter(|| compute_equity(black_box(&hands), &flop_board, &[]));
+    });
+    group.finish();
+}
+
+fn ranges(c: &mut Criterion) {
+    let hero = Range::from(hand("AhKh"));
+    let villain: Range = "22+, A2s+, K9s+, QTs+, JTs, ATo+, KJo+".parse().unwrap();
+    let flop = board("Qh7h2c");
+
+    let mut group = c.benchmark_group("ranges");
+    group.sample_size(10);
+    group.bench_function("hand against a range on the flop", |b| {
+        b.iter(|| {
+            compute_range_equity(
+                &hero,
+                black_box(&villain),
+                &flop,
+                &[],
+                EquityMethod::Exhaustive,
+            )
+        });
+    });
+    group.bench_function("request builder, hand against a range", |b| {
+        b.iter(|| {
+            EquityRequest::builder()
+                .hand("AhKh")
+                .and_then(|builder| builder.hand_range("22+, A2s+, K9s+, QTs+, JTs, ATo+, KJo+"))
+                .and_then(|builder| builder.board("Qh7h2c"))
+                .and_then(|builder| builder.build())
+                .and_then(|request| request.compute())
+        });
+    });
+    group.finish();
+}
+
+fn monte_carlo(c: &mut Criterion) {
+    let hands = [hand("AsKs"), hand("QdQc"), hand("7h6h")];
+
+    let mut group = c.benchmark_group("monte carlo");
+    group.sample_size(10);
+    group.bench_function("three-way preflop, 100k samples", |b| {
+        b.iter_batched(
+            || MonteCarlo::new(&hands, &[], &[], Some(0)).unwrap(),
+            |mut simulation| {
+                simulation.run(100_000);
+                simulation
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, evaluation, exhaustive, ranges, monte_carlo);
+criterion_main!(benches);
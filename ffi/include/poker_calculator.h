@@ -0,0 +1,111 @@
+#ifndef POKER_CALCULATOR_H
+#define POKER_CALCULATOR_H
+
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a call.
+ */
+typedef enum PcStatus {
+  PC_STATUS_OK = 0,
+  /**
+   * A required pointer was null.
+   */
+  PC_STATUS_NULL_POINTER = 1,
+  /**
+   * A card index was 52 or more, or a string wasn't a card.
+   */
+  PC_STATUS_INVALID_CARD = 2,
+  /**
+   * The same card was passed more than once.
+   */
+  PC_STATUS_DUPLICATE_CARD = 3,
+  /**
+   * The board had more than five cards.
+   */
+  PC_STATUS_INVALID_BOARD_SIZE = 4,
+  /**
+   * The wrong number of cards was passed.
+   */
+  PC_STATUS_INVALID_HAND_SIZE = 5,
+  /**
+   * The library panicked, which is a bug in the library.
+   */
+  PC_STATUS_PANIC = 6,
+  /**
+   * The hands and dead cards left too few cards to finish the board.
+   */
+  PC_STATUS_NOT_ENOUGH_CARDS = 7,
+} PcStatus;
+
+/**
+ * The results for one hand.
+ */
+typedef struct PcEquityResult {
+  uint64_t win_count;
+  uint64_t loss_count;
+  uint64_t tie_count;
+  uint64_t count;
+  /**
+   * The share of the pot the hand wins on average, from 0 to 1.
+   */
+  double equity;
+} PcEquityResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses a card such as `"As"`, writing its index to `out`.
+ *
+ * # Safety
+ *
+ * `card` must be a NUL-terminated string, and `out` must be valid for
+ * writing.
+ */
+PcStatus pc_parse_card(const char *card, uint8_t *out);
+
+/**
+ * Evaluates exactly seven cards, writing a rank to `out_eval` which is higher
+ * for stronger hands and equal for hands which tie.
+ *
+ * # Safety
+ *
+ * `cards` must point to `n` readable bytes, and `out_eval` must be valid for
+ * writing.
+ */
+PcStatus pc_evaluate(const uint8_t *cards, size_t n, uint32_t *out_eval);
+
+/**
+ * Computes the exact equity of `hand_count` hands, by enumerating every
+ * runout.
+ *
+ * The hands are read two cards at a time from `hands`, and one result per
+ * hand is written to `out_results`, in the same order.
+ *
+ * # Safety
+ *
+ * `hands` must point to `2 * hand_count` readable bytes, `board` to
+ * `board_len` and `dead` to `dead_len`, where any of them may be null when
+ * their length is zero. `out_results` must be valid for writing `hand_count`
+ * results.
+ */
+PcStatus pc_compute_equity(const uint8_t *hands,
+                           size_t hand_count,
+                           const uint8_t *board,
+                           size_t board_len,
+                           const uint8_t *dead,
+                           size_t dead_len,
+                           PcEquityResult *out_results);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* POKER_CALCULATOR_H */
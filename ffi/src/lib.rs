@@ -0,0 +1,308 @@
+//! A C interface to the hand evaluator and the equity engine, for calling them
+//! from C or C++. The declarations live in `include/poker_calculator.h`, which
+//! is regenerated with `cbindgen --config cbindgen.toml --output
+//! include/poker_calculator.h`.
+//!
+//! Cards are passed as their index from 0 to 51: the suit, counting hearts,
+//! diamonds, clubs and spades from 0, times 13, plus the number, counting twos
+//! up to aces from 0. Every function returns a [`PcStatus`] rather than
+//! panicking, since unwinding into C is undefined behavior.
+
+use poker_calculator::{compute_equity, evaluate_hand, Card, Hand, PokerError};
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, UnwindSafe};
+
+/// The outcome of a call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PcStatus {
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// A card index was 52 or more, or a string wasn't a card.
+    InvalidCard = 2,
+    /// The same card was passed more than once.
+    DuplicateCard = 3,
+    /// The board had more than five cards.
+    InvalidBoardSize = 4,
+    /// The wrong number of cards was passed.
+    InvalidHandSize = 5,
+    /// The library panicked, which is a bug in the library.
+    Panic = 6,
+    /// The hands and dead cards left too few cards to finish the board.
+    NotEnoughCards = 7,
+}
+
+impl From<PokerError> for PcStatus {
+    fn from(error: PokerError) -> Self {
+        match error {
+            // Ranges and hand histories aren't exposed, so only cards can
+            // fail to parse.
+            PokerError::InvalidCard(_)
+            | PokerError::InvalidSuit(_)
+            | PokerError::InvalidNumber(_)
+            | PokerError::InvalidCardIndex(_)
+            | PokerError::InvalidRange { .. }
+            | PokerError::InvalidHistory { .. } => Self::InvalidCard,
+            PokerError::DuplicateCard(_) => Self::DuplicateCard,
+            PokerError::InvalidBoardSize(_) => Self::InvalidBoardSize,
+            PokerError::NotEnoughCards { .. } => Self::NotEnoughCards,
+            // Running a board more than once, cancelling and requests aren't
+            // exposed either, so only a hand of the wrong size can fail.
+            PokerError::InvalidHandSize { .. }
+            | PokerError::Cancelled
+            | PokerError::InvalidRequest(_) => Self::InvalidHandSize,
+        }
+    }
+}
+
+/// The results for one hand.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PcEquityResult {
+    pub win_count: u64,
+    pub loss_count: u64,
+    pub tie_count: u64,
+    pub count: u64,
+    /// The share of the pot the hand wins on average, from 0 to 1.
+    pub equity: f64,
+}
+
+/// Reads `len` card indices, rejecting any which aren't cards.
+///
+/// # Safety
+///
+/// `cards` must point to `len` readable bytes, unless `len` is zero, in which
+/// case it may be null.
+unsafe fn read_cards(cards: *const u8, len: usize) -> Result<Vec<Card>, PcStatus> {
+    if len == 0 {
+        return Ok(vec![]);
+    }
+    if cards.is_null() {
+        return Err(PcStatus::NullPointer);
+    }
+    std::slice::from_raw_parts(cards, len)
+        .iter()
+        .map(|&index| Card::try_from_index(index).ok_or(PcStatus::InvalidCard))
+        .collect()
+}
+
+/// Runs the body of an exported function, turning a panic into
+/// [`PcStatus::Panic`].
+fn guard(body: impl FnOnce() -> Result<(), PcStatus> + UnwindSafe) -> PcStatus {
+    match catch_unwind(body) {
+        Ok(Ok(())) => PcStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => PcStatus::Panic,
+    }
+}
+
+/// Parses a card such as `"As"`, writing its index to `out`.
+///
+/// # Safety
+///
+/// `card` must be a NUL-terminated string, and `out` must be valid for
+/// writing.
+#[no_mangle]
+pub unsafe extern "C" fn pc_parse_card(card: *const c_char, out: *mut u8) -> PcStatus {
+    guard(|| {
+        if card.is_null() || out.is_null() {
+            return Err(PcStatus::NullPointer);
+        }
+        let card = CStr::from_ptr(card)
+            .to_str()
+            .map_err(|_| PcStatus::InvalidCard)?;
+        *out = card.parse::<Card>()?.to_index();
+        Ok(())
+    })
+}
+
+/// Evaluates exactly seven cards, writing a rank to `out_eval` which is higher
+/// for stronger hands and equal for hands which tie.
+///
+/// # Safety
+///
+/// `cards` must point to `n` readable bytes, and `out_eval` must be valid for
+/// writing.
+#[no_mangle]
+pub unsafe extern "C" fn pc_evaluate(cards: *const u8, n: usize, out_eval: *mut u32) -> PcStatus {
+    guard(|| {
+        if out_eval.is_null() {
+            return Err(PcStatus::NullPointer);
+        }
+        let cards = Hand::try_from_iter(read_cards(cards, n)?)?;
+        *out_eval = evaluate_hand(cards).rank();
+        Ok(())
+    })
+}
+
+/// Computes the exact equity of `hand_count` hands, by enumerating every
+/// runout.
+///
+/// The hands are read two cards at a time from `hands`, and one result per
+/// hand is written to `out_results`, in the same order.
+///
+/// # Safety
+///
+/// `hands` must point to `2 * hand_count` readable bytes, `board` to
+/// `board_len` and `dead` to `dead_len`, where any of them may be null when
+/// their length is zero. `out_results` must be valid for writing `hand_count`
+/// results.
+#[no_mangle]
+pub unsafe extern "C" fn pc_compute_equity(
+    hands: *const u8,
+    hand_count: usize,
+    board: *const u8,
+    board_len: usize,
+    dead: *const u8,
+    dead_len: usize,
+    out_results: *mut PcEquityResult,
+) -> PcStatus {
+    guard(|| {
+        if hand_count > 0 && out_results.is_null() {
+            return Err(PcStatus::NullPointer);
+        }
+        let card_count = hand_count.checked_mul(2).ok_or(PcStatus::InvalidHandSize)?;
+        let hands: Vec<[Card; 2]> = read_cards(hands, card_count)?
+            .chunks(2)
+            .map(|hand| [hand[0], hand[1]])
+            .collect();
+        let board = read_cards(board, board_len)?;
+        let dead = read_cards(dead, dead_len)?;
+
+        let results = compute_equity(&hands, &board, &dead)?;
+        for (index, result) in results.iter().enumerate() {
+            out_results.add(index).write(PcEquityResult {
+                win_count: result.win_count,
+                loss_count: result.loss_count,
+                tie_count: result.tie_count,
+                count: result.count,
+                equity: result.equity(),
+            });
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    fn parse(card: &str) -> Result<u8, PcStatus> {
+        let card = CString::new(card).unwrap();
+        let mut index = 0;
+        match unsafe { pc_parse_card(card.as_ptr(), &mut index) } {
+            PcStatus::Ok => Ok(index),
+            status => Err(status),
+        }
+    }
+
+    fn indices(cards: &str) -> Vec<u8> {
+        poker_calculator::parse_cards(cards)
+            .unwrap()
+            .into_iter()
+            .map(Card::to_index)
+            .collect()
+    }
+
+    fn evaluate(cards: &[u8]) -> Result<u32, PcStatus> {
+        let mut rank = 0;
+        match unsafe { pc_evaluate(cards.as_ptr(), cards.len(), &mut rank) } {
+            PcStatus::Ok => Ok(rank),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(parse("2h"), Ok(0));
+        assert_eq!(parse("Ah"), Ok(12));
+        assert_eq!(parse("2d"), Ok(13));
+        assert_eq!(parse("As"), Ok(51));
+        assert_eq!(parse("Xx"), Err(PcStatus::InvalidCard));
+        assert_eq!(
+            unsafe { pc_parse_card(std::ptr::null(), &mut 0) },
+            PcStatus::NullPointer
+        );
+        assert_eq!(evaluate(&[52]), Err(PcStatus::InvalidCard));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let royal_flush = evaluate(&indices("AcKcQcJcTc8h5h")).unwrap();
+        let pair = evaluate(&indices("AcAdKcTd9c8c6h")).unwrap();
+        assert!(royal_flush > pair);
+        assert_eq!(evaluate(&indices("AhAsKdTc9d8h6c")), Ok(pair));
+
+        assert_eq!(
+            evaluate(&indices("AcKcQcJcTc8h")),
+            Err(PcStatus::InvalidHandSize)
+        );
+        assert_eq!(
+            evaluate(&indices("AcKcQcJcTc8hAc")),
+            Err(PcStatus::DuplicateCard)
+        );
+        assert_eq!(
+            evaluate(&[0, 1, 2, 3, 4, 5, 52]),
+            Err(PcStatus::InvalidCard)
+        );
+    }
+
+    #[test]
+    fn test_compute_equity() {
+        let hands = indices("AhQcKsKd");
+        let board = indices("2h7hJhKc");
+        let mut results = [PcEquityResult::default(); 2];
+        let status = unsafe {
+            pc_compute_equity(
+                hands.as_ptr(),
+                2,
+                board.as_ptr(),
+                board.len(),
+                std::ptr::null(),
+                0,
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, PcStatus::Ok);
+        assert_eq!(results[0].win_count, 11);
+        assert_eq!(results[1].win_count, 33);
+        assert_eq!(results[0].count, 44);
+
+        let board = indices("2h7hJhAh");
+        let status = unsafe {
+            pc_compute_equity(
+                hands.as_ptr(),
+                2,
+                board.as_ptr(),
+                board.len(),
+                std::ptr::null(),
+                0,
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, PcStatus::DuplicateCard);
+
+        // Twenty four hands leave four cards, which can't make a board.
+        let hands: Vec<u8> = (0..48).collect();
+        let mut results = [PcEquityResult::default(); 24];
+        let status = unsafe {
+            pc_compute_equity(
+                hands.as_ptr(),
+                24,
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+                0,
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, PcStatus::NotEnoughCards);
+    }
+
+    #[test]
+    fn test_panics_are_caught() {
+        assert_eq!(guard(|| panic!("a bug")), PcStatus::Panic);
+    }
+}
@@ -4,38 +4,635 @@
 #![allow(clippy::wildcard_imports)]
 #![allow(clippy::future_not_send)]
 
+use poker_calculator_wasm::{EquitySimulation, Scenario};
 use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+use share::SharedScenario;
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+
+mod range_matrix;
+mod share;
+
+const JOBS_URL: &str = "http://localhost:8000/jobs";
+const RANGE_EQUITY_URL: &str = "http://localhost:8000/equity/range";
+
+const NUMBERS: [char; 13] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+const SUITS: [char; 4] = ['s', 'h', 'd', 'c'];
+
+/// The most hands the calculator lets the user add.
+const MAX_HANDS: usize = 9;
+
+/// The most runouts an exact answer enumerates in the browser before handing
+/// the job to the backend.
+const LOCAL_EXHAUSTIVE_RUNOUTS: u64 = 50_000;
+
+/// How many runouts a simulation in the browser deals in total.
+const SIMULATION_SAMPLES: u64 = 200_000;
+
+/// How many runouts are dealt before yielding back to the browser.
+const SIMULATION_CHUNK: u32 = 5_000;
 
 // ------ ------
 //     Init
 // ------ ------
 
-async fn fetch_from_backend() -> fetch::Result<String> {
-    Request::new("http://localhost:8000/hello")
-        .fetch()
-        .await?
-        .check_status()?
-        .text()
-        .await
-}
-
 // `init` describes what should happen when your app started.
-fn init(_: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.perform_cmd(async { Msg::Fetched(fetch_from_backend().await) });
-    Model {
-        text: "default".into(),
-        counter: 0,
-    }
+fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
+    let mut model = Model {
+        hands: vec![vec![], vec![]],
+        flop: vec![],
+        turn: vec![],
+        river: vec![],
+        dead: vec![],
+        used: HashSet::new(),
+        active: Slot::Hand(0),
+        exact: false,
+        in_flight: false,
+        generation: 0,
+        progress: None,
+        simulation: None,
+        job: None,
+        result: None,
+        note: None,
+        error: None,
+        range_matrix: range_matrix::Model::default(),
+        range_in_flight: false,
+        range_result: None,
+        range_error: None,
+        shared_params: vec![],
+    };
+
+    // Open with the scenario from a shared link, if there is one.
+    let shared = share::decode(|key| url.search().get(key)?.first().cloned());
+    model.load(&shared, orders);
+    model.shared_params = share::encode(&model.shared());
+    model
 }
 
 // ------ ------
 //     Model
 // ------ ------
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Card {
+    number: char,
+    suit: char,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.number, self.suit)
+    }
+}
+
+impl From<poker_calculator::Card> for Card {
+    fn from(card: poker_calculator::Card) -> Self {
+        Self {
+            number: card.rank_char(),
+            suit: card.suit_char(),
+        }
+    }
+}
+
+/// A computation running on the backend, whose progress streams in as
+/// server-sent events.
+struct Job {
+    id: u64,
+    events: web_sys::EventSource,
+    // The event source calls these, so they must live as long as it does.
+    _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+    _on_error: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Job {
+    /// Subscribes to the events of a job which the backend has started.
+    fn listen(id: u64, orders: &mut impl Orders<Msg>) -> Result<Self, String> {
+        let events = web_sys::EventSource::new(&format!("{JOBS_URL}/{id}/events"))
+            .map_err(|error| format!("could not reach the backend: {error:?}"))?;
+
+        // Messages are sent from a fresh task rather than from inside the
+        // callback, since handling them may drop the callback.
+        let send = orders.msg_sender();
+        let post = move |msg: Msg| {
+            let send = Rc::clone(&send);
+            wasm_bindgen_futures::spawn_local(async move { send(Some(msg)) });
+        };
+
+        let on_message = {
+            let post = post.clone();
+            Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
+                let status = event
+                    .data()
+                    .as_string()
+                    .and_then(|data| serde_json::from_str::<JobStatus>(&data).ok());
+                post(status.map_or_else(
+                    || Msg::JobFailed(id, "the backend sent a malformed event".into()),
+                    |status| status.into_msg(id),
+                ));
+            }) as Box<dyn FnMut(_)>)
+        };
+        let on_error = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            post(Msg::ConnectionLost(id));
+        }) as Box<dyn FnMut(_)>);
+
+        events.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        events.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        Ok(Self {
+            id,
+            events,
+            _on_message: on_message,
+            _on_error: on_error,
+        })
+    }
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        self.events.set_onmessage(None);
+        self.events.set_onerror(None);
+        self.events.close();
+    }
+}
+
+/// A group of cards the user can pick into.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Hand(usize),
+    Flop,
+    Turn,
+    River,
+    Dead,
+}
+
 // `Model` describes our app state.
 struct Model {
-    text: String,
-    counter: i32,
+    hands: Vec<Vec<Card>>,
+    flop: Vec<Card>,
+    turn: Vec<Card>,
+    river: Vec<Card>,
+    dead: Vec<Card>,
+    /// Every card in any slot. Cards are only added to or removed from slots
+    /// through [`Model::pick`] and [`Model::release`], which keep this in step,
+    /// so no card can ever be in two slots.
+    used: HashSet<Card>,
+    /// The slot which clicking a card adds to.
+    active: Slot,
+    /// Whether to enumerate every runout rather than sampling them.
+    exact: bool,
+    in_flight: bool,
+    /// Counts the computations started, so that messages about one which has
+    /// since been replaced or cancelled can be told apart and ignored.
+    generation: u32,
+    /// How much of the computation in flight is done, out of how much.
+    progress: Option<(u64, u64)>,
+    /// The simulation being run in the browser, a chunk at a time.
+    simulation: Option<EquitySimulation>,
+    /// The computation running on the backend.
+    job: Option<Job>,
+    result: Option<EquityResponse>,
+    /// Describes how the result was computed.
+    note: Option<String>,
+    error: Option<String>,
+    /// The villain range which the first hand is compared against.
+    range_matrix: range_matrix::Model,
+    range_in_flight: bool,
+    range_result: Option<RangeEquityResponse>,
+    range_error: Option<String>,
+    /// The parameters last written to the URL.
+    shared_params: Vec<(String, String)>,
+}
+
+impl Model {
+    fn slot(&self, slot: Slot) -> &[Card] {
+        match slot {
+            Slot::Hand(index) => &self.hands[index],
+            Slot::Flop => &self.flop,
+            Slot::Turn => &self.turn,
+            Slot::River => &self.river,
+            Slot::Dead => &self.dead,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: Slot) -> &mut Vec<Card> {
+        match slot {
+            Slot::Hand(index) => &mut self.hands[index],
+            Slot::Flop => &mut self.flop,
+            Slot::Turn => &mut self.turn,
+            Slot::River => &mut self.river,
+            Slot::Dead => &mut self.dead,
+        }
+    }
+
+    const fn capacity(slot: Slot) -> usize {
+        match slot {
+            Slot::Hand(_) => 2,
+            Slot::Flop => 3,
+            Slot::Turn | Slot::River => 1,
+            Slot::Dead => usize::MAX,
+        }
+    }
+
+    fn is_used(&self, card: Card) -> bool {
+        self.used.contains(&card)
+    }
+
+    /// Adds a card to a slot, unless it is already used somewhere or the slot
+    /// is full.
+    fn pick(&mut self, slot: Slot, card: Card) -> bool {
+        if self.is_used(card) || self.slot(slot).len() >= Self::capacity(slot) {
+            return false;
+        }
+        self.slot_mut(slot).push(card);
+        self.used.insert(card);
+        true
+    }
+
+    /// Removes a card from a slot, freeing it to be picked anywhere.
+    fn release(&mut self, slot: Slot, card: Card) {
+        let cards = self.slot_mut(slot);
+        if let Some(index) = cards.iter().position(|&c| c == card) {
+            cards.remove(index);
+            self.used.remove(&card);
+        }
+    }
+
+    fn clear_slot(&mut self, slot: Slot) {
+        for card in std::mem::take(self.slot_mut(slot)) {
+            self.used.remove(&card);
+        }
+    }
+
+    fn board(&self) -> Vec<Card> {
+        [&self.flop, &self.turn, &self.river]
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    fn hand_errors(&self) -> Vec<String> {
+        self.hands
+            .iter()
+            .enumerate()
+            .filter(|(_, hand)| hand.len() < 2)
+            .map(|(index, _)| format!("Hand {} needs two cards", index + 1))
+            .collect()
+    }
+
+    fn board_errors(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if !matches!(self.board().len(), 0 | 3 | 4 | 5) {
+            errors.push("The board must have 0, 3, 4, or 5 cards".into());
+        }
+        if !self.turn.is_empty() && self.flop.len() < 3 {
+            errors.push("The turn can't be dealt before the whole flop".into());
+        }
+        if !self.river.is_empty() && self.turn.is_empty() {
+            errors.push("The river can't be dealt before the turn".into());
+        }
+        errors
+    }
+
+    /// Only a coherent scenario is sent, so an incomplete one disables
+    /// computing rather than producing a request the backend would reject.
+    fn can_compute(&self) -> bool {
+        self.hand_errors().is_empty() && self.board_errors().is_empty()
+    }
+
+    fn shared(&self) -> SharedScenario {
+        let cards = |cards: &[Card]| {
+            cards
+                .iter()
+                .filter_map(|card| card.to_string().parse().ok())
+                .collect()
+        };
+        SharedScenario {
+            hands: self.hands.iter().map(|hand| cards(hand)).collect(),
+            board: cards(&self.board()),
+            dead: cards(&self.dead),
+            range: self.range_matrix.notation().map(Into::into),
+            exact: self.exact,
+            compute: self.result.is_some(),
+        }
+    }
+
+    /// Picks the cards of a shared scenario. Cards which are already used, or
+    /// which don't fit in their slot, are left out.
+    fn load(&mut self, shared: &SharedScenario, orders: &mut impl Orders<Msg>) {
+        self.hands = vec![vec![]; shared.hands.len().min(MAX_HANDS)];
+        for (index, hand) in shared.hands.iter().take(MAX_HANDS).enumerate() {
+            for &card in hand {
+                self.pick(Slot::Hand(index), card.into());
+            }
+        }
+        for (index, &card) in shared.board.iter().enumerate() {
+            let street = match index {
+                0..=2 => Slot::Flop,
+                3 => Slot::Turn,
+                _ => Slot::River,
+            };
+            self.pick(street, card.into());
+        }
+        for &card in &shared.dead {
+            self.pick(Slot::Dead, card.into());
+        }
+
+        if let Some(range) = &shared.range {
+            range_matrix::update(
+                range_matrix::Msg::TextChanged(range.clone()),
+                &mut self.range_matrix,
+                &mut orders.proxy(Msg::RangeMatrix),
+            );
+        }
+        self.exact = shared.exact;
+        self.advance();
+        if shared.compute {
+            orders.send_msg(Msg::Compute);
+        }
+    }
+
+    /// Keeps the URL describing the current scenario, so that it can be
+    /// copied and shared at any time. The history entry is replaced rather
+    /// than pushed, so the back button still leaves the page.
+    fn update_url(&mut self) {
+        let params = share::encode(&self.shared());
+        if params != self.shared_params {
+            Url::current()
+                .set_search(UrlSearch::new(
+                    params
+                        .iter()
+                        .map(|(key, value)| (key.as_str(), vec![value.as_str()])),
+                ))
+                .go_and_replace();
+            self.shared_params = params;
+        }
+    }
+
+    fn request(&self) -> EquityRequest {
+        let join = |cards: &[Card]| cards.iter().map(ToString::to_string).collect::<String>();
+        EquityRequest {
+            hands: self.hands.iter().map(|hand| join(hand)).collect(),
+            board: join(&self.board()),
+            dead: join(&self.dead),
+        }
+    }
+
+    fn can_compute_range(&self) -> bool {
+        !self.range_in_flight
+            && self.hands[0].len() == 2
+            && self.board_errors().is_empty()
+            && self.range_matrix.notation().is_some()
+    }
+
+    fn range_request(&self) -> RangeEquityRequest {
+        let request = self.request();
+        RangeEquityRequest {
+            hero: request.hands[0].clone(),
+            villain: self.range_matrix.notation().unwrap_or_default().into(),
+            board: request.board,
+            dead: request.dead,
+            mode: if self.exact {
+                "exhaustive"
+            } else {
+                "montecarlo"
+            },
+        }
+    }
+
+    fn is_job(&self, id: u64) -> bool {
+        self.job.as_ref().is_some_and(|job| job.id == id)
+    }
+
+    /// Stops the computation in flight, cancelling it on the backend if it is
+    /// running there, but keeps whatever result it has reached so far.
+    fn stop(&mut self, orders: &mut impl Orders<Msg>) {
+        if let Some(job) = self.job.take() {
+            orders.perform_cmd(cancel_job(job.id));
+        }
+        self.generation += 1;
+        self.simulation = None;
+        self.progress = None;
+        self.in_flight = false;
+    }
+
+    /// Forgets the last result, and stops any computation of it.
+    fn clear_result(&mut self, orders: &mut impl Orders<Msg>) {
+        self.stop(orders);
+        self.result = None;
+        self.note = None;
+    }
+
+    /// Ends a job which the backend reported on, without cancelling it there.
+    fn finish_job(&mut self) {
+        self.job = None;
+        self.progress = None;
+        self.in_flight = false;
+    }
+
+    /// Computes in the browser when that is quick enough, and otherwise sends
+    /// the request to the backend.
+    fn compute(&mut self, orders: &mut impl Orders<Msg>) -> Result<(), String> {
+        let request = self.request();
+        let scenario = Scenario::parse(&request.hands, &request.board, &request.dead)
+            .map_err(|error| error.to_string())?;
+
+        if !self.exact {
+            self.simulation = Some(scenario.simulate(None).map_err(|error| error.to_string())?);
+            self.in_flight = true;
+            orders.send_msg(Msg::Step(self.generation));
+        } else if scenario.runout_count().map_err(|error| error.to_string())?
+            <= LOCAL_EXHAUSTIVE_RUNOUTS
+        {
+            let results = scenario.exact_equity().map_err(|error| error.to_string())?;
+            self.result = Some(EquityResponse::from_local(&results));
+            self.note = Some("Exact, computed in the browser".into());
+        } else {
+            self.in_flight = true;
+            let generation = self.generation;
+            orders
+                .perform_cmd(async move { Msg::JobStarted(generation, start_job(&request).await) });
+        }
+        Ok(())
+    }
+
+    /// Moves on to the next hand which still needs cards, if there is one.
+    fn advance(&mut self) {
+        if let Some(index) = self.hands.iter().position(|hand| hand.len() < 2) {
+            self.active = Slot::Hand(index);
+        }
+    }
+
+    /// Moves on from a slot once it is full, to the next hand which needs
+    /// cards or the next street of the board.
+    fn advance_from(&mut self, slot: Slot) {
+        if self.slot(slot).len() < Self::capacity(slot) {
+            return;
+        }
+        match slot {
+            Slot::Hand(_) => self.advance(),
+            Slot::Flop => self.active = Slot::Turn,
+            Slot::Turn => self.active = Slot::River,
+            Slot::River | Slot::Dead => {}
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EquityRequest {
+    hands: Vec<String>,
+    board: String,
+    dead: String,
+}
+
+#[derive(Deserialize)]
+struct HandEquity {
+    win_count: u64,
+    loss_count: u64,
+    tie_count: u64,
+    equity: f64,
+}
+
+#[derive(Deserialize)]
+struct EquityResponse {
+    results: Vec<HandEquity>,
+}
+
+impl EquityResponse {
+    fn from_local(results: &[poker_calculator_wasm::HandEquity]) -> Self {
+        Self {
+            results: results
+                .iter()
+                .map(|result| HandEquity {
+                    win_count: result.win_count,
+                    loss_count: result.loss_count,
+                    tie_count: result.tie_count,
+                    equity: result.equity,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JobCreated {
+    id: u64,
+}
+
+/// An event from a job's stream.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JobStatus {
+    Progress {
+        done: u64,
+        total: u64,
+        results: Vec<HandEquity>,
+    },
+    Finished {
+        results: Vec<HandEquity>,
+    },
+    Failed {
+        error: String,
+    },
+    Cancelled,
+}
+
+impl JobStatus {
+    fn into_msg(self, id: u64) -> Msg {
+        match self {
+            Self::Progress {
+                done,
+                total,
+                results,
+            } => Msg::JobProgress {
+                id,
+                done,
+                total,
+                results,
+            },
+            Self::Finished { results } => Msg::JobFinished(id, results),
+            Self::Failed { error } => Msg::JobFailed(id, error),
+            Self::Cancelled => Msg::JobCancelled(id),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RangeEquityRequest {
+    hero: String,
+    villain: String,
+    board: String,
+    dead: String,
+    mode: &'static str,
+}
+
+#[derive(Deserialize)]
+struct ClassEquity {
+    hand: String,
+    equity: f64,
+}
+
+#[derive(Deserialize)]
+struct RangeEquityResponse {
+    result: HandEquity,
+    classes: Vec<ClassEquity>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+async fn fetch_range_equity(request: RangeEquityRequest) -> Result<RangeEquityResponse, String> {
+    let response = Request::new(RANGE_EQUITY_URL)
+        .method(fetch::Method::Post)
+        .json(&request)
+        .map_err(|error| format!("{error:?}"))?
+        .fetch()
+        .await
+        .map_err(|error| format!("could not reach the backend: {error:?}"))?;
+
+    if !response.status().is_ok() {
+        return Err(response.json::<ErrorResponse>().await.map_or_else(
+            |_| "the backend rejected the request".into(),
+            |response| response.error,
+        ));
+    }
+    response.json().await.map_err(|error| format!("{error:?}"))
+}
+
+async fn start_job(request: &EquityRequest) -> Result<u64, String> {
+    let response = Request::new(JOBS_URL)
+        .method(fetch::Method::Post)
+        .json(request)
+        .map_err(|error| format!("{error:?}"))?
+        .fetch()
+        .await
+        .map_err(|error| format!("could not reach the backend: {error:?}"))?;
+
+    if !response.status().is_ok() {
+        return Err(response.json::<ErrorResponse>().await.map_or_else(
+            |_| "the backend rejected the request".into(),
+            |response| response.error,
+        ));
+    }
+    let job: JobCreated = response
+        .json()
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    Ok(job.id)
+}
+
+/// Asks the backend to stop a job. Nothing is listening for its outcome any
+/// more, so failures are ignored.
+async fn cancel_job(id: u64) {
+    let _ = Request::new(format!("{JOBS_URL}/{id}"))
+        .method(fetch::Method::Delete)
+        .fetch()
+        .await;
 }
 
 // ------ ------
@@ -44,18 +641,204 @@ struct Model {
 
 // `Msg` describes the different events you can modify state with.
 enum Msg {
-    Increment,
-    Fetched(fetch::Result<String>),
+    SelectSlot(Slot),
+    ToggleCard(Card),
+    ClearSlot(Slot),
+    AddHand,
+    RemoveHand(usize),
+    ToggleExact,
+    Compute,
+    Cancel,
+    Step(u32),
+    JobStarted(u32, Result<u64, String>),
+    JobProgress {
+        id: u64,
+        done: u64,
+        total: u64,
+        results: Vec<HandEquity>,
+    },
+    JobFinished(u64, Vec<HandEquity>),
+    JobFailed(u64, String),
+    JobCancelled(u64),
+    ConnectionLost(u64),
+    RangeMatrix(range_matrix::Msg),
+    ComputeRange,
+    RangeComputed(Result<RangeEquityResponse, String>),
 }
 
 // `update` describes how to handle each `Msg`.
-fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    handle(msg, model, orders);
+    model.update_url();
+}
+
+fn handle(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
-        Msg::Increment => model.counter += 1,
-        Msg::Fetched(Ok(text)) => {
-            model.text = text;
+        Msg::SelectSlot(slot) => model.active = slot,
+        Msg::ToggleCard(card) => {
+            let active = model.active;
+            if model.slot(active).contains(&card) {
+                model.release(active, card);
+            } else if model.pick(active, card) {
+                model.advance_from(active);
+            }
+            model.clear_result(orders);
+        }
+        Msg::ClearSlot(slot) => {
+            model.clear_slot(slot);
+            model.active = slot;
+            model.clear_result(orders);
+        }
+        Msg::AddHand => {
+            if model.hands.len() < MAX_HANDS {
+                model.hands.push(vec![]);
+                model.active = Slot::Hand(model.hands.len() - 1);
+                model.clear_result(orders);
+            }
+        }
+        Msg::RemoveHand(index) => {
+            if model.hands.len() > 2 {
+                model.clear_slot(Slot::Hand(index));
+                model.hands.remove(index);
+                model.active = Slot::Hand(0);
+                model.advance();
+                model.clear_result(orders);
+            }
+        }
+        Msg::ToggleExact => {
+            model.exact = !model.exact;
+            model.clear_result(orders);
+        }
+        Msg::Compute => {
+            // Starting again replaces any computation still in flight.
+            if model.can_compute() {
+                model.clear_result(orders);
+                model.error = model.compute(orders).err();
+            }
+        }
+        Msg::Cancel => {
+            if model.in_flight {
+                model.stop(orders);
+                if model.result.is_some() {
+                    model.note = Some("Cancelled, so this result is incomplete".into());
+                }
+            }
+        }
+        Msg::Step(generation) => {
+            if generation != model.generation {
+                return;
+            }
+            let Some(simulation) = &mut model.simulation else {
+                return;
+            };
+            simulation.run(SIMULATION_CHUNK);
+            model.result = Some(EquityResponse::from_local(&simulation.equities()));
+            model.note = Some(format!("Monte Carlo, {} samples", simulation.samples()));
+            model.progress = Some((simulation.samples(), SIMULATION_SAMPLES));
+
+            if simulation.samples() < SIMULATION_SAMPLES {
+                // Give the browser a chance to render and handle input before
+                // the next chunk.
+                orders.perform_cmd(cmds::timeout(0, move || Msg::Step(generation)));
+            } else {
+                model.simulation = None;
+                model.progress = None;
+                model.in_flight = false;
+            }
+        }
+        Msg::JobStarted(generation, result) => match result {
+            // The cards changed, or the computation was cancelled or
+            // restarted, while the backend was starting the job.
+            Ok(id) if generation != model.generation => {
+                orders.perform_cmd(cancel_job(id));
+            }
+            Ok(id) => match Job::listen(id, orders) {
+                Ok(job) => model.job = Some(job),
+                Err(error) => {
+                    model.stop(orders);
+                    orders.perform_cmd(cancel_job(id));
+                    model.error = Some(error);
+                }
+            },
+            Err(error) if generation == model.generation => {
+                model.stop(orders);
+                model.error = Some(error);
+            }
+            Err(_) => {}
+        },
+        Msg::JobProgress {
+            id,
+            done,
+            total,
+            results,
+        } => {
+            if model.is_job(id) {
+                model.progress = Some((done, total));
+                model.result = Some(EquityResponse { results });
+                model.note = Some(format!(
+                    "Exact, {done} of {total} runouts enumerated by the backend so far"
+                ));
+            }
+        }
+        Msg::JobFinished(id, results) => {
+            if model.is_job(id) {
+                model.finish_job();
+                model.result = Some(EquityResponse { results });
+                model.note = Some("Exact, computed by the backend".into());
+            }
+        }
+        Msg::JobFailed(id, error) => {
+            if model.is_job(id) {
+                model.finish_job();
+                model.error = Some(error);
+            }
+        }
+        Msg::JobCancelled(id) => {
+            if model.is_job(id) {
+                model.finish_job();
+                model.note = Some("Cancelled by the backend, so this result is incomplete".into());
+            }
+        }
+        Msg::ConnectionLost(id) => {
+            if model.is_job(id) {
+                model.finish_job();
+                model.error = Some("Lost the connection to the backend while computing".into());
+            }
+        }
+        Msg::RangeMatrix(msg) => {
+            range_matrix::update(
+                msg,
+                &mut model.range_matrix,
+                &mut orders.proxy(Msg::RangeMatrix),
+            );
+            model.range_result = None;
+        }
+        Msg::ComputeRange => {
+            if model.can_compute_range() {
+                model.range_in_flight = true;
+                model.range_error = None;
+                let request = model.range_request();
+                orders.perform_cmd(
+                    async move { Msg::RangeComputed(fetch_range_equity(request).await) },
+                );
+            }
+        }
+        Msg::RangeComputed(result) => {
+            model.range_in_flight = false;
+            match result {
+                Ok(response) => {
+                    model.range_matrix.set_equities(
+                        response
+                            .classes
+                            .iter()
+                            .map(|class| (class.hand.clone(), class.equity))
+                            .collect(),
+                    );
+                    model.range_result = Some(response);
+                }
+                Err(error) => model.range_error = Some(error),
+            }
         }
-        Msg::Fetched(Err(_)) => model.text = "error".into(),
     }
 }
 
@@ -63,13 +846,217 @@ fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
 //     View
 // ------ ------
 
+fn view_slot(model: &Model, slot: Slot, label: String) -> Node<Msg> {
+    let cards = model.slot(slot);
+    div![
+        C!["slot", IF!(model.active == slot => "active")],
+        ev(Ev::Click, move |_| Msg::SelectSlot(slot)),
+        span![C!["slot-label"], label],
+        span![
+            C!["slot-cards"],
+            if cards.is_empty() {
+                "—".to_string()
+            } else {
+                cards
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        ],
+        button![
+            "Clear",
+            ev(Ev::Click, move |event| {
+                event.stop_propagation();
+                Msg::ClearSlot(slot)
+            }),
+        ],
+        if let Slot::Hand(index) = slot {
+            button![
+                "Remove",
+                attrs! {At::Disabled => (model.hands.len() <= 2).as_at_value()},
+                ev(Ev::Click, move |event| {
+                    event.stop_propagation();
+                    Msg::RemoveHand(index)
+                }),
+            ]
+        } else {
+            empty![]
+        },
+    ]
+}
+
+fn view_card_grid(model: &Model) -> Node<Msg> {
+    let active = model.slot(model.active);
+    div![
+        C!["card-grid"],
+        SUITS.iter().map(|&suit| {
+            div![
+                C!["card-row"],
+                NUMBERS.iter().map(|&number| {
+                    let card = Card { number, suit };
+                    let selected = active.contains(&card);
+                    let disabled = model.is_used(card) && !selected;
+                    button![
+                        C!["card", format!("suit-{suit}"), IF!(selected => "selected")],
+                        attrs! {At::Disabled => disabled.as_at_value()},
+                        card.to_string(),
+                        ev(Ev::Click, move |_| Msg::ToggleCard(card)),
+                    ]
+                })
+            ]
+        })
+    ]
+}
+
+fn view_progress(model: &Model) -> Node<Msg> {
+    if !model.in_flight {
+        return empty![];
+    }
+    div![
+        C!["progress"],
+        // Without a value, the bar shows that the job is still starting.
+        progress![model.progress.map(|(done, total)| {
+            attrs! {At::Value => done, At::Max => total}
+        })],
+        button!["Cancel", ev(Ev::Click, |_| Msg::Cancel)],
+    ]
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn percent(count: u64, total: u64) -> String {
+    if total == 0 {
+        return "0.00%".into();
+    }
+    format!("{:.2}%", count as f64 / total as f64 * 100.)
+}
+
+fn view_results(model: &Model) -> Node<Msg> {
+    if let Some(error) = &model.error {
+        return div![C!["error"], error];
+    }
+    let Some(response) = &model.result else {
+        return empty![];
+    };
+
+    div![
+        model.note.as_ref().map(|note| p![C!["note"], note]),
+        table![
+            C!["results"],
+            tr![
+                th!["Hand"],
+                th!["Equity"],
+                th!["Win"],
+                th!["Tie"],
+                th!["Loss"]
+            ],
+            response.results.iter().enumerate().map(|(index, result)| {
+                let total = result.win_count + result.tie_count + result.loss_count;
+                let hand = model.hands.get(index).map_or_else(String::new, |hand| {
+                    hand.iter().map(ToString::to_string).collect::<String>()
+                });
+                tr![
+                    td![hand],
+                    td![format!("{:.2}%", result.equity * 100.)],
+                    td![format!(
+                        "{} ({})",
+                        percent(result.win_count, total),
+                        result.win_count
+                    )],
+                    td![format!(
+                        "{} ({})",
+                        percent(result.tie_count, total),
+                        result.tie_count
+                    )],
+                    td![format!(
+                        "{} ({})",
+                        percent(result.loss_count, total),
+                        result.loss_count
+                    )],
+                ]
+            })
+        ]
+    ]
+}
+
+fn view_range(model: &Model) -> Node<Msg> {
+    div![
+        C!["range"],
+        h2!["Hand 1 against a range"],
+        range_matrix::view(&model.range_matrix).map_msg(Msg::RangeMatrix),
+        button![
+            if model.range_in_flight {
+                "Computing…"
+            } else {
+                "Compute against range"
+            },
+            attrs! {At::Disabled => (!model.can_compute_range()).as_at_value()},
+            ev(Ev::Click, |_| Msg::ComputeRange),
+        ],
+        model
+            .range_error
+            .as_ref()
+            .map(|error| div![C!["error"], error]),
+        model.range_result.as_ref().map(|response| {
+            p![format!(
+                "Hand 1 has {:.2}% equity against the range.",
+                response.result.equity * 100.
+            )]
+        }),
+    ]
+}
+
 // `view` describes what to display.
 fn view(model: &Model) -> Node<Msg> {
     div![
-        "This is a counter: ",
-        C!["counter"],
-        button![model.counter, ev(Ev::Click, |_| Msg::Increment),],
-        p![format!("Server text: {}", model.text)]
+        C!["calculator"],
+        h1!["Poker Equity Calculator"],
+        div![
+            C!["slots"],
+            (0..model.hands.len()).map(|index| view_slot(
+                model,
+                Slot::Hand(index),
+                format!("Hand {}", index + 1)
+            )),
+            button![
+                "Add hand",
+                attrs! {At::Disabled => (model.hands.len() >= MAX_HANDS).as_at_value()},
+                ev(Ev::Click, |_| Msg::AddHand),
+            ],
+            view_slot(model, Slot::Flop, "Flop".into()),
+            view_slot(model, Slot::Turn, "Turn".into()),
+            view_slot(model, Slot::River, "River".into()),
+            view_slot(model, Slot::Dead, "Dead cards".into()),
+        ],
+        view_card_grid(model),
+        label![
+            input![
+                attrs! {At::Type => "checkbox", At::Checked => model.exact.as_at_value()},
+                ev(Ev::Change, |_| Msg::ToggleExact),
+            ],
+            "Exact (enumerate every runout)",
+        ],
+        button![
+            C!["compute"],
+            if model.in_flight {
+                "Restart"
+            } else {
+                "Compute"
+            },
+            attrs! {At::Disabled => (!model.can_compute()).as_at_value()},
+            ev(Ev::Click, |_| Msg::Compute),
+        ],
+        view_progress(model),
+        ul![
+            C!["validation"],
+            model
+                .hand_errors()
+                .into_iter()
+                .chain(model.board_errors())
+                .map(|error| li![error]),
+        ],
+        view_results(model),
+        view_range(model),
     ]
 }
 
@@ -0,0 +1,205 @@
+use poker_calculator::{Number, PokerError, Range, StartingHand};
+use seed::{prelude::*, *};
+use std::collections::{BTreeSet, HashMap};
+
+/// The numbers along each side of the matrix, from aces down.
+const NUMBERS: [Number; 13] = [
+    Number::Ace,
+    Number::King,
+    Number::Queen,
+    Number::Jack,
+    Number::Ten,
+    Number::Nine,
+    Number::Eight,
+    Number::Seven,
+    Number::Six,
+    Number::Five,
+    Number::Four,
+    Number::Three,
+    Number::Two,
+];
+
+/// Returns the class shown in a cell of the matrix: pairs on the diagonal,
+/// suited hands above it and offsuit hands below it.
+fn class_at(row: usize, column: usize) -> StartingHand {
+    let (first, second) = (NUMBERS[row], NUMBERS[column]);
+    match row.cmp(&column) {
+        std::cmp::Ordering::Equal => StartingHand::Pair(first),
+        std::cmp::Ordering::Less => StartingHand::Suited(first, second),
+        std::cmp::Ordering::Greater => StartingHand::Offsuit(second, first),
+    }
+}
+
+/// A rectangle of cells being dragged out, which will all be selected or all
+/// be deselected depending on the cell the drag started from.
+#[derive(Clone, Copy)]
+struct Drag {
+    start: (usize, usize),
+    end: (usize, usize),
+    select: bool,
+}
+
+impl Drag {
+    fn contains(&self, (row, column): (usize, usize)) -> bool {
+        let rows = self.start.0.min(self.end.0)..=self.start.0.max(self.end.0);
+        let columns = self.start.1.min(self.end.1)..=self.start.1.max(self.end.1);
+        rows.contains(&row) && columns.contains(&column)
+    }
+}
+
+// ------ ------
+//     Model
+// ------ ------
+
+#[derive(Default)]
+pub struct Model {
+    selected: BTreeSet<StartingHand>,
+    drag: Option<Drag>,
+    /// The range notation shown in the text field.
+    text: String,
+    /// Why the text field couldn't be parsed, if it couldn't.
+    error: Option<String>,
+    /// The hero's equity against each class, keyed by its name, from the last
+    /// computation.
+    equities: HashMap<String, f64>,
+}
+
+impl Model {
+    /// Returns the selected range written in range notation, unless it is
+    /// empty or the text field can't be parsed.
+    pub fn notation(&self) -> Option<&str> {
+        if self.error.is_some() || self.text.trim().is_empty() {
+            None
+        } else {
+            Some(&self.text)
+        }
+    }
+
+    /// Tints each cell by the hero's equity against its class.
+    pub fn set_equities(&mut self, equities: HashMap<String, f64>) {
+        self.equities = equities;
+    }
+
+    fn is_selected(&self, cell: (usize, usize)) -> bool {
+        match self.drag {
+            Some(drag) if drag.contains(cell) => drag.select,
+            _ => self.selected.contains(&class_at(cell.0, cell.1)),
+        }
+    }
+
+    fn update_text(&mut self) {
+        self.text = Range::from_classes(self.selected.iter().copied()).to_string();
+        self.error = None;
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    DragStarted(usize, usize),
+    DragEntered(usize, usize),
+    DragEnded,
+    TextChanged(String),
+}
+
+pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+    match msg {
+        Msg::DragStarted(row, column) => {
+            model.drag = Some(Drag {
+                start: (row, column),
+                end: (row, column),
+                select: !model.selected.contains(&class_at(row, column)),
+            });
+        }
+        Msg::DragEntered(row, column) => {
+            if let Some(drag) = &mut model.drag {
+                drag.end = (row, column);
+            }
+        }
+        Msg::DragEnded => {
+            let Some(drag) = model.drag.take() else {
+                return;
+            };
+            for row in 0..NUMBERS.len() {
+                for column in 0..NUMBERS.len() {
+                    if drag.contains((row, column)) {
+                        let class = class_at(row, column);
+                        if drag.select {
+                            model.selected.insert(class);
+                        } else {
+                            model.selected.remove(&class);
+                        }
+                    }
+                }
+            }
+            model.update_text();
+            model.equities.clear();
+        }
+        Msg::TextChanged(text) => {
+            match text.parse::<Range>() {
+                Ok(range) => {
+                    model.selected = range.classes().into_iter().collect();
+                    model.error = None;
+                }
+                Err(PokerError::InvalidRange { token, position }) => {
+                    model.error = Some(format!("Can't read {token:?} at position {position}"));
+                }
+                Err(error) => model.error = Some(error.to_string()),
+            }
+            model.text = text;
+            model.equities.clear();
+        }
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+/// Shades from red at no equity, through yellow, to green at full equity.
+fn equity_color(equity: f64) -> String {
+    format!("hsl({:.0}, 70%, 60%)", equity.clamp(0., 1.) * 120.)
+}
+
+fn view_cell(model: &Model, row: usize, column: usize) -> Node<Msg> {
+    let class = class_at(row, column);
+    let name = class.to_string();
+    let selected = model.is_selected((row, column));
+    let equity = model.equities.get(&name).filter(|_| selected);
+
+    td![
+        C!["range-cell", IF!(selected => "selected")],
+        equity.map(|&equity| style! {St::Background => equity_color(equity)}),
+        attrs! {At::Title => equity.map_or_else(|| name.clone(), |equity| format!("{name}: {:.1}%", equity * 100.))},
+        class.to_string(),
+        ev(Ev::MouseDown, move |event| {
+            event.prevent_default();
+            Msg::DragStarted(row, column)
+        }),
+        ev(Ev::MouseEnter, move |_| Msg::DragEntered(row, column)),
+        ev(Ev::MouseUp, |_| Msg::DragEnded),
+    ]
+}
+
+pub fn view(model: &Model) -> Node<Msg> {
+    div![
+        C!["range-matrix"],
+        table![
+            ev(Ev::MouseLeave, |_| Msg::DragEnded),
+            (0..NUMBERS.len())
+                .map(|row| { tr![(0..NUMBERS.len()).map(|column| view_cell(model, row, column))] })
+        ],
+        input![
+            C!["range-text"],
+            attrs! {
+                At::Type => "text",
+                At::Value => model.text,
+                At::Placeholder => "22+, ATs+, KQo",
+            },
+            input_ev(Ev::Input, Msg::TextChanged),
+        ],
+        model.error.as_ref().map(|error| div![C!["error"], error]),
+    ]
+}
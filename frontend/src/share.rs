@@ -0,0 +1,179 @@
+//! Encodes a scenario into URL parameters, so that it can be sent as a link,
+//! and decodes it again. A link such as
+//! `?h1=AsKc&h2=QhQs&board=7d8d2c&range=QQ%2B` opens the calculator with those
+//! hands, board and villain range already picked.
+
+use crate::MAX_HANDS;
+use poker_calculator::{Card, Range};
+
+/// Everything about a scenario which is kept in a link.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SharedScenario {
+    /// Every hand, including those which aren't complete yet.
+    pub hands: Vec<Vec<Card>>,
+    pub board: Vec<Card>,
+    pub dead: Vec<Card>,
+    /// The villain range which the first hand is compared against.
+    pub range: Option<String>,
+    pub exact: bool,
+    /// Whether opening the link should compute the equity straight away.
+    pub compute: bool,
+}
+
+fn hand_key(index: usize) -> String {
+    format!("h{}", index + 1)
+}
+
+fn join(cards: &[Card]) -> String {
+    cards.iter().map(ToString::to_string).collect()
+}
+
+/// Reads cards two characters at a time, skipping any pair which isn't a card
+/// rather than giving up on the rest.
+fn parse_cards(s: &str) -> Vec<Card> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    chars
+        .chunks(2)
+        .filter_map(|pair| pair.iter().collect::<String>().parse().ok())
+        .collect()
+}
+
+/// Returns the parameters which describe a scenario, in the order they should
+/// appear in the URL. Every hand is included, even an empty one, so that the
+/// number of hands survives the round trip.
+pub fn encode(scenario: &SharedScenario) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = scenario
+        .hands
+        .iter()
+        .enumerate()
+        .map(|(index, hand)| (hand_key(index), join(hand)))
+        .collect();
+    if !scenario.board.is_empty() {
+        params.push(("board".into(), join(&scenario.board)));
+    }
+    if !scenario.dead.is_empty() {
+        params.push(("dead".into(), join(&scenario.dead)));
+    }
+    if let Some(range) = &scenario.range {
+        params.push(("range".into(), range.clone()));
+    }
+    if scenario.exact {
+        params.push(("exact".into(), "1".into()));
+    }
+    if scenario.compute {
+        params.push(("compute".into(), "1".into()));
+    }
+    params
+}
+
+/// Rebuilds a scenario from the value of each parameter. Links are typed and
+/// truncated by hand, so anything which can't be read is dropped on its own
+/// and the rest of the scenario is kept.
+pub fn decode(get: impl Fn(&str) -> Option<String>) -> SharedScenario {
+    let hands: Vec<Option<String>> = (0..MAX_HANDS).map(|index| get(&hand_key(index))).collect();
+    let hand_count = hands
+        .iter()
+        .rposition(Option::is_some)
+        .map_or(0, |index| index + 1);
+
+    let cards = |key: &str| {
+        get(key)
+            .map(|cards| parse_cards(&cards))
+            .unwrap_or_default()
+    };
+    let mut board = cards("board");
+    board.truncate(5);
+
+    SharedScenario {
+        hands: hands[..hand_count.max(2)]
+            .iter()
+            .map(|hand| {
+                let mut cards = hand.as_deref().map(parse_cards).unwrap_or_default();
+                cards.truncate(2);
+                cards
+            })
+            .collect(),
+        board,
+        dead: cards("dead"),
+        range: get("range").filter(|range| range.parse::<Range>().is_ok()),
+        exact: get("exact").as_deref() == Some("1"),
+        compute: get("compute").as_deref() == Some("1"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn decode_params(params: &[(&str, &str)]) -> SharedScenario {
+        let params: HashMap<&str, &str> = params.iter().copied().collect();
+        decode(|key| params.get(key).map(ToString::to_string))
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        poker_calculator::parse_cards(s).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let scenario = SharedScenario {
+            hands: vec![cards("AsKc"), cards("QhQs"), cards("7c")],
+            board: cards("7d8d2cJh"),
+            dead: cards("2s3s"),
+            range: Some("QQ+, AKs".into()),
+            exact: true,
+            compute: true,
+        };
+        let params = encode(&scenario);
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(decode_params(&params), scenario);
+    }
+
+    #[test]
+    fn test_empty_hands_are_kept() {
+        let scenario = SharedScenario {
+            hands: vec![vec![], vec![], vec![]],
+            ..SharedScenario::default()
+        };
+        assert_eq!(
+            encode(&scenario),
+            [("h1", ""), ("h2", ""), ("h3", "")].map(|(key, value)| (key.into(), value.into()))
+        );
+        assert_eq!(decode_params(&[("h3", "")]), scenario);
+    }
+
+    #[test]
+    fn test_missing_parameters() {
+        assert_eq!(
+            decode_params(&[]),
+            SharedScenario {
+                hands: vec![vec![], vec![]],
+                ..SharedScenario::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_garbled_parameters_are_dropped() {
+        let scenario = decode_params(&[
+            ("h1", "AsXx"),
+            ("h2", "QhQsKd"),
+            ("board", "7d8dZz2c"),
+            ("dead", "nonsense"),
+            ("range", "QQ+, AXs"),
+            ("exact", "yes"),
+        ]);
+        assert_eq!(
+            scenario,
+            SharedScenario {
+                hands: vec![cards("As"), cards("QhQs")],
+                board: cards("7d8d2c"),
+                ..SharedScenario::default()
+            }
+        );
+    }
+}
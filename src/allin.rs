@@ -0,0 +1,372 @@
+//! Whether to call an all-in in a tournament, by chips and by prize money.
+
+use crate::{
+    compute_range_equity, icm_equities, Card, EquityMethod, EquityResult, PokerError, Range,
+};
+
+/// The forced bets of a hand.
+///
+/// Only the big blind is needed, as the small blind has shoved on top of its
+/// own blind and gets it back in every outcome the big blind's decision can
+/// change.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Blinds {
+    pub big_blind: f64,
+    /// The ante each of the two players posts, which goes into the pot
+    /// whether or not the all-in is called.
+    pub ante: f64,
+}
+
+/// A heads-up all-in in a tournament: the villain has shoved from the small
+/// blind, and the hero must call or fold in the big blind.
+///
+/// Stacks are counted before the blinds and antes are posted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllinSpot<'a> {
+    pub hero_stack: f64,
+    pub villain_stack: f64,
+    pub blinds: Blinds,
+    /// The prizes for first place, second place and so on. Without them,
+    /// only the chip EV is computed.
+    pub payouts: Option<&'a [f64]>,
+    /// The stacks of the players who aren't in the hand, which matter to the
+    /// prizes the hero can expect.
+    pub other_stacks: &'a [f64],
+}
+
+/// The value of calling and of folding an all-in, along with what it comes
+/// from.
+///
+/// Chip EVs are the hero's expected change in chips over the hand, so folding
+/// loses the blind and ante. ICM EVs are the hero's expected prize, in the
+/// units of the payouts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AllinEv {
+    /// The hero's results at showdown against the villain's range.
+    pub result: EquityResult,
+    pub equity: f64,
+    /// The pot the hero plays for by calling, including both antes.
+    pub pot: f64,
+    /// The chips the hero adds to the blind and ante to call.
+    pub to_call: f64,
+    /// The chips the hero loses by calling and losing, which is everything
+    /// the hero puts in the pot.
+    pub risk: f64,
+    pub call_chip_ev: f64,
+    pub fold_chip_ev: f64,
+    pub call_icm_ev: Option<f64>,
+    pub fold_icm_ev: Option<f64>,
+}
+
+impl AllinEv {
+    /// Returns the equity at which calling and folding are worth the same
+    /// number of chips, which is the price the pot lays the hero.
+    #[must_use]
+    pub fn required_equity(&self) -> f64 {
+        self.to_call / self.pot
+    }
+
+    /// Returns how many more chips calling is worth than folding.
+    #[must_use]
+    pub fn chip_ev_gain(&self) -> f64 {
+        self.call_chip_ev - self.fold_chip_ev
+    }
+
+    /// Returns how much more prize money calling is worth than folding, if
+    /// there are payouts.
+    #[must_use]
+    pub fn icm_ev_gain(&self) -> Option<f64> {
+        Some(self.call_icm_ev? - self.fold_icm_ev?)
+    }
+
+    /// Returns whether calling is better, going by the prize money when there
+    /// are payouts and by chips otherwise.
+    #[must_use]
+    pub fn should_call(&self) -> bool {
+        self.icm_ev_gain().unwrap_or_else(|| self.chip_ev_gain()) > 0.
+    }
+}
+
+impl AllinSpot<'_> {
+    fn validate(&self) -> Result<(), PokerError> {
+        let amounts = [
+            self.hero_stack,
+            self.villain_stack,
+            self.blinds.big_blind,
+            self.blinds.ante,
+        ];
+        if amounts
+            .iter()
+            .chain(self.other_stacks)
+            .chain(self.payouts.unwrap_or_default())
+            .any(|amount| !amount.is_finite() || *amount < 0.)
+        {
+            return Err(PokerError::InvalidRequest(
+                "stacks, blinds and payouts can't be negative".into(),
+            ));
+        }
+        if self.hero_stack.min(self.villain_stack) <= self.blinds.ante {
+            return Err(PokerError::InvalidRequest(
+                "both players need more chips than the ante".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the hero's prize, by ICM, after the hero's stack changes by
+    /// `hero_change` chips and the villain's by the opposite.
+    fn hero_icm(&self, payouts: &[f64], hero_change: f64) -> f64 {
+        let mut stacks = vec![
+            self.hero_stack + hero_change,
+            self.villain_stack - hero_change,
+        ];
+        stacks.extend_from_slice(self.other_stacks);
+        icm_equities(&stacks, payouts)[0]
+    }
+
+    /// Computes the value of calling and of folding, given how the hero does
+    /// at showdown. Ties split the pot, which leaves both stacks as they were.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if an amount is negative or not finite, or if
+    /// either player can't cover the ante.
+    pub fn ev(&self, result: EquityResult) -> Result<AllinEv, PokerError> {
+        self.validate()?;
+        let Blinds { big_blind, ante } = self.blinds;
+
+        // Only the chips both players have can be won, and the big blind can
+        // only lose as much of its blind as the villain put in.
+        let effective = (self.hero_stack - ante).min(self.villain_stack - ante);
+        let blind = big_blind.min(effective);
+        let risk = ante + effective;
+        let (win, loss, tie) = (
+            result.win_fraction(),
+            result.loss_fraction(),
+            result.tie_fraction(),
+        );
+        let fold_chip_ev = -(ante + blind);
+
+        let (call_icm_ev, fold_icm_ev) = self.payouts.map_or((None, None), |payouts| {
+            let call = [(win, risk), (loss, -risk), (tie, 0.)]
+                .into_iter()
+                .map(|(probability, change)| probability * self.hero_icm(payouts, change))
+                .sum();
+            (Some(call), Some(self.hero_icm(payouts, fold_chip_ev)))
+        });
+
+        Ok(AllinEv {
+            result,
+            equity: result.equity(),
+            pot: 2. * risk,
+            to_call: effective - blind,
+            risk,
+            call_chip_ev: (win - loss) * risk,
+            fold_chip_ev,
+            call_icm_ev,
+            fold_icm_ev,
+        })
+    }
+}
+
+/// Computes whether the hero should call an all-in with `hero_hand` against
+/// the villain's shoving range, by chips and, when the spot has payouts, by
+/// prize money.
+///
+/// # Errors
+///
+/// Will return an error if the spot is invalid, as for [`AllinSpot::ev`], or
+/// if every combination of the villain's range shares a card with the hero's
+/// hand.
+pub fn allin_ev(
+    spot: &AllinSpot,
+    hero_hand: [Card; 2],
+    villain_range: &Range,
+    method: EquityMethod,
+) -> Result<AllinEv, PokerError> {
+    spot.validate()?;
+    let equity = compute_range_equity(&Range::from(hero_hand), villain_range, &[], &[], method)?;
+    if equity.result.count == 0 {
+        return Err(PokerError::InvalidRequest(
+            "every combination of the villain's range shares a card with the hero's hand".into(),
+        ));
+    }
+    spot.ev(equity.result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Hand;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn result(win_count: u64, loss_count: u64, tie_count: u64) -> EquityResult {
+        EquityResult {
+            win_count,
+            loss_count,
+            tie_count,
+            count: win_count + loss_count + tie_count,
+        }
+    }
+
+    fn spot(hero_stack: f64, villain_stack: f64) -> AllinSpot<'static> {
+        AllinSpot {
+            hero_stack,
+            villain_stack,
+            blinds: Blinds {
+                big_blind: 100.,
+                ante: 0.,
+            },
+            payouts: None,
+            other_stacks: &[],
+        }
+    }
+
+    #[test]
+    fn test_bubble_with_equal_stacks() {
+        let payouts = [0.65, 0.35];
+        let bubble = AllinSpot {
+            payouts: Some(&payouts),
+            other_stacks: &[1000.],
+            ..spot(1000., 1000.)
+        };
+        let ev = bubble.ev(result(55, 45, 0)).unwrap();
+
+        assert_eq!((ev.pot, ev.to_call, ev.risk), (2000., 900., 1000.));
+        assert!((ev.required_equity() - 0.45).abs() < TOLERANCE);
+        assert!((ev.call_chip_ev - 100.).abs() < TOLERANCE);
+        assert_eq!(ev.fold_chip_ev, -100.);
+
+        // Winning leaves the hero with two thirds of the chips heads-up, and
+        // losing busts the hero on the bubble.
+        let call = 0.55 * (2. / 3. * 0.65 + 1. / 3. * 0.35);
+        // After folding, the hero has 900 chips against 1100 and 1000.
+        let second = 1100. / 3000. * 900. / 1900. + 1000. / 3000. * 900. / 2000.;
+        let fold = 0.3 * 0.65 + second * 0.35;
+        assert!((ev.call_icm_ev.unwrap() - call).abs() < TOLERANCE);
+        assert!((ev.fold_icm_ev.unwrap() - fold).abs() < TOLERANCE);
+
+        // A call which wins chips still gives up prize money on the bubble.
+        assert!(ev.chip_ev_gain() > 0.);
+        assert!(ev.icm_ev_gain().unwrap() < 0.);
+        assert!(!ev.should_call());
+        assert!(spot(1000., 1000.)
+            .ev(result(55, 45, 0))
+            .unwrap()
+            .should_call());
+    }
+
+    #[test]
+    fn test_covered_and_covering_stacks() {
+        let payouts = [0.65, 0.35];
+        let covered = AllinSpot {
+            payouts: Some(&payouts),
+            other_stacks: &[1000.],
+            ..spot(500., 2000.)
+        };
+        let covering = AllinSpot {
+            hero_stack: 2000.,
+            villain_stack: 500.,
+            ..covered
+        };
+
+        // Only the 500 chips both players have are at stake either way.
+        let [covered, covering] = [covered, covering].map(|spot| spot.ev(result(1, 1, 0)).unwrap());
+        for ev in [covered, covering] {
+            assert_eq!((ev.pot, ev.to_call, ev.risk), (1000., 400., 500.));
+            assert!((ev.required_equity() - 0.4).abs() < TOLERANCE);
+            assert_eq!((ev.call_chip_ev, ev.fold_chip_ev), (0., -100.));
+        }
+
+        // The covered hero busts out of the money when losing, but the
+        // covering hero keeps 1500 chips.
+        let covered_call = 0.5 * icm_equities(&[1000., 1500., 1000.], &payouts)[0];
+        assert!((covered.call_icm_ev.unwrap() - covered_call).abs() < TOLERANCE);
+        let covering_call = 0.5 * icm_equities(&[2500., 0., 1000.], &payouts)[0]
+            + 0.5 * icm_equities(&[1500., 1000., 1000.], &payouts)[0];
+        assert!((covering.call_icm_ev.unwrap() - covering_call).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_antes_and_ties() {
+        let payouts = [1.];
+        let spot = AllinSpot {
+            blinds: Blinds {
+                big_blind: 100.,
+                ante: 25.,
+            },
+            payouts: Some(&payouts),
+            ..spot(1000., 1000.)
+        };
+        let ev = spot.ev(result(40, 40, 20)).unwrap();
+
+        // Both antes are in the pot, and folding gives up the ante too.
+        assert_eq!((ev.pot, ev.to_call, ev.risk), (2000., 875., 1000.));
+        assert_eq!(ev.fold_chip_ev, -125.);
+        assert!((ev.required_equity() - 0.4375).abs() < TOLERANCE);
+        // Splitting the pot gives both players their chips back.
+        assert!((ev.equity - 0.5).abs() < TOLERANCE);
+        assert!(ev.call_chip_ev.abs() < TOLERANCE);
+        // With the winner taking all, prize money is in proportion to chips.
+        assert!((ev.call_icm_ev.unwrap() - 0.5).abs() < TOLERANCE);
+        assert!((ev.fold_icm_ev.unwrap() - 875. / 2000.).abs() < TOLERANCE);
+        assert!(ev.should_call());
+    }
+
+    #[test]
+    fn test_invalid_spots() {
+        let invalid = [
+            spot(25., 1000.),
+            spot(-1., 1000.),
+            spot(1000., f64::NAN),
+            AllinSpot {
+                other_stacks: &[-5.],
+                ..spot(1000., 1000.)
+            },
+            AllinSpot {
+                payouts: Some(&[f64::INFINITY]),
+                ..spot(1000., 1000.)
+            },
+        ];
+        for spot in invalid {
+            let spot = AllinSpot {
+                blinds: Blinds {
+                    big_blind: 100.,
+                    ante: 25.,
+                },
+                ..spot
+            };
+            assert!(matches!(
+                spot.ev(result(1, 1, 0)),
+                Err(PokerError::InvalidRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_allin_ev_against_a_range() {
+        let hero = "AsAh".parse::<Hand>().unwrap().cards();
+        let villain: Range = "KK".parse().unwrap();
+        let method = EquityMethod::MonteCarlo {
+            samples: 20_000,
+            seed: Some(848),
+        };
+        let ev = allin_ev(&spot(1000., 1000.), hero, &villain, method).unwrap();
+
+        let equity = compute_range_equity(&Range::from(hero), &villain, &[], &[], method).unwrap();
+        assert_eq!(ev.result, equity.result);
+        assert!((ev.equity - 0.82).abs() < 0.02);
+        assert!(ev.should_call());
+
+        assert!(matches!(
+            allin_ev(
+                &spot(1000., 1000.),
+                hero,
+                &Range::from(hero),
+                EquityMethod::Exhaustive
+            ),
+            Err(PokerError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Badugi, a draw lowball game where the best hand has four cards of
+//! different suits and different numbers.
+
+use crate::{Card, Number};
+use core::cmp::Ordering;
+
+/// The best badugi which some cards make: the most cards with no suit or
+/// number in common, and the lowest of those, with aces low.
+///
+/// Unlike [`HandEvaluation`](crate::HandEvaluation), there are no kinds of
+/// hand. Any four card badugi beats any three card one, and hands with the
+/// same number of cards compare by their highest card, then their next
+/// highest and so on, where lower is better. Better hands compare greater, so
+/// the winner of several is their maximum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BadugiHand {
+    /// The numbers in the hand, where aces are the bit below the twos.
+    low_bits: u16,
+}
+
+impl BadugiHand {
+    /// Returns how many cards play, from 1 to 4, or 0 if there were no cards.
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.low_bits.count_ones() as usize
+    }
+
+    /// Returns whether no cards play, which only happens without any cards.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.low_bits == 0
+    }
+
+    /// Returns whether all four cards play, making a true badugi.
+    #[must_use]
+    pub const fn is_badugi(self) -> bool {
+        self.len() == 4
+    }
+
+    /// Iterates over the numbers which play, from the highest down, with aces
+    /// last.
+    pub fn numbers(self) -> impl Iterator<Item = Number> {
+        Number::iter_desc()
+            .skip(1)
+            .chain(Some(Number::Ace))
+            .filter(move |&number| self.low_bits & low_bit(number) != 0)
+    }
+}
+
+impl Ord for BadugiHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // With the same number of cards, the hand holding the highest card
+        // which the other lacks is worse, which is how the bits compare.
+        self.len()
+            .cmp(&other.len())
+            .then_with(|| other.low_bits.cmp(&self.low_bits))
+    }
+}
+
+impl PartialOrd for BadugiHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the bit for a number in a badugi, where aces play below the twos.
+const fn low_bit(number: Number) -> u16 {
+    match number {
+        Number::Ace => Number::Two.as_bit() >> 1,
+        number => number.as_bit(),
+    }
+}
+
+/// Finds the best badugi in any number of cards, which are usually the four
+/// cards of a badugi hand.
+///
+/// Only cards with different suits and different numbers can play together,
+/// so a hand with two cards of the same suit or number plays at most three
+/// cards.
+#[must_use]
+pub fn evaluate_badugi(cards: &[Card]) -> BadugiHand {
+    let mut best = BadugiHand::default();
+    search(cards, 0, 0, &mut best);
+    best
+}
+
+/// Tries adding each card from `cards` to a hand which already has the
+/// numbers in `low_bits` and the suits in `suit_bits`, keeping the best hand
+/// found in `best`.
+fn search(cards: &[Card], low_bits: u16, suit_bits: u8, best: &mut BadugiHand) {
+    let hand = BadugiHand { low_bits };
+    if hand > *best {
+        *best = hand;
+    }
+    for (index, card) in cards.iter().enumerate() {
+        let (number_bit, suit_bit) = (low_bit(card.number()), 1 << card.suit() as u8);
+        if low_bits & number_bit == 0 && suit_bits & suit_bit == 0 {
+            search(
+                &cards[index + 1..],
+                low_bits | number_bit,
+                suit_bits | suit_bit,
+                best,
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+
+    fn badugi(cards: &str) -> BadugiHand {
+        evaluate_badugi(&parse_cards(cards).unwrap())
+    }
+
+    #[test]
+    fn test_nut_badugi() {
+        let nuts = badugi("Ah2d3c4s");
+        assert!(nuts.is_badugi());
+        assert_eq!(
+            nuts.numbers().collect::<Vec<_>>(),
+            [Number::Four, Number::Three, Number::Two, Number::Ace]
+        );
+
+        // Every other four card badugi is worse.
+        for other in ["Ah2d3c5s", "2h3d4c5s", "Kh2d3c4s"] {
+            assert!(badugi(other).is_badugi());
+            assert!(nuts > badugi(other), "{other}");
+        }
+        // The highest card decides before the lower ones.
+        assert!(badugi("Ah2d3c8s") > badugi("5h6d7c8s").max(badugi("Ah2d4c9s")));
+    }
+
+    #[test]
+    fn test_duplicate_suits_and_numbers() {
+        // Two hearts can't both play, so the higher one is dropped.
+        let three_cards = badugi("Ah2d3c4h");
+        assert_eq!(three_cards.len(), 3);
+        assert_eq!(three_cards, badugi("Ah2d3c"));
+        // Any four card badugi beats any three card one.
+        assert!(badugi("KhQdJcTs") > three_cards);
+
+        // A pair plays as one card.
+        assert_eq!(badugi("2h2d3c4s"), badugi("2h3c4s"));
+        assert_eq!(badugi("AhKhQhJh").len(), 1);
+        assert_eq!(badugi("AhKhQhJh"), badugi("Ah"));
+        assert!(evaluate_badugi(&[]).is_empty());
+
+        // More cards than four, as when drawing, still play at most four.
+        assert_eq!(badugi("KhQdJcTs Ah2d3c4s"), badugi("Ah2d3c4s"));
+    }
+}
@@ -0,0 +1,52 @@
+//! Frequencies for balanced betting, which depend only on the sizes of the
+//! bet and the pot.
+
+/// Returns the minimum defense frequency against a bet of `bet` into a pot of
+/// `pot`: how much of their range the defender must continue with so that a
+/// bluff with any two cards doesn't profit.
+///
+/// The bettor risks `bet` to win `pot`, so a bluff breaks even when it works
+/// `bet / (pot + bet)` of the time.
+#[must_use]
+pub fn mdf(bet: f64, pot: f64) -> f64 {
+    pot / (pot + bet)
+}
+
+/// Returns the fraction of a balanced betting range which should be bluffs
+/// for a bet of `bet` into a pot of `pot`.
+///
+/// Balanced, the caller is indifferent between calling and folding: a call
+/// risks `bet` to win the pot and both bets, so bluffs make up
+/// `bet / (pot + 2 * bet)` of the bets.
+#[must_use]
+pub fn bluff_value_ratio(bet: f64, pot: f64) -> f64 {
+    bet / (pot + bet + bet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_pot_sized_bet() {
+        assert_close(mdf(100., 100.), 0.5);
+        // One bluff for every two value bets.
+        assert_close(bluff_value_ratio(100., 100.), 1. / 3.);
+    }
+
+    #[test]
+    fn test_half_pot_bet() {
+        assert_close(mdf(50., 100.), 2. / 3.);
+        assert_close(bluff_value_ratio(50., 100.), 0.25);
+
+        // Smaller bets need fewer bluffs and more defense.
+        assert!(mdf(25., 100.) > mdf(50., 100.));
+        assert!(bluff_value_ratio(25., 100.) < bluff_value_ratio(50., 100.));
+        assert_close(mdf(0., 100.), 1.);
+        assert_close(bluff_value_ratio(0., 100.), 0.);
+    }
+}
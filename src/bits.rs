@@ -0,0 +1,250 @@
+//! The bit tricks the evaluator is built on, for analyses of boards and
+//! ranges which need the same primitives.
+//!
+//! A set of ranks is a `u16` with the bit `1 << number` set for each
+//! [`Number`] in it, as returned by [`Number::as_bit`]. Only bits 2 to 14
+//! stand for a number, and every function here ignores the others.
+
+use crate::{Card, Number, Suit};
+
+/// The bits of a set of ranks which stand for a number, from [`Number::Two`]
+/// to [`Number::Ace`].
+const NUMBER_BITS: u16 = 0b0111_1111_1111_1100;
+
+/// Returns the set of ranks of the cards.
+#[must_use]
+pub fn rank_bits_of(cards: &[Card]) -> u16 {
+    cards
+        .iter()
+        .fold(0, |bits, card| bits | card.number().as_bit())
+}
+
+/// Returns the set of ranks of the cards of one suit.
+#[must_use]
+pub fn suit_rank_bits_of(cards: &[Card], suit: Suit) -> u16 {
+    cards
+        .iter()
+        .filter(|card| card.suit() == suit)
+        .fold(0, |bits, card| bits | card.number().as_bit())
+}
+
+/// Returns the set of ranks of the cards of each suit, indexed by [`Suit`].
+#[must_use]
+pub fn rank_bits_by_suit(cards: &[Card]) -> [u16; 4] {
+    let mut bits = [0; 4];
+    for card in cards {
+        bits[card.suit() as usize] |= card.number().as_bit();
+    }
+    bits
+}
+
+/// Counts how many of the cards have each number, indexed by the number's
+/// value, so that twos are at index 2 and aces at index 14. Indices 0 and 1
+/// are always zero.
+#[must_use]
+pub fn rank_histogram(cards: &[Card]) -> [u8; 15] {
+    let mut histogram = [0; 15];
+    for card in cards {
+        histogram[card.number() as usize] += 1;
+    }
+    histogram
+}
+
+/// Counts how many of the cards have each suit, indexed by [`Suit`].
+#[must_use]
+pub fn suit_histogram(cards: &[Card]) -> [u8; 4] {
+    let mut histogram = [0; 4];
+    for card in cards {
+        histogram[card.suit() as usize] += 1;
+    }
+    histogram
+}
+
+/// Returns the highest card of the highest straight in a set of ranks. The
+/// ace also plays low, so the wheel is a straight to the [`Number::Five`].
+#[must_use]
+pub fn straight_high_card(rank_bits: u16) -> Option<Number> {
+    let mut rank_bits = rank_bits & NUMBER_BITS;
+    // Duplicate the ace at the bottom of the bitset, if it is present.
+    if rank_bits & Number::Ace.as_bit() != 0 {
+        rank_bits |= 2;
+    }
+
+    // Check for straights by using 5 bit windows, and seeing if all bits
+    // in the mask are present.
+    let mask = 0b11111;
+    for shift_index in (1..11).rev() {
+        if (rank_bits & (mask << shift_index)) >> shift_index == mask {
+            // The window's top bit is between 5 and 14, so it is a number.
+            unsafe {
+                return Some(Number::from_u8_unchecked(shift_index + 4));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the highest number in a set of ranks, or `None` if it is empty.
+#[must_use]
+pub fn highest_rank(rank_bits: u16) -> Option<Number> {
+    let rank_bits = rank_bits & NUMBER_BITS;
+    if rank_bits == 0 {
+        return None;
+    }
+    // The highest bit is between 2 and 14, so it is a number.
+    #[allow(clippy::cast_possible_truncation)]
+    unsafe {
+        Some(Number::from_u8_unchecked(rank_bits.ilog2() as u8))
+    }
+}
+
+/// Keeps only the `n` highest numbers of a set of ranks, dropping the
+/// lowest. Sets with `n` or fewer numbers are returned unchanged.
+#[must_use]
+pub const fn top_n_ranks(rank_bits: u16, n: u32) -> u16 {
+    let mut rank_bits = rank_bits & NUMBER_BITS;
+    while rank_bits.count_ones() > n {
+        rank_bits &= rank_bits - 1;
+    }
+    rank_bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(numbers: &[Number]) -> u16 {
+        numbers
+            .iter()
+            .fold(0, |bits, number| bits | number.as_bit())
+    }
+
+    /// Eights full of nothing, with four spades: 8s 8h As Ks 2d 8c 5s.
+    fn sample_hand() -> [Card; 7] {
+        [
+            (Suit::Spades, Number::Eight),
+            (Suit::Hearts, Number::Eight),
+            (Suit::Spades, Number::Ace),
+            (Suit::Spades, Number::King),
+            (Suit::Diamonds, Number::Two),
+            (Suit::Clubs, Number::Eight),
+            (Suit::Spades, Number::Five),
+        ]
+        .map(|(suit, number)| Card::new(suit, number))
+    }
+
+    #[test]
+    fn test_rank_bits() {
+        let cards = sample_hand();
+        let all = bits(&[
+            Number::Ace,
+            Number::King,
+            Number::Eight,
+            Number::Five,
+            Number::Two,
+        ]);
+        assert_eq!(rank_bits_of(&cards), all);
+        let spades = bits(&[Number::Ace, Number::King, Number::Eight, Number::Five]);
+        assert_eq!(suit_rank_bits_of(&cards, Suit::Spades), spades);
+        assert_eq!(
+            rank_bits_by_suit(&cards),
+            [
+                Number::Eight.as_bit(),
+                Number::Two.as_bit(),
+                Number::Eight.as_bit(),
+                spades
+            ]
+        );
+
+        assert_eq!(rank_bits_of(&[]), 0);
+        assert_eq!(suit_rank_bits_of(&cards[..2], Suit::Diamonds), 0);
+        assert_eq!(rank_bits_by_suit(&[]), [0; 4]);
+        assert_eq!(rank_bits_of(&Card::ALL), NUMBER_BITS);
+    }
+
+    #[test]
+    fn test_histograms() {
+        let cards = sample_hand();
+        let mut ranks = [0; 15];
+        ranks[Number::Eight as usize] = 3;
+        for number in [Number::Ace, Number::King, Number::Two, Number::Five] {
+            ranks[number as usize] = 1;
+        }
+        assert_eq!(rank_histogram(&cards), ranks);
+        assert_eq!(suit_histogram(&cards), [1, 1, 1, 4]);
+
+        assert_eq!(rank_histogram(&[]), [0; 15]);
+        assert_eq!(suit_histogram(&[]), [0; 4]);
+        let mut full = [4; 15];
+        full[..2].copy_from_slice(&[0, 0]);
+        assert_eq!(rank_histogram(&Card::ALL), full);
+        assert_eq!(suit_histogram(&Card::ALL), [13; 4]);
+    }
+
+    #[test]
+    fn test_straight_high_card() {
+        let broadway = bits(&[
+            Number::Ace,
+            Number::King,
+            Number::Queen,
+            Number::Jack,
+            Number::Ten,
+        ]);
+        let wheel = bits(&[
+            Number::Ace,
+            Number::Two,
+            Number::Three,
+            Number::Four,
+            Number::Five,
+        ]);
+        assert_eq!(straight_high_card(broadway), Some(Number::Ace));
+        assert_eq!(straight_high_card(wheel), Some(Number::Five));
+        // The highest straight wins when there are several.
+        assert_eq!(
+            straight_high_card(wheel | Number::Six.as_bit()),
+            Some(Number::Six)
+        );
+        assert_eq!(straight_high_card(broadway & !Number::Jack.as_bit()), None);
+
+        assert_eq!(straight_high_card(0), None);
+        assert_eq!(straight_high_card(u16::MAX), Some(Number::Ace));
+        // Bit 1 isn't an ace, so it can't complete a wheel.
+        assert_eq!(straight_high_card(wheel & !Number::Ace.as_bit() | 2), None);
+    }
+
+    #[test]
+    fn test_highest_rank() {
+        assert_eq!(highest_rank(0), None);
+        // Bits 0, 1 and 15 aren't numbers, so they have no highest card.
+        assert_eq!(highest_rank(0b1000_0000_0000_0011), None);
+        assert_eq!(highest_rank(Number::Two.as_bit()), Some(Number::Two));
+        assert_eq!(
+            highest_rank(bits(&[Number::Nine, Number::Three])),
+            Some(Number::Nine)
+        );
+        assert_eq!(highest_rank(NUMBER_BITS), Some(Number::Ace));
+        assert_eq!(highest_rank(u16::MAX), Some(Number::Ace));
+    }
+
+    #[test]
+    fn test_top_n_ranks() {
+        let ranks = bits(&[Number::Ace, Number::Nine, Number::Five, Number::Two]);
+        assert_eq!(top_n_ranks(ranks, 2), bits(&[Number::Ace, Number::Nine]));
+        assert_eq!(top_n_ranks(ranks, 4), ranks);
+        assert_eq!(top_n_ranks(ranks, 13), ranks);
+        assert_eq!(top_n_ranks(ranks, 0), 0);
+
+        assert_eq!(top_n_ranks(0, 5), 0);
+        assert_eq!(
+            top_n_ranks(u16::MAX, 5),
+            bits(&[
+                Number::Ace,
+                Number::King,
+                Number::Queen,
+                Number::Jack,
+                Number::Ten
+            ])
+        );
+        assert_eq!(top_n_ranks(u16::MAX, 13), NUMBER_BITS);
+    }
+}
@@ -0,0 +1,227 @@
+//! The community cards, which are dealt a street at a time.
+
+use crate::range::categorize;
+use crate::{
+    ahead_behind_breakdown, compute_equity, hand_kind_frequencies, parse_cards, Card, EquityMethod,
+    EquityResult, HandKind, PokerError, Range, Street,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// The community cards dealt so far: none before the flop, then three, four
+/// and five.
+///
+/// The equity and analysis functions are available as methods, which can't be
+/// given a board of the wrong size. A board also dereferences to its cards, so
+/// it can be passed anywhere else a slice of board cards is taken.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Board(Vec<Card>);
+
+impl Board {
+    /// # Errors
+    ///
+    /// Will return [`PokerError::InvalidBoardSize`] unless there are zero,
+    /// three, four or five cards, or [`PokerError::DuplicateCard`] if a card
+    /// appears twice.
+    pub fn new(cards: &[Card]) -> Result<Self, PokerError> {
+        if !matches!(cards.len(), 0 | 3..=5) {
+            return Err(PokerError::InvalidBoardSize(cards.len()));
+        }
+        for (i, card) in cards.iter().enumerate() {
+            if cards[..i].contains(card) {
+                return Err(PokerError::DuplicateCard(*card));
+            }
+        }
+        Ok(Self(cards.to_vec()))
+    }
+
+    /// Returns the street the board has been dealt up to.
+    #[must_use]
+    pub const fn street(&self) -> Street {
+        match self.0.len() {
+            0 => Street::Preflop,
+            3 => Street::Flop,
+            4 => Street::Turn,
+            _ => Street::River,
+        }
+    }
+
+    #[must_use]
+    pub fn cards(&self) -> &[Card] {
+        &self.0
+    }
+
+    /// Computes the exact equity of each hand on this board, as
+    /// [`compute_equity`] does.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if any card is used more than once.
+    pub fn compute_equity(
+        &self,
+        hands: &[impl Copy + Into<[Card; 2]>],
+        dead: &[Card],
+    ) -> Result<Vec<EquityResult>, PokerError> {
+        compute_equity(hands, self, dead)
+    }
+
+    /// Counts the combinations of `range` which can still be dealt by the kind
+    /// of hand they make on this board, as [`Range::categorize_on_board`]
+    /// does.
+    #[must_use]
+    pub fn categorize(&self, range: &Range) -> BTreeMap<HandKind, u32> {
+        categorize(range, self)
+    }
+
+    /// Returns the fractions of villain holdings the hero is behind, tied with
+    /// and ahead of on this board, as [`ahead_behind_breakdown`] does.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error before the flop, or if any card is used more than
+    /// once.
+    pub fn ahead_behind_breakdown(
+        &self,
+        hero: impl Into<[Card; 2]>,
+        dead: &[Card],
+    ) -> Result<(f64, f64, f64), PokerError> {
+        ahead_behind_breakdown(hero, self, dead)
+    }
+
+    /// Returns how often `hole` makes each kind of hand by the river from this
+    /// board, as [`hand_kind_frequencies`] does.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if any card is used more than once.
+    pub fn hand_kind_frequencies(
+        &self,
+        hole: impl Into<[Card; 2]>,
+        dead: &[Card],
+        method: EquityMethod,
+    ) -> Result<[f64; 9], PokerError> {
+        hand_kind_frequencies(hole, self, dead, method)
+    }
+}
+
+impl Deref for Board {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<Card>> for Board {
+    type Error = PokerError;
+
+    fn try_from(cards: Vec<Card>) -> Result<Self, Self::Error> {
+        Self::new(&cards)
+    }
+}
+
+impl From<Board> for Vec<Card> {
+    fn from(board: Board) -> Self {
+        board.0
+    }
+}
+
+impl FromStr for Board {
+    type Err = PokerError;
+
+    /// Parses cards written back to back, such as `"Ah Kd 7c"` or
+    /// `"AhKd7c"`, as [`parse_cards`] does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(&parse_cards(s)?)
+    }
+}
+
+impl fmt::Display for Board {
+    /// Writes the cards separated by spaces, such as `"Ah Kd 7c"`, with suit
+    /// symbols for the alternate flag, as [`Hand`](crate::Hand) does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            fmt::Display::fmt(card, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_equity, Hand};
+
+    #[test]
+    fn test_streets() {
+        let streets = [
+            ("", Street::Preflop),
+            ("Ah Kd 7c", Street::Flop),
+            ("AhKd7c2s", Street::Turn),
+            ("Ah Kd 7c 2s 2d", Street::River),
+        ];
+        for (cards, street) in streets {
+            let board: Board = cards.parse().unwrap();
+            assert_eq!(board.street(), street);
+            assert_eq!(board.len(), street.board_len());
+        }
+
+        let board: Board = "ahkd7c".parse().unwrap();
+        assert_eq!(board.to_string(), "Ah Kd 7c");
+        assert_eq!(format!("{board:#}"), "A♥ K♦ 7♣");
+        assert_eq!(board.to_string().parse(), Ok(board));
+    }
+
+    #[test]
+    fn test_invalid_boards() {
+        assert_eq!(
+            "Ah Kd".parse::<Board>(),
+            Err(PokerError::InvalidBoardSize(2))
+        );
+        assert_eq!(
+            "Ah Kd 7c 2s 2d 3d".parse::<Board>(),
+            Err(PokerError::InvalidBoardSize(6))
+        );
+        assert_eq!(
+            "Ah Kd Ah".parse::<Board>(),
+            Err(PokerError::DuplicateCard(parse_cards("Ah").unwrap()[0]))
+        );
+        assert!("Ah Kd 7x".parse::<Board>().is_err());
+    }
+
+    #[test]
+    fn test_analysis_on_a_board() {
+        let board: Board = "Jd9c2s".parse().unwrap();
+        let cards = parse_cards("Jd9c2s").unwrap();
+        let hands: Vec<[Card; 2]> = ["AsKc", "QhQs"]
+            .iter()
+            .map(|hand| hand.parse::<Hand>().unwrap().cards())
+            .collect();
+        assert_eq!(
+            board.compute_equity(&hands, &[]),
+            compute_equity(&hands, &cards, &[])
+        );
+
+        let range: Range = "QQ+, AK, JTs".parse().unwrap();
+        assert_eq!(
+            Ok(board.categorize(&range)),
+            range.categorize_on_board(&cards)
+        );
+
+        let hero = hands[1];
+        assert_eq!(
+            board.ahead_behind_breakdown(hero, &[]),
+            ahead_behind_breakdown(hero, &cards, &[])
+        );
+        assert!(Board::default().ahead_behind_breakdown(hero, &[]).is_err());
+        assert_eq!(
+            board.hand_kind_frequencies(hero, &[], EquityMethod::Exhaustive),
+            hand_kind_frequencies(hero, &cards, &[], EquityMethod::Exhaustive)
+        );
+    }
+}
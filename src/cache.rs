@@ -0,0 +1,236 @@
+use crate::{Card, EquityResult, PokerError, Suit};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Remembers the results of recently computed scenarios, so that repeating a
+/// scenario, even with the hands in a different order or the suits swapped
+/// around, doesn't compute it again.
+///
+/// Once `capacity` scenarios are stored, the least recently used one is
+/// forgotten to make room for the next.
+#[derive(Clone, Debug)]
+pub struct EquityCache {
+    capacity: usize,
+    entries: HashMap<Vec<u8>, CacheEntry>,
+    /// Counts every lookup, so that entries can be ordered by when they were
+    /// last used.
+    clock: u64,
+}
+
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    /// The results for each hand, in the order of the canonical scenario.
+    results: Vec<EquityResult>,
+    last_used: u64,
+}
+
+/// A scenario relabelled so that every scenario which differs only by the
+/// order of its hands, board and dead cards, or by a permutation of the suits,
+/// has the same key.
+struct CanonicalScenario {
+    key: Vec<u8>,
+    /// The index into the original hands of each canonical hand.
+    order: Vec<usize>,
+}
+
+/// Encodes a card as a byte, after relabelling its suit with `suits`.
+fn card_code(card: Card, suits: &[u8]) -> u8 {
+    suits[card.suit() as usize] << 4 | card.number() as u8
+}
+
+fn canonicalize(hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> CanonicalScenario {
+    let sorted_codes = |cards: &[Card], suits: &[u8]| {
+        let mut codes: Vec<u8> = cards.iter().map(|&card| card_code(card, suits)).collect();
+        codes.sort_unstable();
+        codes
+    };
+
+    Suit::ALL
+        .map(|suit| suit as u8)
+        .into_iter()
+        .permutations(Suit::ALL.len())
+        .map(|suits| {
+            let mut hand_codes: Vec<(Vec<u8>, usize)> = hands
+                .iter()
+                .enumerate()
+                .map(|(index, hand)| (sorted_codes(hand, &suits), index))
+                .collect();
+            hand_codes.sort_unstable();
+
+            // No card encodes to the separator, so the board and the dead
+            // cards can't run into each other.
+            let mut key: Vec<u8> = hand_codes
+                .iter()
+                .flat_map(|(codes, _)| codes)
+                .copied()
+                .collect();
+            key.push(u8::MAX);
+            key.extend(sorted_codes(board, &suits));
+            key.push(u8::MAX);
+            key.extend(sorted_codes(dead, &suits));
+
+            let order = hand_codes.into_iter().map(|(_, index)| index).collect();
+            CanonicalScenario { key, order }
+        })
+        .min_by(|a, b| a.key.cmp(&b.key))
+        .unwrap_or(CanonicalScenario {
+            key: vec![],
+            order: vec![],
+        })
+}
+
+impl EquityCache {
+    /// Creates an empty cache which stores up to `capacity` scenarios.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of scenarios which are stored.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the results for a scenario, calling `compute` only if neither
+    /// it nor an equivalent scenario is stored. `compute` must return one
+    /// result per hand, in the same order as `hands`, as
+    /// [`compute_equity`](crate::compute_equity) does.
+    ///
+    /// The results are always in the order of `hands`, whichever order the
+    /// stored scenario had them in.
+    ///
+    /// # Errors
+    ///
+    /// Will return the error from `compute`, in which case nothing is stored.
+    pub fn get_or_compute(
+        &mut self,
+        hands: &[[Card; 2]],
+        board: &[Card],
+        dead: &[Card],
+        compute: impl FnOnce() -> Result<Vec<EquityResult>, PokerError>,
+    ) -> Result<Vec<EquityResult>, PokerError> {
+        let CanonicalScenario { key, order } = canonicalize(hands, board, dead);
+        self.clock += 1;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+            let mut results = vec![EquityResult::default(); order.len()];
+            for (&index, &result) in order.iter().zip(&entry.results) {
+                results[index] = result;
+            }
+            return Ok(results);
+        }
+
+        let results = compute()?;
+        if self.capacity == 0 {
+            return Ok(results);
+        }
+        if self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                results: order.iter().map(|&index| results[index]).collect(),
+                last_used: self.clock,
+            },
+        );
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_equity, parse_cards};
+
+    fn scenario(hands: &str, board: &str) -> (Vec<[Card; 2]>, Vec<Card>) {
+        let cards = parse_cards(hands).unwrap();
+        (
+            cards.chunks(2).map(|hand| [hand[0], hand[1]]).collect(),
+            parse_cards(board).unwrap(),
+        )
+    }
+
+    fn lookup(
+        cache: &mut EquityCache,
+        hands: &str,
+        board: &str,
+        computed: &mut bool,
+    ) -> Vec<EquityResult> {
+        let (hands, board) = scenario(hands, board);
+        cache
+            .get_or_compute(&hands, &board, &[], || {
+                *computed = true;
+                compute_equity(&hands, &board, &[])
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_equivalent_scenarios_hit() {
+        let mut cache = EquityCache::new(4);
+        let mut computed = false;
+        let first = lookup(&mut cache, "AhQc KsKd", "2h7hJh5c", &mut computed);
+        assert!(computed);
+
+        // Hearts become spades, clubs become diamonds and diamonds become
+        // hearts, and the hands and board are reordered.
+        let mut computed = false;
+        let swapped = lookup(&mut cache, "KcKh AsQd", "7s5dJs2s", &mut computed);
+        assert!(!computed);
+        assert_eq!(swapped, [first[1], first[0]]);
+        let (hands, board) = scenario("KcKh AsQd", "7s5dJs2s");
+        assert_eq!(swapped, compute_equity(&hands, &board, &[]).unwrap());
+        assert_eq!(cache.len(), 1);
+
+        // Changing a card is a different scenario.
+        let mut computed = false;
+        lookup(&mut cache, "AhQc KsKd", "2h7hJh5d", &mut computed);
+        assert!(computed);
+
+        let error = cache.get_or_compute(&[], &[], &[], || Err(PokerError::Cancelled));
+        assert_eq!(error, Err(PokerError::Cancelled));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut cache = EquityCache::new(2);
+        let mut computed = false;
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc9c", &mut computed);
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc8c", &mut computed);
+        // Using the first scenario again makes the second the oldest.
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc9c", &mut computed);
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc7c", &mut computed);
+        assert_eq!(cache.len(), cache.capacity());
+
+        let mut computed = false;
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc9c", &mut computed);
+        assert!(!computed);
+        lookup(&mut cache, "AhQc KsKd", "2h7hJhTc8c", &mut computed);
+        assert!(computed);
+    }
+}
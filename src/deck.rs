@@ -0,0 +1,154 @@
+//! A deck of cards which can be shuffled and dealt from.
+
+use crate::{Card, PokerError};
+use rand::{seq::SliceRandom, Rng};
+use std::slice;
+
+/// The cards which are left to deal, in order from the top of the deck.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    /// Returns all 52 cards, in the same order as [`Card::ALL`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cards: Card::ALL.to_vec(),
+        }
+    }
+
+    /// Removes the given cards, such as the hands, the board and any dead
+    /// cards, leaving only those which can still be dealt. Cards which
+    /// aren't in the deck are ignored.
+    #[must_use]
+    pub fn without(mut self, cards: &[Card]) -> Self {
+        self.cards.retain(|card| !cards.contains(card));
+        self
+    }
+
+    /// Shuffles the cards which are left into a uniformly random order.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    /// Deals `count` cards from the top of the deck, removing them from it.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PokerError::NotEnoughCards`] if fewer than `count` cards
+    /// are left, in which case nothing is dealt.
+    pub fn deal(&mut self, count: usize) -> Result<Vec<Card>, PokerError> {
+        if count > self.cards.len() {
+            return Err(PokerError::NotEnoughCards {
+                needed: count,
+                remaining: self.cards.len(),
+            });
+        }
+        Ok(self.cards.drain(..count).collect())
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, card: Card) -> bool {
+        self.cards.contains(&card)
+    }
+
+    /// Returns the cards which are left, from the top of the deck.
+    #[must_use]
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Iterates over the cards which are left, from the top of the deck.
+    pub fn iter(&self) -> slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Deck> for Vec<Card> {
+    fn from(deck: Deck) -> Self {
+        deck.cards
+    }
+}
+
+impl IntoIterator for Deck {
+    type Item = Card;
+    type IntoIter = std::vec::IntoIter<Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a Card;
+    type IntoIter = slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_without() {
+        let deck = Deck::new();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(deck.cards(), Card::ALL);
+
+        let used = parse_cards("AsKc QhQs Jd9c2s").unwrap();
+        let deck = deck.without(&used);
+        assert_eq!(deck.len(), 45);
+        assert!(used.iter().all(|&card| !deck.contains(card)));
+        // Removing cards twice changes nothing.
+        assert_eq!(deck.clone().without(&used), deck);
+        assert!(deck.iter().is_sorted());
+    }
+
+    #[test]
+    fn test_shuffle_and_deal() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut deck = Deck::new();
+        deck.shuffle(&mut rng);
+        assert_ne!(deck.cards(), Card::ALL);
+
+        let top = deck.cards()[..5].to_vec();
+        let board = deck.deal(5).unwrap();
+        assert_eq!(board, top);
+        assert_eq!(deck.len(), 47);
+        assert!(board.iter().all(|&card| !deck.contains(card)));
+
+        assert_eq!(
+            deck.deal(48),
+            Err(PokerError::NotEnoughCards {
+                needed: 48,
+                remaining: 47
+            })
+        );
+        assert_eq!(deck.deal(47).unwrap().len(), 47);
+        assert!(deck.is_empty());
+        assert_eq!(deck.deal(0), Ok(vec![]));
+    }
+}
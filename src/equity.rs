@@ -0,0 +1,2455 @@
+use crate::{
+    combine, evaluate_hand, Card, Deck, EvalState, HandCounts, HandEvaluation, HandKind, Number,
+    PokerError, Suit,
+};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// How often one hand won, lost and tied over a number of boards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EquityResult {
+    pub win_count: u64,
+    pub loss_count: u64,
+    pub tie_count: u64,
+    pub count: u64,
+}
+
+impl EquityResult {
+    /// Adds the counts of another result for the same hand, such as one
+    /// computed over a different part of the runouts.
+    pub const fn merge(&mut self, other: &Self) {
+        self.win_count += other.win_count;
+        self.loss_count += other.loss_count;
+        self.tie_count += other.tie_count;
+        self.count += other.count;
+    }
+
+    /// Returns `count` as a share of all the boards, between 0 and 1, or zero
+    /// when no boards have been counted.
+    #[allow(clippy::cast_precision_loss)]
+    fn fraction(&self, count: u64) -> f64 {
+        if self.count == 0 {
+            0.
+        } else {
+            count as f64 / self.count as f64
+        }
+    }
+
+    /// Returns the share of boards which were won outright.
+    #[must_use]
+    pub fn win_fraction(&self) -> f64 {
+        self.fraction(self.win_count)
+    }
+
+    /// Returns the share of boards which were lost.
+    #[must_use]
+    pub fn loss_fraction(&self) -> f64 {
+        self.fraction(self.loss_count)
+    }
+
+    /// Returns the share of boards which were tied.
+    #[must_use]
+    pub fn tie_fraction(&self) -> f64 {
+        self.fraction(self.tie_count)
+    }
+
+    /// Returns the percentage of boards which were won outright.
+    #[must_use]
+    pub fn win_pct(&self) -> f64 {
+        self.win_fraction() * 100.
+    }
+
+    /// Returns the percentage of boards which were lost.
+    #[must_use]
+    pub fn loss_pct(&self) -> f64 {
+        self.loss_fraction() * 100.
+    }
+
+    /// Returns the percentage of boards which were tied.
+    #[must_use]
+    pub fn tie_pct(&self) -> f64 {
+        self.tie_fraction() * 100.
+    }
+
+    /// Returns the share of the pot this hand expects to win, between 0 and
+    /// 1, counting a tie as half a win.
+    ///
+    /// Ties between more than two hands split the pot further, so this is
+    /// only exact heads-up.
+    #[must_use]
+    pub fn equity(&self) -> f64 {
+        self.win_fraction() + self.tie_fraction() / 2.
+    }
+
+    /// Returns the standard error of [`equity`](Self::equity) when the boards
+    /// were dealt at random, using the binomial approximation. Exact results
+    /// have no error, but this can't tell them apart.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn standard_error(&self) -> f64 {
+        if self.count == 0 {
+            return 0.;
+        }
+        let equity = self.equity();
+        (equity * (1. - equity) / self.count as f64).sqrt()
+    }
+
+    /// Returns the bounds of a confidence interval for the chance of winning
+    /// outright, as [`equity_confidence_interval`] does.
+    #[must_use]
+    pub fn win_confidence_interval(&self, z: f64) -> (f64, f64) {
+        equity_confidence_interval(self.win_count, self.count, z)
+    }
+}
+
+/// Returns the lower and upper bounds of the Wilson score interval for the
+/// chance of winning, given `win` wins out of `count` random boards.
+///
+/// The interval covers the true chance with the confidence of `z` standard
+/// deviations, such as 1.96 for 95%. Unlike the normal approximation, it
+/// stays between 0 and 1, even after few boards or when every board was won.
+/// With no boards at all, the chance could be anything from 0 to 1.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn equity_confidence_interval(win: u64, count: u64, z: f64) -> (f64, f64) {
+    if count == 0 {
+        return (0., 1.);
+    }
+    let (win, count) = (win as f64, count as f64);
+    let p = win / count;
+    let z_squared = z * z;
+
+    let denominator = 1. + z_squared / count;
+    let center = z_squared.mul_add(0.5 / count, p) / denominator;
+    let spread = (p * (1. - p) / count + z_squared / (4. * count * count)).sqrt();
+    let half_width = z * spread / denominator;
+    ((center - half_width).max(0.), (center + half_width).min(1.))
+}
+
+impl fmt::Display for EquityResult {
+    /// Writes the win, loss and tie percentages, such as
+    /// `"61.3% / 37.2% / 1.5%"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.1}% / {:.1}% / {:.1}%",
+            self.win_pct(),
+            self.loss_pct(),
+            self.tie_pct()
+        )
+    }
+}
+
+impl Add for EquityResult {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self.merge(&other);
+        self
+    }
+}
+
+impl AddAssign for EquityResult {
+    fn add_assign(&mut self, other: Self) {
+        self.merge(&other);
+    }
+}
+
+impl Sum for EquityResult {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Merges the results for every hand of a scenario, pairing them up by
+/// position. An empty `totals` is first filled with zeroed results, so it can
+/// be used as the starting point of a reduction.
+///
+/// # Panics
+///
+/// Will panic if `totals` is not empty and has a different length to
+/// `results`.
+pub fn merge_results(totals: &mut Vec<EquityResult>, results: &[EquityResult]) {
+    if totals.is_empty() {
+        totals.resize(results.len(), EquityResult::default());
+    }
+    assert_eq!(
+        totals.len(),
+        results.len(),
+        "results must be for the same hands"
+    );
+    for (total, result) in totals.iter_mut().zip(results) {
+        total.merge(result);
+    }
+}
+
+/// How equity should be computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EquityMethod {
+    /// Enumerate every runout for an exact answer.
+    Exhaustive,
+    /// Estimate the answer from `samples` random runouts.
+    MonteCarlo { samples: u64, seed: Option<u64> },
+}
+
+/// Builds the deck of cards which can still be dealt, rejecting scenarios
+/// which use the same card more than once or leave too few cards to finish
+/// the board.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, if any card is
+/// used more than once, or if fewer cards are left than the board is missing.
+pub fn remaining_deck(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<Card>, PokerError> {
+    if board.len() > 5 {
+        return Err(PokerError::InvalidBoardSize(board.len()));
+    }
+
+    let mut used = vec![];
+    for &card in hands.iter().flatten().chain(board).chain(dead) {
+        if used.contains(&card) {
+            return Err(PokerError::DuplicateCard(card));
+        }
+        used.push(card);
+    }
+
+    let deck: Vec<Card> = Deck::new().without(&used).into();
+    let needed = 5 - board.len();
+    if deck.len() < needed {
+        return Err(PokerError::NotEnoughCards {
+            needed,
+            remaining: deck.len(),
+        });
+    }
+    Ok(deck)
+}
+
+/// Copies the known board cards into the front of a full five card board.
+/// The remaining slots are overwritten with each runout.
+fn starting_board(board: &[Card]) -> [Card; 5] {
+    let mut full_board = [Card::new(Suit::Hearts, Number::Two); 5];
+    full_board[..board.len()].copy_from_slice(board);
+    full_board
+}
+
+/// Evaluates every hand against a complete board, and scores the showdown as
+/// [`score`] does.
+fn showdown(
+    hands: &[[Card; 2]],
+    board: [Card; 5],
+    evaluations: &mut [HandEvaluation],
+    results: &mut [EquityResult],
+    weight: u64,
+) {
+    for (evaluation, hand) in evaluations.iter_mut().zip(hands) {
+        *evaluation = evaluate_hand(combine(*hand, board));
+    }
+    score(evaluations, results, weight);
+}
+
+/// Credits the single best hand with a win, every hand sharing the best
+/// evaluation with a tie, and everyone else with a loss. The showdown is
+/// counted `weight` times.
+fn score(evaluations: &[HandEvaluation], results: &mut [EquityResult], weight: u64) {
+    let Some(&best) = evaluations.iter().max() else {
+        return;
+    };
+    let winners = evaluations
+        .iter()
+        .filter(|&&evaluation| evaluation == best)
+        .count();
+
+    for (evaluation, result) in evaluations.iter().zip(results.iter_mut()) {
+        if *evaluation != best {
+            result.loss_count += weight;
+        } else if winners == 1 {
+            result.win_count += weight;
+        } else {
+            result.tie_count += weight;
+        }
+        result.count += weight;
+    }
+}
+
+const fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let mut result = 1;
+    let mut i = 0;
+    while i < k {
+        result = result * (n - i) / (i + 1);
+        i += 1;
+    }
+    result
+}
+
+/// Computes the exact equity of every hand by enumerating every way the rest
+/// of the board can be dealt. The results are in the same order as `hands`.
+///
+/// A complete board is the only runout of itself, so every result has a count
+/// of 1. A hand on its own wins every runout, as there is nobody to beat, and
+/// no hands give no results.
+///
+/// The hands can be given as [`HoleCards`](crate::HoleCards),
+/// [`Hand`](crate::Hand) or plain pairs of cards.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, if any card is
+/// used more than once, or if there aren't enough cards left to finish the
+/// board.
+pub fn compute_equity(
+    hands: &[impl Copy + Into<[Card; 2]>],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<EquityResult>, PokerError> {
+    let hands: Vec<[Card; 2]> = hands.iter().map(|&hand| hand.into()).collect();
+    let hands = &hands[..];
+    let mut results = vec![EquityResult::default(); hands.len()];
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    score_every_runout(hands, board, dead, &mut evaluations, &mut results)?;
+    Ok(results)
+}
+
+/// Returns the bit standing for a card in a set of cards, where each suit
+/// has 16 bits to itself.
+fn card_bit(card: Card) -> u64 {
+    1 << (card.suit() as u64 * 16 + card.number() as u64)
+}
+
+/// Computes exactly the same results as [`compute_equity`] for two hands and
+/// an empty board, which is by far the most common scenario, only faster.
+///
+/// Each card of the board is added to the counts of both hands as it is
+/// dealt, so that the first four cards of a runout are counted once for all
+/// of the runouts which share them, rather than once per runout.
+///
+/// # Errors
+///
+/// Will return an error if any card is used more than once.
+pub fn compute_equity_headsup(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+) -> Result<[EquityResult; 2], PokerError> {
+    headsup_with_dead(hero.into(), villain.into(), &[])
+}
+
+/// Runs [`compute_equity_headsup`] with the dead cards left out of the deck,
+/// which lets the tests compare it against [`compute_equity`] on fewer
+/// runouts.
+fn headsup_with_dead(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    dead: &[Card],
+) -> Result<[EquityResult; 2], PokerError> {
+    let mut used = 0;
+    for &card in hero.iter().chain(&villain).chain(dead) {
+        if used & card_bit(card) != 0 {
+            return Err(PokerError::DuplicateCard(card));
+        }
+        used |= card_bit(card);
+    }
+    let deck: Vec<Card> = Card::ALL
+        .into_iter()
+        .filter(|&card| used & card_bit(card) == 0)
+        .collect();
+
+    let (mut win_count, mut loss_count, mut tie_count) = (0, 0, 0);
+    let add_card = |counts: [HandCounts; 2], card: Card| counts.map(|counts| counts.with(card));
+    let start = [HandCounts::new(&hero), HandCounts::new(&villain)];
+    for (a, &first) in deck.iter().enumerate() {
+        let one = add_card(start, first);
+        for (b, &second) in deck.iter().enumerate().skip(a + 1) {
+            let two = add_card(one, second);
+            for (c, &third) in deck.iter().enumerate().skip(b + 1) {
+                let three = add_card(two, third);
+                for (d, &fourth) in deck.iter().enumerate().skip(c + 1) {
+                    let four = add_card(three, fourth);
+                    for &fifth in &deck[d + 1..] {
+                        let [hero, villain] =
+                            add_card(four, fifth).map(|counts| counts.best_high());
+                        match hero.cmp(&villain) {
+                            Ordering::Greater => win_count += 1,
+                            Ordering::Less => loss_count += 1,
+                            Ordering::Equal => tie_count += 1,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let count = win_count + loss_count + tie_count;
+    Ok([
+        EquityResult {
+            win_count,
+            loss_count,
+            tie_count,
+            count,
+        },
+        EquityResult {
+            win_count: loss_count,
+            loss_count: win_count,
+            tie_count,
+            count,
+        },
+    ])
+}
+
+/// Computes exactly the same results as [`compute_equity`] for a fixed number
+/// of hands, so the results can be destructured and can't get out of step
+/// with the hands.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn compute_equity_n<const N: usize>(
+    hands: [[Card; 2]; N],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<[EquityResult; N], PokerError> {
+    let mut results = [EquityResult::default(); N];
+    let mut evaluations = [HandEvaluation::new_high_card(0); N];
+    score_every_runout(&hands, board, dead, &mut evaluations, &mut results)?;
+    Ok(results)
+}
+
+/// The results of a multiway scenario, along with exactly who shared each
+/// pot which was split.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiwayResult {
+    /// The results for each hand, in the same order as the hands.
+    pub results: Vec<EquityResult>,
+    /// The number of boards split by each set of hands, keyed by a bitmask
+    /// with bit `i` set when hand `i` shared the pot.
+    pub split_pots: BTreeMap<u32, u64>,
+}
+
+impl MultiwayResult {
+    /// Returns the number of boards on which hands `a` and `b` both shared
+    /// the pot, whoever else shared it with them.
+    #[must_use]
+    pub fn ties_between(&self, a: usize, b: usize) -> u64 {
+        let pair = 1 << a | 1 << b;
+        self.split_pots
+            .iter()
+            .filter(|&(&tied, _)| tied & pair == pair)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// Returns the share of the pot a hand wins on average, between 0 and 1,
+    /// dividing each split pot equally between the hands which shared it.
+    /// Unlike [`EquityResult::equity`], this is exact however many hands tie.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn pot_share(&self, hand: usize) -> f64 {
+        let result = self.results[hand];
+        if result.count == 0 {
+            return 0.;
+        }
+        let split_shares: f64 = self
+            .split_pots
+            .iter()
+            .filter(|&(&tied, _)| tied & 1 << hand != 0)
+            .map(|(&tied, &count)| count as f64 / f64::from(tied.count_ones()))
+            .sum();
+        (result.win_count as f64 + split_shares) / result.count as f64
+    }
+}
+
+/// Computes exactly the same results as [`compute_equity`], while also
+/// recording which hands shared each split pot, so that pots split three or
+/// more ways can be divided exactly.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn compute_equity_multiway(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<MultiwayResult, PokerError> {
+    // Only 26 hands can be dealt without repeating a card, so every hand has
+    // a bit in the mask.
+    let deck = remaining_deck(hands, board, dead)?;
+    let mut full_board = starting_board(board);
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    let mut result = MultiwayResult {
+        results: vec![EquityResult::default(); hands.len()],
+        split_pots: BTreeMap::new(),
+    };
+
+    for runout in deck.into_iter().combinations(5 - board.len()) {
+        full_board[board.len()..].copy_from_slice(&runout);
+        showdown(hands, full_board, &mut evaluations, &mut result.results, 1);
+
+        let Some(&best) = evaluations.iter().max() else {
+            continue;
+        };
+        let tied = evaluations
+            .iter()
+            .enumerate()
+            .filter(|&(_, &evaluation)| evaluation == best)
+            .fold(0_u32, |tied, (index, _)| tied | 1 << index);
+        if tied.count_ones() > 1 {
+            *result.split_pots.entry(tied).or_default() += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// Counts how many ways the pot was split on every runout. Entry `k` is the
+/// number of runouts on which `k + 1` hands shared the pot, so the first
+/// entry counts the runouts with a single winner.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn split_breakdown(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<u64>, PokerError> {
+    let MultiwayResult {
+        results,
+        split_pots,
+    } = compute_equity_multiway(hands, board, dead)?;
+    let mut breakdown = vec![0; hands.len()];
+    for (tied, count) in split_pots {
+        breakdown[tied.count_ones() as usize - 1] += count;
+    }
+    // Every runout which wasn't split had a single winner.
+    let runouts = results.first().map_or(0, |result| result.count);
+    let split: u64 = breakdown.iter().sum();
+    if let Some(single_winner) = breakdown.first_mut() {
+        *single_winner = runouts - split;
+    }
+    Ok(breakdown)
+}
+
+/// Splits the chips each hand put in into a main pot and side pots, from the
+/// smallest to the largest. Each pot is the chips in it along with a bitmask
+/// of the hands which can win it, which are those that put in at least as
+/// much as the pot's level.
+fn side_pots(contributions: &[u64]) -> Vec<(u64, u32)> {
+    let mut levels = contributions.to_vec();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut previous = 0;
+    levels
+        .into_iter()
+        .filter(|&level| level > 0)
+        .map(|level| {
+            let chips = contributions
+                .iter()
+                .map(|&contribution| contribution.min(level) - contribution.min(previous))
+                .sum();
+            let eligible = contributions
+                .iter()
+                .enumerate()
+                .filter(|&(_, &contribution)| contribution >= level)
+                .fold(0_u32, |eligible, (index, _)| eligible | 1 << index);
+            previous = level;
+            (chips, eligible)
+        })
+        .collect()
+}
+
+/// Computes how many chips each hand gets back on average, when each hand
+/// has put `contributions[i]` chips in and some are all-in for less than the
+/// others. The results are in the same order as `hands`.
+///
+/// On every runout, the main pot and each side pot go to the best hands which
+/// put in enough to be part of it, and are split evenly between ties. Chips
+/// which nobody else matched always go back to the hand which put them in.
+///
+/// # Errors
+///
+/// Will return an error if there isn't one contribution for each hand, if
+/// the board has more than five cards, or if any card is used more than once.
+#[allow(clippy::cast_precision_loss)]
+pub fn compute_side_pot_equity(
+    hands: &[[Card; 2]],
+    contributions: &[u64],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<f64>, PokerError> {
+    if contributions.len() != hands.len() {
+        return Err(PokerError::InvalidRequest(format!(
+            "{} hands need as many contributions, but found {}",
+            hands.len(),
+            contributions.len()
+        )));
+    }
+    // As for compute_equity_multiway, every hand has a bit in the masks.
+    let deck = remaining_deck(hands, board, dead)?;
+    let pots = side_pots(contributions);
+    let mut full_board = starting_board(board);
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    let mut results = vec![EquityResult::default(); hands.len()];
+    let mut returns = vec![0.; hands.len()];
+    let mut runouts = 0_u64;
+
+    for runout in deck.into_iter().combinations(5 - board.len()) {
+        full_board[board.len()..].copy_from_slice(&runout);
+        showdown(hands, full_board, &mut evaluations, &mut results, 1);
+        runouts += 1;
+
+        for &(chips, eligible) in &pots {
+            let in_pot = |index: usize| eligible & 1 << index != 0;
+            let Some(best) = (0..hands.len())
+                .filter(|&index| in_pot(index))
+                .map(|index| evaluations[index])
+                .max()
+            else {
+                continue;
+            };
+            let winners: Vec<usize> = (0..hands.len())
+                .filter(|&index| in_pot(index) && evaluations[index] == best)
+                .collect();
+            let share = chips as f64 / winners.len() as f64;
+            for winner in winners {
+                returns[winner] += share;
+            }
+        }
+    }
+
+    for chips in &mut returns {
+        *chips /= runouts as f64;
+    }
+    Ok(returns)
+}
+
+/// Computes equity as [`compute_equity`] does, but only among the hands
+/// which are still active, for when `active[i]` is false because hand `i`
+/// has folded. The results are in the same order as `hands`.
+///
+/// Folded hands can't win, so they lose every runout, but their cards are
+/// still known to be out of the deck and are never dealt.
+///
+/// # Errors
+///
+/// Will return an error if there isn't one flag for each hand, if the board
+/// has more than five cards, or if any card is used more than once.
+pub fn compute_equity_active(
+    hands: &[[Card; 2]],
+    active: &[bool],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<EquityResult>, PokerError> {
+    if active.len() != hands.len() {
+        return Err(PokerError::InvalidRequest(format!(
+            "{} hands need as many active flags, but found {}",
+            hands.len(),
+            active.len()
+        )));
+    }
+    let (live, folded): (Vec<_>, Vec<_>) = hands
+        .iter()
+        .zip(active)
+        .partition(|&(_, &is_active)| is_active);
+    let live: Vec<[Card; 2]> = live.into_iter().map(|(&hand, _)| hand).collect();
+    let mut dead = dead.to_vec();
+    dead.extend(folded.into_iter().flat_map(|(hand, _)| hand));
+
+    let count = runout_count(&live, board, &dead)?;
+    let mut live_results = compute_equity(&live, board, &dead)?.into_iter();
+    Ok(active
+        .iter()
+        .map(|&is_active| {
+            let folded = EquityResult {
+                loss_count: count,
+                count,
+                ..EquityResult::default()
+            };
+            if is_active {
+                live_results.next().unwrap_or(folded)
+            } else {
+                folded
+            }
+        })
+        .collect())
+}
+
+/// Extra bookkeeping for [`compute_equity_with_options`], which is all off
+/// by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EquityOptions {
+    /// Whether to count which kind of hand each hand made on every board.
+    pub track_hand_kinds: bool,
+}
+
+/// How often a hand finished as each kind of hand, split by whether it won.
+///
+/// Both arrays are indexed by the [`HandKind`] as a number, and a board
+/// counts as won when the hand wins at least a share of the pot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HandKindBreakdown {
+    pub won: [u64; 9],
+    pub lost: [u64; 9],
+}
+
+impl HandKindBreakdown {
+    /// Returns the share of the boards the hand won on which it made `kind`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn won_with(&self, kind: HandKind) -> f64 {
+        let won: u64 = self.won.iter().sum();
+        if won == 0 {
+            0.
+        } else {
+            self.won[kind as usize] as f64 / won as f64
+        }
+    }
+
+    /// Returns the share of the boards the hand lost on which it made
+    /// `kind`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn lost_with(&self, kind: HandKind) -> f64 {
+        let lost: u64 = self.lost.iter().sum();
+        if lost == 0 {
+            0.
+        } else {
+            self.lost[kind as usize] as f64 / lost as f64
+        }
+    }
+}
+
+/// The result of one hand from [`compute_equity_with_options`], with the
+/// extra bookkeeping which was asked for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DetailedEquityResult {
+    pub result: EquityResult,
+    /// Set when [`EquityOptions::track_hand_kinds`] is.
+    pub hand_kinds: Option<HandKindBreakdown>,
+}
+
+/// Computes exactly the same results as [`compute_equity`], along with
+/// whatever extra bookkeeping `options` asks for. With the default options,
+/// this is as fast as [`compute_equity`].
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn compute_equity_with_options(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    options: EquityOptions,
+) -> Result<Vec<DetailedEquityResult>, PokerError> {
+    if !options.track_hand_kinds {
+        return Ok(compute_equity(hands, board, dead)?
+            .into_iter()
+            .map(|result| DetailedEquityResult {
+                result,
+                hand_kinds: None,
+            })
+            .collect());
+    }
+
+    let deck = remaining_deck(hands, board, dead)?;
+    let mut full_board = starting_board(board);
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    let mut results = vec![EquityResult::default(); hands.len()];
+    let mut breakdowns = vec![HandKindBreakdown::default(); hands.len()];
+
+    for runout in deck.into_iter().combinations(5 - board.len()) {
+        full_board[board.len()..].copy_from_slice(&runout);
+        showdown(hands, full_board, &mut evaluations, &mut results, 1);
+
+        let Some(&best) = evaluations.iter().max() else {
+            continue;
+        };
+        for (evaluation, breakdown) in evaluations.iter().zip(&mut breakdowns) {
+            let counts = if *evaluation == best {
+                &mut breakdown.won
+            } else {
+                &mut breakdown.lost
+            };
+            counts[evaluation.kind() as usize] += 1;
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .zip(breakdowns)
+        .map(|(result, breakdown)| DetailedEquityResult {
+            result,
+            hand_kinds: Some(breakdown),
+        })
+        .collect())
+}
+
+/// Adds the showdown of every way the rest of the board can be dealt to
+/// `results`, using `evaluations` as scratch space. Both must have one entry
+/// per hand.
+fn score_every_runout(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    evaluations: &mut [HandEvaluation],
+    results: &mut [EquityResult],
+) -> Result<(), PokerError> {
+    let deck = remaining_deck(hands, board, dead)?;
+    let mut states: Vec<EvalState> = hands
+        .iter()
+        .map(|hand| EvalState::new(&[&hand[..], board].concat()))
+        .collect();
+    deal_runouts(&deck, 5 - board.len(), &mut states, evaluations, results);
+    Ok(())
+}
+
+/// Deals every combination of `remaining` cards from `deck` to the state of
+/// each hand, and scores the showdown of each complete board.
+///
+/// Each card is added to the states once for every runout which starts with
+/// it, rather than counting every card again for each runout.
+fn deal_runouts(
+    deck: &[Card],
+    remaining: usize,
+    states: &mut [EvalState],
+    evaluations: &mut [HandEvaluation],
+    results: &mut [EquityResult],
+) {
+    if remaining == 0 {
+        // Every state holds seven cards by now.
+        for (evaluation, state) in evaluations.iter_mut().zip(&*states) {
+            *evaluation = state.counts.best_high();
+        }
+        score(evaluations, results, 1);
+        return;
+    }
+
+    // Leave enough cards after this one to finish the runout.
+    for (index, &card) in deck.iter().enumerate().take(deck.len() + 1 - remaining) {
+        for state in states.iter_mut() {
+            state.add_card(card);
+        }
+        deal_runouts(
+            &deck[index + 1..],
+            remaining - 1,
+            states,
+            evaluations,
+            results,
+        );
+        for state in states.iter_mut() {
+            state.remove_card(card);
+        }
+    }
+}
+
+/// A complete board, the hero's and the villain's made hands on it, and how
+/// the hero's hand compares to the villain's.
+pub type RunoutShowdown = (Vec<Card>, HandEvaluation, HandEvaluation, Ordering);
+
+/// Lists every runout [`compute_equity`] would enumerate for two hands, with
+/// both players' made hands and who won.
+///
+/// This is much slower than computing the equity, and is meant for checking
+/// the evaluator against a disputed spot.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn compute_equity_verbose(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<RunoutShowdown>, PokerError> {
+    let deck = remaining_deck(&[hero, villain], board, dead)?;
+    let mut full_board = starting_board(board);
+
+    Ok(deck
+        .into_iter()
+        .combinations(5 - board.len())
+        .map(|runout| {
+            full_board[board.len()..].copy_from_slice(&runout);
+            let hero = evaluate_hand(combine(hero, full_board));
+            let villain = evaluate_hand(combine(villain, full_board));
+            (full_board.to_vec(), hero, villain, hero.cmp(&villain))
+        })
+        .collect())
+}
+
+/// Iterates over every complete board which can follow a flop, with each
+/// unordered turn and river pair appearing once.
+///
+/// Cards on the flop or in `dead`, which should include any known hole cards,
+/// are never dealt.
+pub fn enumerate_runouts(board3: [Card; 3], dead: &[Card]) -> impl Iterator<Item = [Card; 5]> {
+    Deck::new()
+        .without(&board3)
+        .without(dead)
+        .into_iter()
+        .tuple_combinations()
+        .map(move |(turn, river)| [board3[0], board3[1], board3[2], turn, river])
+}
+
+/// Groups the suits which can be swapped for each other without changing any
+/// known card. Two suits are interchangeable when every hand, the board and
+/// the dead cards hold exactly the same numbers in both of them.
+fn interchangeable_suits(hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> Vec<Vec<usize>> {
+    let mut signatures = vec![vec![0_u16; hands.len() + 2]; 4];
+    let groups = hands.iter().map(<[Card; 2]>::as_slice).chain([board, dead]);
+    for (group_index, group) in groups.enumerate() {
+        for card in group {
+            signatures[card.suit() as usize][group_index] |= card.number().as_bit();
+        }
+    }
+
+    let mut classes: Vec<Vec<usize>> = vec![];
+    for suit in Suit::ALL.map(|suit| suit as usize) {
+        match classes
+            .iter_mut()
+            .find(|class| signatures[class[0]] == signatures[suit])
+        {
+            Some(class) => class.push(suit),
+            None => classes.push(vec![suit]),
+        }
+    }
+    classes
+}
+
+/// Packs a runout into one number bitset per suit, relabelling the suits so
+/// that every runout related by swapping interchangeable suits packs to the
+/// same key.
+fn canonical_runout(runout: &[Card], classes: &[Vec<usize>]) -> u64 {
+    let mut bitsets = [0_u16; 4];
+    for card in runout {
+        bitsets[card.suit() as usize] |= card.number().as_bit();
+    }
+
+    let mut key = 0;
+    for class in classes {
+        let mut class_bitsets = [0_u16; 4];
+        for (class_bitset, &suit) in class_bitsets.iter_mut().zip(class) {
+            *class_bitset = bitsets[suit];
+        }
+        let class_bitsets = &mut class_bitsets[..class.len()];
+        class_bitsets.sort_unstable_by(|a, b| b.cmp(a));
+        for (&suit, &bitset) in class.iter().zip(class_bitsets.iter()) {
+            key |= u64::from(bitset) << (16 * suit);
+        }
+    }
+    key
+}
+
+/// Unpacks a key made by [`canonical_runout`] back into a runout.
+fn runout_from_key(key: u64, runout: &mut [Card]) {
+    let mut cards = runout.iter_mut();
+    for suit in Suit::ALL {
+        for number in Number::ALL {
+            if key >> (16 * suit as u8) & u64::from(number.as_bit()) != 0 {
+                if let Some(card) = cards.next() {
+                    *card = Card::new(suit, number);
+                }
+            }
+        }
+    }
+}
+
+/// Returns every river card which lets the hero win or tie against the
+/// villain on a four card board, in deck order.
+///
+/// # Errors
+///
+/// Will return an error if the board doesn't have exactly four cards, or if
+/// any card is used more than once.
+pub fn rivers_that_win(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+    board: &[Card],
+) -> Result<Vec<Card>, PokerError> {
+    let (hero, villain) = (hero.into(), villain.into());
+    if board.len() != 4 {
+        return Err(PokerError::InvalidBoardSize(board.len()));
+    }
+    let deck = remaining_deck(&[hero, villain], board, &[])?;
+
+    let evaluate = |hand: [Card; 2], river: Card| {
+        evaluate_hand(combine(
+            hand,
+            [board[0], board[1], board[2], board[3], river],
+        ))
+    };
+    Ok(deck
+        .into_iter()
+        .filter(|&river| evaluate(hero, river) >= evaluate(villain, river))
+        .collect())
+}
+
+/// Returns whether the hero is freerolling the villain: the hero never loses
+/// on any runout of the board, but wins on at least one of them.
+///
+/// Scenarios which use a card twice, or have more than five board cards, are never
+/// freerolls.
+#[must_use]
+pub fn is_freeroll(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+    board: &[Card],
+) -> bool {
+    compute_equity_n([hero.into(), villain.into()], board, &[])
+        .is_ok_and(|[hero, _]| hero.loss_count == 0 && hero.win_count > 0)
+}
+
+/// Returns whether the hand at `hero` in `hands` is drawing dead: it neither
+/// wins nor ties on any runout of the board against the rest of the field.
+///
+/// Scenarios which use a card twice, have more than five board cards or have
+/// no hand at `hero` are never drawing dead.
+#[must_use]
+pub fn drawing_dead(hero: usize, hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> bool {
+    hero < hands.len()
+        && compute_equity(hands, board, dead)
+            .is_ok_and(|results| results[hero].win_count == 0 && results[hero].tie_count == 0)
+}
+
+/// Returns whether the hand which is ahead on the board now stays ahead on
+/// every runout, so that no cards still to come can change the winner.
+///
+/// When the hero is ahead, this means the hero has the effective nuts against
+/// this villain. A hand which is tied now is locked only if it ties every
+/// runout.
+///
+/// There is no leader before the flop, so scenarios with fewer than three
+/// board cards are never locked, and neither are scenarios which use a card
+/// twice or have more than five board cards.
+#[must_use]
+pub fn is_hand_locked(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+    board: &[Card],
+) -> bool {
+    let (hero, villain) = (hero.into(), villain.into());
+    if board.len() < 3 {
+        return false;
+    }
+    let Ok([result, _]) = compute_equity_n([hero, villain], board, &[]) else {
+        return false;
+    };
+    let evaluate = |hand: [Card; 2]| HandCounts::new(&[&hand[..], board].concat()).best_high();
+    let always = match evaluate(hero).cmp(&evaluate(villain)) {
+        Ordering::Greater => result.win_count,
+        Ordering::Less => result.loss_count,
+        Ordering::Equal => result.tie_count,
+    };
+    always == result.count
+}
+
+/// Returns `(behind, tied, ahead)` for the hero on the board as it is now:
+/// the fractions of every two cards a villain could hold which beat, tie
+/// with and lose to the hero's hand.
+///
+/// This is a snapshot of the current street rather than equity: the cards
+/// still to come are ignored, so a flush draw counts as behind a pair.
+///
+/// # Errors
+///
+/// Will return an error if the board has fewer than three or more than five
+/// cards, or if any card is used more than once.
+#[allow(clippy::cast_precision_loss)]
+pub fn ahead_behind_breakdown(
+    hero: impl Into<[Card; 2]>,
+    board: &[Card],
+    dead: &[Card],
+) -> Result<(f64, f64, f64), PokerError> {
+    let hero = hero.into();
+    if board.len() < 3 {
+        return Err(PokerError::InvalidRequest(format!(
+            "hands can only be compared from the flop on, but the board has {} cards",
+            board.len()
+        )));
+    }
+    let deck = remaining_deck(&[hero], board, dead)?;
+    if deck.len() < 2 {
+        return Err(PokerError::NotEnoughCards {
+            needed: 2,
+            remaining: deck.len(),
+        });
+    }
+
+    let evaluate = |hand: [Card; 2]| HandCounts::new(&[&hand[..], board].concat()).best_high();
+    let hero = evaluate(hero);
+    let (mut behind, mut tied, mut ahead) = (0_u64, 0_u64, 0_u64);
+    for (&first, &second) in deck.iter().tuple_combinations() {
+        match hero.cmp(&evaluate([first, second])) {
+            Ordering::Less => behind += 1,
+            Ordering::Equal => tied += 1,
+            Ordering::Greater => ahead += 1,
+        }
+    }
+    let total = (behind + tied + ahead) as f64;
+    Ok((
+        behind as f64 / total,
+        tied as f64 / total,
+        ahead as f64 / total,
+    ))
+}
+
+/// Computes exactly the same results as [`compute_equity`], while evaluating
+/// fewer runouts.
+///
+/// Runouts which are identical up to swapping interchangeable suits always
+/// have the same showdown, so only one runout of each such class is evaluated
+/// and its result is weighted by the size of the class.
+///
+/// This pays off when the hands leave several suits interchangeable, such as
+/// `AsKs` against `QdQc`, where diamonds and clubs can be swapped freely.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if any card
+/// is used more than once.
+pub fn compute_equity_memoized(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<EquityResult>, PokerError> {
+    let deck = remaining_deck(hands, board, dead)?;
+    let classes = interchangeable_suits(hands, board, dead);
+
+    let mut multiplicities: HashMap<u64, u64> = HashMap::new();
+    for runout in deck.into_iter().combinations(5 - board.len()) {
+        *multiplicities
+            .entry(canonical_runout(&runout, &classes))
+            .or_default() += 1;
+    }
+
+    let mut results = vec![EquityResult::default(); hands.len()];
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    let mut full_board = starting_board(board);
+
+    for (key, weight) in multiplicities {
+        runout_from_key(key, &mut full_board[board.len()..]);
+        showdown(hands, full_board, &mut evaluations, &mut results, weight);
+    }
+
+    Ok(results)
+}
+
+/// Returns the number of runouts [`compute_equity`] would need to evaluate,
+/// which is useful for deciding whether an exact answer is affordable.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, if any card is
+/// used more than once, or if there aren't enough cards left to finish the
+/// board.
+pub fn runout_count(hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> Result<u64, PokerError> {
+    let deck = remaining_deck(hands, board, dead)?;
+    Ok(binomial(deck.len() as u64, 5 - board.len() as u64))
+}
+
+/// Partially shuffles the deck, so that its first `count` cards are a
+/// uniformly random deal, and returns them.
+fn deal<'a>(rng: &mut StdRng, deck: &'a mut [Card], count: usize) -> &'a [Card] {
+    for i in 0..count {
+        let j = rng.gen_range(i..deck.len());
+        deck.swap(i, j);
+    }
+    &deck[..count]
+}
+
+/// The share of the pot one hand won over the trials of
+/// [`compute_equity_run_it_n`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RunItResult {
+    /// The hand's share of the pot, summed over every trial.
+    pub pot_share: f64,
+    /// The square of the hand's share of the pot, summed over every trial.
+    pub pot_share_squared: f64,
+    pub trials: u64,
+}
+
+impl RunItResult {
+    /// Returns the share of the pot this hand expects to win, between 0 and
+    /// 1, or zero when no trials have been run.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn equity(&self) -> f64 {
+        if self.trials == 0 {
+            0.
+        } else {
+            self.pot_share / self.trials as f64
+        }
+    }
+
+    /// Returns the variance of the share of the pot won in a single trial,
+    /// which is how much a result can be expected to swing.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn variance(&self) -> f64 {
+        if self.trials == 0 {
+            0.
+        } else {
+            let equity = self.equity();
+            equity.mul_add(-equity, self.pot_share_squared / self.trials as f64)
+        }
+    }
+}
+
+/// Estimates equity when the rest of the board is run `runs` times.
+///
+/// Each trial deals `runs` complete boards from the same deck, so no card
+/// appears on two of them, and every board awards `1 / runs` of the pot to
+/// its winner, split evenly between hands which tie. Running it more than
+/// once leaves each hand's equity unchanged, but lowers its variance.
+///
+/// # Errors
+///
+/// Will return an error if `runs` is zero, if the board has more than five
+/// cards, if any card is used more than once, or if the deck can't deal every
+/// run.
+#[allow(clippy::cast_precision_loss)]
+pub fn compute_equity_run_it_n(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    runs: usize,
+    trials: u64,
+    seed: Option<u64>,
+) -> Result<Vec<RunItResult>, PokerError> {
+    if runs == 0 {
+        return Err(PokerError::InvalidRequest(
+            "the board must be run at least once".into(),
+        ));
+    }
+    let mut deck = remaining_deck(hands, board, dead)?;
+    let per_run = 5 - board.len();
+    let needed = per_run * runs;
+    if needed > deck.len() {
+        return Err(PokerError::NotEnoughCards {
+            needed,
+            remaining: deck.len(),
+        });
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(rand::random));
+    let mut full_board = starting_board(board);
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); hands.len()];
+    let mut board_results = vec![EquityResult::default(); hands.len()];
+    let mut shares = vec![0.; hands.len()];
+    let mut results = vec![RunItResult::default(); hands.len()];
+
+    for _ in 0..trials {
+        shares.fill(0.);
+        let runouts = deal(&mut rng, &mut deck, needed);
+        for run in 0..runs {
+            full_board[board.len()..].copy_from_slice(&runouts[run * per_run..][..per_run]);
+            board_results.fill(EquityResult::default());
+            showdown(hands, full_board, &mut evaluations, &mut board_results, 1);
+
+            let winners = board_results
+                .iter()
+                .filter(|result| result.loss_count == 0)
+                .count();
+            for (share, result) in shares.iter_mut().zip(&board_results) {
+                if result.loss_count == 0 {
+                    *share += 1. / (runs * winners) as f64;
+                }
+            }
+        }
+
+        for (result, share) in results.iter_mut().zip(&shares) {
+            result.pot_share += share;
+            result.pot_share_squared += share * share;
+            result.trials += 1;
+        }
+    }
+
+    Ok(results)
+}
+
+/// Estimates equity by dealing random runouts.
+///
+/// Sampling is fully determined by the seed, and the samples can be taken in
+/// as many batches as the caller likes: running 500 samples twice produces
+/// exactly the same results as running 1000 samples once.
+pub struct MonteCarlo {
+    hands: Vec<[Card; 2]>,
+    board: [Card; 5],
+    known_cards: usize,
+    deck: Vec<Card>,
+    rng: StdRng,
+    seed: u64,
+    samples: u64,
+    evaluations: Vec<HandEvaluation>,
+    results: Vec<EquityResult>,
+}
+
+impl MonteCarlo {
+    /// Prepares a simulation for the given scenario. When no seed is given, a
+    /// random one is chosen and can be recovered with [`MonteCarlo::seed`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, or if any
+    /// card is used more than once.
+    pub fn new(
+        hands: &[[Card; 2]],
+        board: &[Card],
+        dead: &[Card],
+        seed: Option<u64>,
+    ) -> Result<Self, PokerError> {
+        let deck = remaining_deck(hands, board, dead)?;
+        let seed = seed.unwrap_or_else(rand::random);
+
+        Ok(Self {
+            hands: hands.to_vec(),
+            board: starting_board(board),
+            known_cards: board.len(),
+            deck,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            samples: 0,
+            evaluations: vec![HandEvaluation::new_high_card(0); hands.len()],
+            results: vec![EquityResult::default(); hands.len()],
+        })
+    }
+
+    /// Deals and scores `samples` more random runouts.
+    pub fn run(&mut self, samples: u64) {
+        let needed = 5 - self.known_cards;
+        if needed > self.deck.len() {
+            return;
+        }
+
+        for _ in 0..samples {
+            let runout = deal(&mut self.rng, &mut self.deck, needed);
+            self.board[self.known_cards..].copy_from_slice(runout);
+            showdown(
+                &self.hands,
+                self.board,
+                &mut self.evaluations,
+                &mut self.results,
+                1,
+            );
+            self.samples += 1;
+        }
+    }
+
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[must_use]
+    pub const fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Returns the results so far, in the same order as the hands.
+    #[must_use]
+    pub fn results(&self) -> &[EquityResult] {
+        &self.results
+    }
+}
+
+/// Statistics gathered by [`simulate_showdowns`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulationStats {
+    pub showdowns: u64,
+    /// The results of each seat, in seat order.
+    pub seats: Vec<EquityResult>,
+    /// How many showdowns were won by each kind of hand, indexed by the
+    /// [`HandKind`] as a number. A split pot counts once.
+    pub winning_kinds: [u64; 9],
+    /// How many showdowns were tied between two or more seats.
+    pub split_pots: u64,
+}
+
+impl SimulationStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn frequency(&self, count: u64) -> f64 {
+        if self.showdowns == 0 {
+            0.
+        } else {
+            count as f64 / self.showdowns as f64
+        }
+    }
+
+    /// Returns the share of showdowns which a seat won outright.
+    #[must_use]
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        self.seats.get(seat).map_or(0., EquityResult::win_fraction)
+    }
+
+    /// Returns the share of showdowns which were won with a kind of hand.
+    #[must_use]
+    pub fn winning_kind_frequency(&self, kind: HandKind) -> f64 {
+        self.frequency(self.winning_kinds[kind as usize])
+    }
+
+    /// Returns the share of showdowns which ended in a split pot.
+    #[must_use]
+    pub fn split_pot_frequency(&self) -> f64 {
+        self.frequency(self.split_pots)
+    }
+}
+
+/// Deals `showdowns` random games between `players` seats, with every seat
+/// going to showdown, and gathers statistics about who won and with what.
+///
+/// Every seat is dealt the same way, so in the long run each should win
+/// equally often. The games are fully determined by the seed.
+///
+/// # Errors
+///
+/// Will return an error if there aren't enough cards to deal every seat and
+/// the board.
+pub fn simulate_showdowns(
+    players: usize,
+    showdowns: u64,
+    seed: Option<u64>,
+) -> Result<SimulationStats, PokerError> {
+    let mut deck = Card::ALL.to_vec();
+    let needed = players * 2 + 5;
+    if needed > deck.len() {
+        return Err(PokerError::NotEnoughCards {
+            needed,
+            remaining: deck.len(),
+        });
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(rand::random));
+    let mut hands = vec![[Card::ALL[0]; 2]; players];
+    let mut evaluations = vec![HandEvaluation::new_high_card(0); players];
+    let mut stats = SimulationStats {
+        seats: vec![EquityResult::default(); players],
+        ..SimulationStats::default()
+    };
+
+    for _ in 0..showdowns {
+        let (hole_cards, board) = deal(&mut rng, &mut deck, needed).split_at(players * 2);
+        for (hand, cards) in hands.iter_mut().zip(hole_cards.chunks(2)) {
+            *hand = [cards[0], cards[1]];
+        }
+        let board = [board[0], board[1], board[2], board[3], board[4]];
+        showdown(&hands, board, &mut evaluations, &mut stats.seats, 1);
+
+        stats.showdowns += 1;
+        if let Some(best) = evaluations.iter().max() {
+            stats.winning_kinds[best.kind() as usize] += 1;
+            if evaluations
+                .iter()
+                .filter(|&evaluation| evaluation == best)
+                .count()
+                > 1
+            {
+                stats.split_pots += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Returns how often one player's hole cards finish as each kind of hand,
+/// indexed by the [`HandKind`] as a number, over every runout of the board or
+/// over random runouts, as `method` asks.
+///
+/// Only the player's own hand is looked at, so this says how often they make
+/// a flush, not how often the flush wins. The frequencies add up to 1, unless
+/// no runouts are sampled, when they are all zero.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards or if any card
+/// is used more than once.
+#[allow(clippy::cast_precision_loss)]
+pub fn hand_kind_frequencies(
+    hole: impl Into<[Card; 2]>,
+    board: &[Card],
+    dead: &[Card],
+    method: EquityMethod,
+) -> Result<[f64; 9], PokerError> {
+    let hole = hole.into();
+    let mut deck = remaining_deck(&[hole], board, dead)?;
+    let missing = 5 - board.len();
+    let mut state = EvalState::new(&[&hole[..], board].concat());
+    let mut counts = [0_u64; 9];
+    let mut count_runout = |state: &mut EvalState, runout: &[Card]| {
+        for &card in runout {
+            state.add_card(card);
+        }
+        counts[state.counts.best_high().kind() as usize] += 1;
+        for &card in runout {
+            state.remove_card(card);
+        }
+    };
+
+    match method {
+        EquityMethod::Exhaustive => {
+            for runout in deck.iter().copied().combinations(missing) {
+                count_runout(&mut state, &runout);
+            }
+        }
+        EquityMethod::MonteCarlo { samples, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(rand::random));
+            for _ in 0..samples {
+                count_runout(&mut state, deal(&mut rng, &mut deck, missing));
+            }
+        }
+    }
+
+    let total: u64 = counts.iter().sum();
+    Ok(counts.map(|count| {
+        if total == 0 {
+            0.
+        } else {
+            count as f64 / total as f64
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+    use std::collections::HashSet;
+
+    fn hand(s: &str) -> [Card; 2] {
+        let cards = parse_cards(s).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn test_hand_kind_frequencies() {
+        // Four hearts on the turn make a flush on 9 of the 46 rivers.
+        let board = parse_cards("2h7hJhKc").unwrap();
+        let exact =
+            hand_kind_frequencies(hand("AhQc"), &board, &[], EquityMethod::Exhaustive).unwrap();
+        assert!((exact[HandKind::Flush as usize] - 9. / 46.).abs() < 1e-12);
+        assert!((exact[HandKind::Pair as usize] - 16. / 46.).abs() < 1e-12);
+        assert!((exact.iter().sum::<f64>() - 1.).abs() < 1e-12);
+
+        // On a complete board the hand is already made.
+        let river = parse_cards("2h7hJhKc9h").unwrap();
+        let made = hand_kind_frequencies(hand("AhQc"), &river, &[], EquityMethod::Exhaustive);
+        assert_eq!(made.unwrap()[HandKind::Flush as usize], 1.);
+
+        let method = EquityMethod::MonteCarlo {
+            samples: 20_000,
+            seed: Some(1),
+        };
+        let sampled = hand_kind_frequencies(hand("AhQc"), &board, &[], method).unwrap();
+        assert!((sampled[HandKind::Flush as usize] - 9. / 46.).abs() < 0.02);
+
+        assert_eq!(
+            hand_kind_frequencies(hand("AhQc"), &board, &board[..1], EquityMethod::Exhaustive),
+            Err(PokerError::DuplicateCard(board[0]))
+        );
+    }
+
+    #[test]
+    fn test_complete_board() {
+        let board = parse_cards("Ah Kd 7c 7s 2h").unwrap();
+        let results = compute_equity(&[hand("AsQc"), hand("KhKc")], &board, &[]).unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                EquityResult {
+                    win_count: 0,
+                    loss_count: 1,
+                    tie_count: 0,
+                    count: 1,
+                },
+                EquityResult {
+                    win_count: 1,
+                    loss_count: 0,
+                    tie_count: 0,
+                    count: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_equity_n_matches_compute_result() {
+        let (hero, villain) = (hand("AsKs"), hand("QdQc"));
+        let [hero_result, villain_result] = compute_equity_n([hero, villain], &[], &[]).unwrap();
+        let legacy = crate::compute_result(hero, villain);
+        assert_eq!(hero_result, legacy);
+        assert_eq!(villain_result.win_count, legacy.loss_count);
+    }
+
+    /// Scores every river one at a time, without any of the shared machinery.
+    fn reference_equity(hands: &[[Card; 2]], board: &[Card]) -> Vec<EquityResult> {
+        let mut results = vec![EquityResult::default(); hands.len()];
+        for river in Card::ALL {
+            if board.contains(&river) || hands.iter().any(|hand| hand.contains(&river)) {
+                continue;
+            }
+            let evaluations: Vec<HandEvaluation> = hands
+                .iter()
+                .map(|hand| {
+                    evaluate_hand([
+                        board[0], board[1], board[2], board[3], river, hand[0], hand[1],
+                    ])
+                })
+                .collect();
+            let best = evaluations.iter().max().unwrap();
+            let winners = evaluations.iter().filter(|&e| e == best).count();
+            for (evaluation, result) in evaluations.iter().zip(&mut results) {
+                result.count += 1;
+                match (evaluation == best, winners) {
+                    (false, _) => result.loss_count += 1,
+                    (true, 1) => result.win_count += 1,
+                    (true, _) => result.tie_count += 1,
+                }
+            }
+        }
+        results
+    }
+
+    fn check_multiway<const N: usize>() {
+        let board = parse_cards("Ah 7d 7c 2s").unwrap();
+        let hands = parse_cards("KsKh QsJs Tc9c 8h8d 6d5d AdQc 3h4h Jd2d Kc2c").unwrap();
+        let hands: [[Card; 2]; N] = std::array::from_fn(|i| [hands[2 * i], hands[2 * i + 1]]);
+
+        let results = compute_equity_n(hands, &board, &[]).unwrap();
+        assert_eq!(results.to_vec(), reference_equity(&hands, &board));
+        assert_eq!(
+            results.to_vec(),
+            compute_equity(&hands, &board, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compute_equity_n_multiway() {
+        check_multiway::<3>();
+        check_multiway::<4>();
+        check_multiway::<5>();
+        check_multiway::<6>();
+        check_multiway::<7>();
+        check_multiway::<8>();
+        check_multiway::<9>();
+    }
+
+    #[test]
+    fn test_enumerate_runouts() {
+        let flop = parse_cards("Qh 7h 2c").unwrap();
+        let flop = [flop[0], flop[1], flop[2]];
+        let dead = parse_cards("AhKh 9s9d").unwrap();
+
+        let runouts: Vec<[Card; 5]> = enumerate_runouts(flop, &dead).collect();
+        assert_eq!(runouts.len() as u64, binomial(52 - 3 - 4, 2));
+        assert!(runouts.iter().all(|runout| runout[..3] == flop
+            && runout[3] != runout[4]
+            && !runout[3..]
+                .iter()
+                .any(|card| dead.contains(card) || flop.contains(card))));
+
+        let turns_and_rivers: HashSet<[String; 2]> = runouts
+            .iter()
+            .map(|runout| {
+                let mut pair = [runout[3].to_string(), runout[4].to_string()];
+                pair.sort();
+                pair
+            })
+            .collect();
+        assert_eq!(turns_and_rivers.len(), runouts.len());
+    }
+
+    #[test]
+    fn test_compute_equity_verbose() {
+        let hands = parse_cards("AhKh QsQd").unwrap();
+        let (hero, villain) = ([hands[0], hands[1]], [hands[2], hands[3]]);
+        let board = parse_cards("Qh7h2hJc3s").unwrap();
+
+        let runouts = compute_equity_verbose(hero, villain, &board, &[]).unwrap();
+        assert_eq!(runouts.len(), 1);
+        let (full_board, hero_hand, villain_hand, ordering) = &runouts[0];
+        assert_eq!(full_board, &board);
+        assert_eq!(hero_hand.kind(), HandKind::Flush);
+        assert_eq!(villain_hand.kind(), HandKind::ThreeOfAKind);
+        assert_eq!(*ordering, Ordering::Greater);
+
+        let runouts = compute_equity_verbose(hero, villain, &board[..4], &[]).unwrap();
+        let results = compute_equity(&[hero, villain], &board[..4], &[]).unwrap();
+        let wins = runouts
+            .iter()
+            .filter(|(_, _, _, ordering)| ordering.is_gt())
+            .count();
+        assert_eq!(runouts.len(), 44);
+        assert_eq!(wins as u64, results[0].win_count);
+    }
+
+    #[test]
+    fn test_river_enumeration() {
+        // The nut flush draw wins with the eight hearts which don't pair the
+        // board, plus the three other tens which make broadway.
+        let board = parse_cards("2h 7h Jh Kc").unwrap();
+        let results = compute_equity(&[hand("AhQc"), hand("KsKd")], &board, &[]).unwrap();
+        assert_eq!(results[0].count, 44);
+        assert_eq!(results[0].win_count, 11);
+        assert_eq!(results[0].tie_count, 0);
+        assert_eq!(results[1].win_count, 33);
+
+        // Against a pair of jacks, every heart, ace, queen and ten wins.
+        let results = compute_equity(&[hand("AhQc"), hand("JsTd")], &board, &[]).unwrap();
+        assert_eq!(results[0].win_count, 16);
+        assert_eq!(results[1].win_count, 28);
+    }
+
+    #[test]
+    fn test_rivers_that_win() {
+        // An open-ended straight draw against aces only gets there with a
+        // ten or a five.
+        let board = parse_cards("7s 6d 2c Kh").unwrap();
+        let rivers = rivers_that_win(hand("9c8d"), hand("AsAh"), &board).unwrap();
+        assert_eq!(rivers.len(), 8);
+        assert!(rivers
+            .iter()
+            .all(|card| matches!(card.number(), Number::Ten | Number::Five)));
+
+        // Every river ties when both hands play the board.
+        let board = parse_cards("As Ks Qs Js").unwrap();
+        let rivers = rivers_that_win(hand("2h3h"), hand("2d3d"), &board).unwrap();
+        assert_eq!(rivers.len(), 44);
+
+        assert_eq!(
+            rivers_that_win(hand("9c8d"), hand("AsAh"), &board[..3]),
+            Err(PokerError::InvalidBoardSize(3))
+        );
+    }
+
+    #[test]
+    fn test_hole_cards_are_accepted() {
+        use crate::{Hand, HoleCards};
+
+        let board = parse_cards("2h 7h Jh Kc").unwrap();
+        let hero: HoleCards = "AhQc".parse().unwrap();
+        let villain: Hand = "KsKd".parse().unwrap();
+        assert_eq!(
+            compute_equity(&[hero, HoleCards::from(villain)], &board, &[]).unwrap(),
+            compute_equity(&[hand("AhQc"), hand("KsKd")], &board, &[]).unwrap()
+        );
+        assert_eq!(rivers_that_win(hero, villain, &board).unwrap().len(), 11);
+        assert!(!is_hand_locked(hero, villain, &board));
+        assert_eq!(
+            ahead_behind_breakdown(hero, &board, &[]).unwrap(),
+            ahead_behind_breakdown(hand("AhQc"), &board, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_multiway_split_pots() {
+        // A five or a ten makes a straight on the board for everyone, an ace
+        // or a king splits the pot between the two big slicks, and anything
+        // else wins for the queens.
+        let hands = [hand("AhKh"), hand("AsKs"), hand("QdQc")];
+        let board = parse_cards("9c 8d 7h 6c").unwrap();
+        let multiway = compute_equity_multiway(&hands, &board, &[]).unwrap();
+
+        assert_eq!(
+            multiway.results,
+            compute_equity(&hands, &board, &[]).unwrap()
+        );
+        assert_eq!(
+            multiway.split_pots,
+            BTreeMap::from([(0b011, 4), (0b111, 8)])
+        );
+        assert_eq!(multiway.ties_between(0, 1), 12);
+        assert_eq!(multiway.ties_between(1, 2), 8);
+        for (index, result) in multiway.results.iter().enumerate() {
+            let tied: u64 = multiway
+                .split_pots
+                .iter()
+                .filter(|&(&tied, _)| tied & 1 << index != 0)
+                .map(|(_, &count)| count)
+                .sum();
+            assert_eq!(tied, result.tie_count);
+        }
+
+        let shares: Vec<f64> = (0..3).map(|hand| multiway.pot_share(hand)).collect();
+        assert!((shares[0] - (8. / 3. + 2.) / 42.).abs() < 1e-9);
+        assert!((shares[2] - (30. + 8. / 3.) / 42.).abs() < 1e-9);
+        assert!((shares.iter().sum::<f64>() - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_headsup_matches_compute_equity() {
+        // A pair against overcards, a domination, suited connectors against
+        // a pair, and a mirrored hand which chops most of the time. Removing
+        // most of the small cards keeps the comparison quick.
+        let dead = parse_cards("2h3h4h5h8h9hTh 3d4d5d6d8d9dTdJd 3c4c5c6c7c8c 3s4s5s6s").unwrap();
+        for (hero, villain) in [
+            ("AsKs", "QdQc"),
+            ("AhKd", "AcQc"),
+            ("7h6h", "2c2d"),
+            ("AhKh", "AdKd"),
+        ] {
+            let (hero, villain) = (hand(hero), hand(villain));
+            assert_eq!(
+                headsup_with_dead(hero, villain, &dead).unwrap(),
+                compute_equity_n([hero, villain], &[], &dead).unwrap()
+            );
+        }
+
+        assert_eq!(
+            compute_equity_headsup(hand("AsKs"), hand("AsQc")),
+            Err(PokerError::DuplicateCard(hand("AsKs")[0]))
+        );
+    }
+
+    #[test]
+    fn test_split_breakdown() {
+        let hands = [hand("AhKh"), hand("AsKs"), hand("QdQc")];
+        let board = parse_cards("9c 8d 7h 6c").unwrap();
+        assert_eq!(split_breakdown(&hands, &board, &[]).unwrap(), [30, 4, 8]);
+
+        // Nobody can beat the straight on the board, so everyone chops.
+        let hands = [hand("2h2d"), hand("3c3s"), hand("AhKd"), hand("QsJd")];
+        let board = parse_cards("9c 8d 7h 6c 5s").unwrap();
+        assert_eq!(split_breakdown(&hands, &board, &[]).unwrap(), [0, 0, 0, 1]);
+
+        // A five or a ten puts a straight on the board which nobody beats.
+        let hands = [hand("2h2d"), hand("3c3s"), hand("AhKd")];
+        let board = parse_cards("9c 8d 7h 6c").unwrap();
+        assert_eq!(split_breakdown(&hands, &board, &[]).unwrap(), [34, 0, 8]);
+        assert_eq!(split_breakdown(&[], &board, &[]).unwrap(), [0; 0]);
+    }
+
+    #[test]
+    fn test_compute_equity_active() {
+        let hands = [hand("AsAd"), hand("KsKd"), hand("QhQc")];
+        let board = parse_cards("Jh 7c 2d").unwrap();
+        let everyone = compute_equity(&hands, &board, &[]).unwrap();
+        let results = compute_equity_active(&hands, &[true, false, true], &board, &[]).unwrap();
+
+        // The kings' share goes to the others, since they no longer need to
+        // beat the kings.
+        assert_eq!(results[1].loss_count, results[1].count);
+        assert_eq!(results[1].equity(), 0.);
+        assert!(results[0].equity() > everyone[0].equity());
+        assert!(results[2].equity() > everyone[2].equity());
+        assert!((results[0].equity() + results[2].equity() - 1.).abs() < 1e-12);
+
+        // The folded kings are still out of the deck, so only 43 cards are
+        // left to deal, and the queens can't hit one of them.
+        assert_eq!(results[0].count, 903);
+        assert_eq!(
+            results[..1],
+            compute_equity(&[hands[0], hands[2]], &board, &hands[1]).unwrap()[..1]
+        );
+        let all_active = compute_equity_active(&hands, &[true; 3], &board, &[]).unwrap();
+        assert_eq!(all_active, everyone);
+
+        assert!(matches!(
+            compute_equity_active(&hands, &[true, true], &board, &[]),
+            Err(PokerError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_side_pot_equity() {
+        let hands = [hand("AhAd"), hand("KsKd"), hand("QdQc")];
+        let board = parse_cards("9c 8d 7h 2s").unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        // The aces are all-in for 100, so they can only win the 300 chip main
+        // pot, while the kings and queens also play for an 800 chip side pot.
+        let returns = compute_side_pot_equity(&hands, &[100, 500, 500], &board, &[]).unwrap();
+        let main = compute_equity_multiway(&hands, &board, &[]).unwrap();
+        let side = compute_equity_multiway(&hands[1..], &board, &hands[0]).unwrap();
+        assert!(close(returns[0], 300. * main.pot_share(0)));
+        assert!(returns[0] <= 300.);
+        assert!(close(
+            returns[1],
+            300. * main.pot_share(1) + 800. * side.pot_share(0)
+        ));
+        assert!(close(
+            returns[2],
+            300. * main.pot_share(2) + 800. * side.pot_share(1)
+        ));
+        assert!(close(returns.iter().sum(), 1100.));
+
+        // The 200 chips nobody matched go straight back to the kings.
+        let unmatched = compute_side_pot_equity(&hands, &[100, 500, 300], &board, &[]).unwrap();
+        assert!(close(
+            unmatched[1],
+            300. * main.pot_share(1) + 400. * side.pot_share(0) + 200.
+        ));
+
+        // With equal stacks, everything is one pot.
+        let returns = compute_side_pot_equity(&hands, &[200; 3], &board, &[]).unwrap();
+        for (index, chips) in returns.into_iter().enumerate() {
+            assert!(close(chips, 600. * main.pot_share(index)));
+        }
+
+        assert!(matches!(
+            compute_side_pot_equity(&hands, &[100, 500], &board, &[]),
+            Err(PokerError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_hand_kind_breakdown() {
+        let hands = [hand("AhQc"), hand("KsKd")];
+        let board = parse_cards("2h 7h Jh Kc").unwrap();
+        let options = EquityOptions {
+            track_hand_kinds: true,
+        };
+        let detailed = compute_equity_with_options(&hands, &board, &[], options).unwrap();
+        let exact = compute_equity(&hands, &board, &[]).unwrap();
+
+        // The flush draw only wins with a flush or broadway, and the kings
+        // always have trips or better.
+        let [draw, kings] = [0, 1].map(|index| {
+            assert_eq!(detailed[index].result, exact[index]);
+            detailed[index].hand_kinds.unwrap()
+        });
+        assert_eq!(draw.won[HandKind::Flush as usize], 8);
+        assert_eq!(draw.won[HandKind::Straight as usize], 3);
+        assert_eq!(draw.won.iter().sum::<u64>(), exact[0].win_count);
+        assert!((draw.won_with(HandKind::Flush) - 8. / 11.).abs() < 1e-9);
+        assert_eq!(kings.won[..HandKind::ThreeOfAKind as usize], [0; 3]);
+        assert_eq!(kings.lost.iter().sum::<u64>(), 11);
+
+        let fast = compute_equity_with_options(&hands, &board, &[], EquityOptions::default());
+        assert!(fast
+            .unwrap()
+            .iter()
+            .all(|result| result.hand_kinds.is_none()));
+    }
+
+    #[test]
+    fn test_is_freeroll() {
+        // Both players have broadway, but only the hero can improve to a
+        // flush.
+        let board = parse_cards("Qh Jh Td").unwrap();
+        assert!(is_freeroll(hand("AhKh"), hand("AsKc"), &board));
+        assert!(!is_freeroll(hand("AsKc"), hand("AhKh"), &board));
+
+        // Splitting every runout isn't a freeroll.
+        let board = parse_cards("As Ks Qs Js Ts").unwrap();
+        assert!(!is_freeroll(hand("2h3h"), hand("2d3d"), &board));
+
+        // Neither is a hand which can still lose.
+        let board = parse_cards("7s 6d 2c").unwrap();
+        assert!(!is_freeroll(hand("AsAh"), hand("9c8d"), &board));
+        assert!(!is_freeroll(hand("AsAh"), hand("AsKd"), &board));
+    }
+
+    #[test]
+    fn test_drawing_dead() {
+        // Quad queens on the turn leave the full house and the royal flush
+        // draw, which needs two more hearts, without any outs.
+        let hands = [hand("QsQd"), hand("7s7d"), hand("AhKh")];
+        let board = parse_cards("Qc 7h 2c Qh").unwrap();
+        assert!(!drawing_dead(0, &hands, &board, &[]));
+        assert!(drawing_dead(1, &hands, &board, &[]));
+        assert!(drawing_dead(2, &hands, &board, &[]));
+
+        // Splitting a board which plays is not drawing dead.
+        let board = parse_cards("Th Jh Qh Kh Ah").unwrap();
+        assert!(!drawing_dead(1, &[hand("2c3c"), hand("4d5d")], &board, &[]));
+
+        // Preflop, nobody is drawing dead.
+        let hands = [hand("AsAd"), hand("7c2h")];
+        assert!(!drawing_dead(1, &hands, &[], &[]));
+        assert!(!drawing_dead(2, &hands, &[], &[]));
+    }
+
+    #[test]
+    fn test_is_hand_locked() {
+        // Only the hero's straight can be made on this board, and a seven or
+        // a ten on the river still leaves the hero with the best straight.
+        let board = parse_cards("9c 8d 6h 2s").unwrap();
+        assert!(is_hand_locked(hand("Ts7s"), hand("AcKd"), &board));
+        // The villain is locked out just the same.
+        assert!(is_hand_locked(hand("AcKd"), hand("Ts7s"), &board));
+        // On the flop, a ten and a seven put the hero's straight on the
+        // board, which the villain then splits.
+        let flop = parse_cards("9c 8d 6h").unwrap();
+        assert!(!is_hand_locked(hand("Ts7s"), hand("AcKd"), &flop));
+
+        // A set is well ahead of a flush draw, but any heart flips it.
+        let board = parse_cards("Qc 7h 2h 3d").unwrap();
+        assert!(!is_hand_locked(hand("QsQd"), hand("AhKh"), &board));
+
+        // Once the board is complete, the winner can't change.
+        let river = parse_cards("Qc 7h 2h 3d 4s").unwrap();
+        assert!(is_hand_locked(hand("QsQd"), hand("AhKh"), &river));
+        assert!(!is_hand_locked(hand("QsQd"), hand("AhKh"), &[]));
+        assert!(!is_hand_locked(hand("QsQd"), hand("QsKh"), &board));
+    }
+
+    #[test]
+    fn test_ahead_behind_breakdown() {
+        // Only flushes and straights beat top set, which few holdings make.
+        let board = parse_cards("Ad 9d 8c").unwrap();
+        let (behind, tied, ahead) = ahead_behind_breakdown(hand("AhAs"), &board, &[]).unwrap();
+        assert!(behind < 0.02, "{behind}");
+        assert_eq!(tied, 0.);
+        assert!((behind + tied + ahead - 1.).abs() < 1e-12);
+
+        // The nuts are never behind, and ties only with the other nut hands.
+        let board = parse_cards("Qd Jd Td 4c 4s").unwrap();
+        assert_eq!(
+            ahead_behind_breakdown(hand("AdKd"), &board, &[]),
+            Ok((0., 0., 1.))
+        );
+        // A weak ace is behind most holdings, and ties the other weak aces.
+        let (behind, tied, _) = ahead_behind_breakdown(hand("As2c"), &board, &[]).unwrap();
+        assert!(behind > 0.5 && tied > 0.05);
+
+        // Dead cards can't be held, so only AdKh and AdKc split the straight
+        // out of the 903 holdings left.
+        let (_, tied, _) =
+            ahead_behind_breakdown(hand("AsKs"), &board, &parse_cards("Ah Ac").unwrap()).unwrap();
+        assert_eq!(tied, 2. / 903.);
+
+        assert!(matches!(
+            ahead_behind_breakdown(hand("AsKs"), &board[..2], &[]),
+            Err(PokerError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_complete_board_is_one_runout() {
+        let hands = [hand("AsQc"), hand("KhKc")];
+        let board = parse_cards("Ah Kd 7c 7s 2h").unwrap();
+        let exact = compute_equity(&hands, &board, &[]).unwrap();
+        assert_eq!(runout_count(&hands, &board, &[]), Ok(1));
+        assert_eq!(compute_equity_memoized(&hands, &board, &[]).unwrap(), exact);
+        assert_eq!(
+            compute_equity_multiway(&hands, &board, &[])
+                .unwrap()
+                .results,
+            exact
+        );
+
+        // Every sample deals the same board.
+        let mut simulation = MonteCarlo::new(&hands, &board, &[], Some(850)).unwrap();
+        simulation.run(10);
+        assert_eq!(simulation.results()[1].win_count, 10);
+    }
+
+    #[test]
+    fn test_lone_hand_wins_every_runout() {
+        let board = parse_cards("Ah Kd 7c 7s").unwrap();
+        assert_eq!(
+            compute_equity(&[hand("2c3d")], &board, &[]).unwrap(),
+            [EquityResult {
+                win_count: 46,
+                loss_count: 0,
+                tie_count: 0,
+                count: 46,
+            }]
+        );
+        assert_eq!(
+            compute_equity(&[] as &[[Card; 2]], &board, &[]).unwrap(),
+            []
+        );
+    }
+
+    #[test]
+    fn test_every_card_used() {
+        // 23 hands, a flop and two dead cards use up the whole deck.
+        let hands: Vec<[Card; 2]> = Card::ALL[..46]
+            .chunks(2)
+            .map(|cards| [cards[0], cards[1]])
+            .collect();
+        let (board, dead) = (&Card::ALL[46..49], &Card::ALL[49..]);
+        let error = PokerError::NotEnoughCards {
+            needed: 2,
+            remaining: 0,
+        };
+        assert_eq!(compute_equity(&hands, board, dead), Err(error.clone()));
+        assert_eq!(runout_count(&hands, board, dead), Err(error.clone()));
+        assert_eq!(
+            MonteCarlo::new(&hands, board, dead, None).err(),
+            Some(error)
+        );
+
+        // A complete board needs nothing more from the deck.
+        let board = &Card::ALL[46..51];
+        assert_eq!(runout_count(&hands, board, &Card::ALL[51..]), Ok(1));
+    }
+
+    #[test]
+    fn test_duplicate_cards() {
+        let board = parse_cards("As 7d 2c").unwrap();
+        assert_eq!(
+            compute_equity(&[hand("AsKs"), hand("QdQc")], &board, &[]),
+            Err(PokerError::DuplicateCard(Card::new(
+                Suit::Spades,
+                Number::Ace
+            )))
+        );
+    }
+
+    #[test]
+    fn test_merged_rivers_match_turn_enumeration() {
+        let hands = [hand("AhQc"), hand("KsKd")];
+        let turn = parse_cards("2h 7h Jh Kc").unwrap();
+        let deck = remaining_deck(&hands, &turn, &[]).unwrap();
+
+        let mut totals = vec![];
+        for river in deck {
+            let mut board = turn.clone();
+            board.push(river);
+            merge_results(&mut totals, &compute_equity(&hands, &board, &[]).unwrap());
+        }
+        assert_eq!(totals, compute_equity(&hands, &turn, &[]).unwrap());
+
+        let mut result = totals[0];
+        result += totals[1];
+        assert_eq!(result.count, 88);
+        assert_eq!(result.win_count, 44);
+    }
+
+    #[test]
+    fn test_percentages() {
+        let empty = EquityResult::default();
+        assert_eq!(empty.win_pct(), 0.);
+        assert_eq!(empty.tie_pct(), 0.);
+        assert_eq!(empty.equity(), 0.);
+
+        let result = EquityResult {
+            win_count: 5,
+            loss_count: 3,
+            tie_count: 2,
+            count: 10,
+        };
+        assert!((result.win_pct() - 50.).abs() < 1e-9);
+        assert!((result.loss_pct() - 30.).abs() < 1e-9);
+        assert!((result.tie_pct() - 20.).abs() < 1e-9);
+        assert!((result.equity() - 0.6).abs() < 1e-9);
+        assert_eq!(result.to_string(), "50.0% / 30.0% / 20.0%");
+        assert_eq!(empty.to_string(), "0.0% / 0.0% / 0.0%");
+    }
+
+    #[test]
+    fn test_ties_count_as_half_a_win() {
+        // Tools disagree on how to count ties, so pin it down: a tie is worth
+        // half the pot, however many hands share it.
+        let result = EquityResult {
+            win_count: 1,
+            loss_count: 1,
+            tie_count: 2,
+            count: 4,
+        };
+        assert!((result.win_fraction() - 0.25).abs() < 1e-9);
+        assert!((result.loss_fraction() - 0.25).abs() < 1e-9);
+        assert!((result.tie_fraction() - 0.5).abs() < 1e-9);
+        assert!((result.equity() - 0.5).abs() < 1e-9);
+
+        let total: EquityResult = [result, result].into_iter().sum();
+        assert_eq!(total, result + result);
+        assert_eq!(total.count, 8);
+        assert!((total.equity() - result.equity()).abs() < 1e-9);
+        assert!((result.standard_error() - 0.25).abs() < 1e-9);
+        assert!((total.standard_error() - 0.25 / 2_f64.sqrt()).abs() < 1e-9);
+        assert!(EquityResult::default().standard_error() == 0.);
+    }
+
+    #[test]
+    fn test_confidence_interval_narrows() {
+        let widths: Vec<f64> = [10, 100, 1_000, 10_000, 100_000]
+            .into_iter()
+            .map(|count| {
+                let (lower, upper) = equity_confidence_interval(count * 3 / 10, count, 1.96);
+                assert!(lower < 0.3 && 0.3 < upper);
+                upper - lower
+            })
+            .collect();
+        assert!(widths.windows(2).all(|pair| pair[1] < pair[0]));
+        // The width shrinks with the square root of the count.
+        assert!((widths[4] - 2. * 1.96 * (0.21_f64 / 100_000.).sqrt()).abs() < 1e-4);
+
+        assert_eq!(equity_confidence_interval(0, 0, 1.96), (0., 1.));
+        let (lower, upper) = equity_confidence_interval(20, 20, 1.96);
+        assert!(lower > 0.8 && upper == 1.);
+        let (lower, upper) = equity_confidence_interval(0, 20, 1.96);
+        assert!(lower == 0. && upper < 0.2);
+
+        let simulation_result = EquityResult {
+            win_count: 300,
+            loss_count: 700,
+            tie_count: 0,
+            count: 1_000,
+        };
+        assert_eq!(
+            simulation_result.win_confidence_interval(1.96),
+            equity_confidence_interval(300, 1_000, 1.96)
+        );
+    }
+
+    #[test]
+    fn test_runout_count() {
+        assert_eq!(
+            runout_count(&[hand("AsKs"), hand("QdQc")], &[], &[]),
+            Ok(1_712_304)
+        );
+        assert_eq!(
+            runout_count(
+                &[hand("AsKs"), hand("QdQc")],
+                &parse_cards("2c3c4c").unwrap(),
+                &[]
+            ),
+            Ok(990)
+        );
+    }
+
+    #[test]
+    fn test_memoized_matches_naive_enumeration() {
+        let scenarios = [
+            (vec![hand("AsKs"), hand("QdQc")], "Js 7d 2c"),
+            (vec![hand("AhKd"), hand("7c7s")], "Ts 9s 8d"),
+            (vec![hand("AsAh"), hand("KsKh")], "2c 3d 9h"),
+            (vec![hand("AsKs"), hand("QdQc"), hand("JhTh")], "2c 7d 8s"),
+            (vec![hand("5c4c"), hand("AdAs")], "6c 7h Kd Qs"),
+            (vec![hand("AsKs"), hand("AhKh")], "Qd Jc 2d"),
+        ];
+
+        for (hands, board) in scenarios {
+            let board = parse_cards(board).unwrap();
+            assert_eq!(
+                compute_equity_memoized(&hands, &board, &[]),
+                compute_equity(&hands, &board, &[]),
+            );
+        }
+    }
+
+    #[test]
+    fn test_memoized_respects_dead_cards() {
+        let hands = [hand("AsKs"), hand("QdQc")];
+        let board = parse_cards("Js 7d 2c").unwrap();
+        let dead = parse_cards("Qh 3s").unwrap();
+        assert_eq!(
+            compute_equity_memoized(&hands, &board, &dead),
+            compute_equity(&hands, &board, &dead),
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_is_deterministic() {
+        let hands = [hand("AsKs"), hand("QdQc"), hand("7h6h")];
+
+        let mut all_at_once = MonteCarlo::new(&hands, &[], &[], Some(42)).unwrap();
+        all_at_once.run(1000);
+
+        let mut batched = MonteCarlo::new(&hands, &[], &[], Some(42)).unwrap();
+        batched.run(300);
+        batched.run(700);
+
+        assert_eq!(all_at_once.samples(), 1000);
+        assert_eq!(all_at_once.results(), batched.results());
+        assert_eq!(all_at_once.results()[0].count, 1000);
+    }
+
+    #[test]
+    fn test_run_it_twice_lowers_variance() {
+        let hands = [hand("AsAh"), hand("KdKc")];
+        let once = compute_equity_run_it_n(&hands, &[], &[], 1, 4000, Some(7)).unwrap();
+        let twice = compute_equity_run_it_n(&hands, &[], &[], 2, 4000, Some(7)).unwrap();
+
+        // Aces are about an 82% favourite, however many times it's run.
+        for results in [&once, &twice] {
+            assert_eq!(results[0].trials, 4000);
+            assert!((results[0].equity() - 0.82).abs() < 0.03);
+            assert!((results[0].equity() + results[1].equity() - 1.).abs() < 1e-9);
+        }
+        assert!((once[0].equity() - twice[0].equity()).abs() < 0.03);
+        assert!(twice[0].variance() < once[0].variance() * 0.7);
+
+        assert_eq!(
+            compute_equity_run_it_n(&hands, &[], &[], 0, 1, None),
+            Err(PokerError::InvalidRequest(
+                "the board must be run at least once".into()
+            ))
+        );
+        // Every run is dealt from the same deck.
+        assert_eq!(
+            compute_equity_run_it_n(&hands, &[], &[], 10, 1, None),
+            Err(PokerError::NotEnoughCards {
+                needed: 50,
+                remaining: 48
+            })
+        );
+    }
+
+    #[test]
+    fn test_showdown_statistics() {
+        let stats = simulate_showdowns(9, 20_000, Some(3)).unwrap();
+        assert_eq!(stats, simulate_showdowns(9, 20_000, Some(3)).unwrap());
+        assert_eq!(stats.winning_kinds.iter().sum::<u64>(), 20_000);
+
+        // The winning hands of nine-handed showdowns, as published.
+        let published = [
+            (HandKind::Pair, 0.093),
+            (HandKind::TwoPair, 0.249),
+            (HandKind::ThreeOfAKind, 0.167),
+            (HandKind::Straight, 0.200),
+            (HandKind::Flush, 0.133),
+            (HandKind::FullHouse, 0.143),
+            (HandKind::FourOfAKind, 0.013),
+            (HandKind::StraightFlush, 0.003),
+        ];
+        for (kind, frequency) in published {
+            assert!((stats.winning_kind_frequency(kind) - frequency).abs() < 0.02);
+        }
+        assert!(stats.winning_kind_frequency(HandKind::HighCard) < 0.005);
+
+        let outright = (1. - stats.split_pot_frequency()) / 9.;
+        for seat in 0..9 {
+            assert!((stats.win_rate(seat) - outright).abs() < 0.01);
+        }
+
+        assert!(simulate_showdowns(24, 1, None).is_err());
+    }
+}
+
+/// Randomized checks of properties which hold whatever the right answer is,
+/// over many scenarios with boards, dead cards and several hands. Each run
+/// uses the same seed, so a failure can always be reproduced.
+#[cfg(test)]
+mod invariant_tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+
+    const SCENARIOS: usize = 100;
+
+    #[derive(Debug)]
+    struct Scenario {
+        hands: Vec<[Card; 2]>,
+        board: Vec<Card>,
+        dead: Vec<Card>,
+        /// The cards which aren't used anywhere in the scenario.
+        unused: Vec<Card>,
+        /// The exhaustive results, which most invariants compare against.
+        results: Vec<EquityResult>,
+    }
+
+    impl Scenario {
+        /// Deals two to four hands, a flop, turn or river, and up to two
+        /// dead cards, which keeps every scenario quick to enumerate.
+        fn random(rng: &mut StdRng) -> Self {
+            let mut deck: Vec<Card> = Deck::new().into();
+            deck.shuffle(rng);
+            let hand_count = rng.gen_range(2..=4);
+            let board_len = rng.gen_range(3..=5);
+            let dead_len = rng.gen_range(0..=2);
+
+            let mut cards = deck.into_iter();
+            let hands: Vec<[Card; 2]> = (0..hand_count)
+                .map(|_| [cards.next().unwrap(), cards.next().unwrap()])
+                .collect();
+            let board: Vec<Card> = cards.by_ref().take(board_len).collect();
+            let dead: Vec<Card> = cards.by_ref().take(dead_len).collect();
+            let results = compute_equity(&hands, &board, &dead).unwrap();
+            Self {
+                hands,
+                board,
+                dead,
+                unused: cards.collect(),
+                results,
+            }
+        }
+    }
+
+    /// Checks one property of a scenario, returning whether it held.
+    type Invariant = fn(&Scenario, &mut StdRng) -> bool;
+
+    /// Every invariant which is checked, by name. Adding an invariant only
+    /// needs an entry here.
+    const INVARIANTS: [(&str, Invariant); 5] = [
+        ("outcomes add up to the count", outcomes_add_up),
+        ("reversing the hands reverses the results", reversing_hands),
+        ("a dead card never adds runouts", dead_card_removes_runouts),
+        ("a complete board has one runout", complete_board),
+        ("Monte Carlo converges", monte_carlo_converges),
+    ];
+
+    fn outcomes_add_up(scenario: &Scenario, _: &mut StdRng) -> bool {
+        scenario
+            .results
+            .iter()
+            .all(|result| result.win_count + result.loss_count + result.tie_count == result.count)
+    }
+
+    fn reversing_hands(scenario: &Scenario, _: &mut StdRng) -> bool {
+        let reversed: Vec<[Card; 2]> = scenario.hands.iter().rev().copied().collect();
+        let mut results = compute_equity(&reversed, &scenario.board, &scenario.dead).unwrap();
+        results.reverse();
+        results == scenario.results
+    }
+
+    fn dead_card_removes_runouts(scenario: &Scenario, rng: &mut StdRng) -> bool {
+        let dead = [&scenario.dead[..], &[*scenario.unused.choose(rng).unwrap()]].concat();
+        let results = compute_equity(&scenario.hands, &scenario.board, &dead).unwrap();
+        results[0].count <= scenario.results[0].count
+    }
+
+    fn complete_board(scenario: &Scenario, rng: &mut StdRng) -> bool {
+        let missing = 5 - scenario.board.len();
+        let runout = scenario.unused.choose_multiple(rng, missing);
+        let board: Vec<Card> = scenario.board.iter().chain(runout).copied().collect();
+        compute_equity(&scenario.hands, &board, &scenario.dead)
+            .unwrap()
+            .iter()
+            .all(|result| result.count == 1)
+    }
+
+    /// Only the turn is sampled, since it has few enough runouts that a
+    /// sample covers each of them many times over.
+    fn monte_carlo_converges(scenario: &Scenario, rng: &mut StdRng) -> bool {
+        if scenario.board.len() != 4 {
+            return true;
+        }
+        let mut simulation = MonteCarlo::new(
+            &scenario.hands,
+            &scenario.board,
+            &scenario.dead,
+            Some(rng.gen()),
+        )
+        .unwrap();
+        simulation.run(10_000);
+        simulation
+            .results()
+            .iter()
+            .zip(&scenario.results)
+            .all(|(sampled, exact)| {
+                (sampled.equity() - exact.equity()).abs() <= 5. * sampled.standard_error() + 1e-9
+            })
+    }
+
+    #[test]
+    fn test_invariants() {
+        let mut rng = StdRng::seed_from_u64(844);
+        for _ in 0..SCENARIOS {
+            let scenario = Scenario::random(&mut rng);
+            for (name, invariant) in INVARIANTS {
+                assert!(
+                    invariant(&scenario, &mut rng),
+                    "{name} failed for {scenario:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Card;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum PokerError {
+    /// A card was not written in the two character `<number><suit>`
+    /// notation, such as `"As"` or `"Td"`.
+    #[error("invalid card: {0:?}")]
+    InvalidCard(String),
+    /// A suit was given a value outside the interval [0, 3].
+    #[error("invalid suit: {0} (expected a value from 0 to 3)")]
+    InvalidSuit(u8),
+    /// A card number was given a value outside the interval [2, 14].
+    #[error("invalid card number: {0} (expected a value from 2 to 14)")]
+    InvalidNumber(u8),
+    /// A card was given an index outside the interval [0, 51].
+    #[error("invalid card index: {0} (expected a value from 0 to 51)")]
+    InvalidCardIndex(u8),
+    /// The same card was used more than once in a scenario.
+    #[error("duplicate card: {0}")]
+    DuplicateCard(Card),
+    /// The board had a number of cards it can't have, such as more than five,
+    /// or one the computation can't use, such as a preflop board for a
+    /// computation which needs a flop.
+    #[error("invalid board size: {0} cards")]
+    InvalidBoardSize(usize),
+    /// A hand was built from the wrong number of cards.
+    #[error("expected {expected} cards, but found {found}")]
+    InvalidHandSize { expected: usize, found: usize },
+    /// A token of range notation could not be parsed. The position is the
+    /// character offset the token starts at.
+    #[error("invalid range token {token:?} at position {position}")]
+    InvalidRange { token: String, position: usize },
+    /// The deck ran out before every card which was asked for could be dealt.
+    #[error("{needed} cards are needed, but only {remaining} remain")]
+    NotEnoughCards { needed: usize, remaining: usize },
+    /// The computation was cancelled before it finished.
+    #[error("the computation was cancelled")]
+    Cancelled,
+    /// A hand history could not be read. Lines are numbered from 1.
+    #[error("invalid hand history on line {line}: {message}")]
+    InvalidHistory { line: usize, message: String },
+    /// A scenario didn't make sense as a whole, such as one with a single
+    /// player.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+}
@@ -0,0 +1,168 @@
+//! Rough equities from counting outs, for when an answer is needed straight
+//! away and the exact computation can follow later.
+
+use crate::{remaining_deck, Card, HandCounts, HandKind, PokerError};
+
+/// Returns the probability of hitting at least one of `outs` cards over the
+/// next `streets_remaining` cards, which is 2 on the flop and 1 on the turn.
+///
+/// This is the exact hypergeometric probability, rather than the rule of
+/// four and two, so two streets with nine outs gives 34.97% rather than 36%.
+#[must_use]
+pub fn estimate_equity_from_outs(outs: u8, streets_remaining: u8) -> f64 {
+    // The hero's hole cards and the board dealt so far are the only cards
+    // which have been seen.
+    let streets = u32::from(streets_remaining.min(5));
+    let unseen = 45 + streets;
+    let blanks = unseen.saturating_sub(u32::from(outs));
+    let miss: f64 = (0..streets)
+        .map(|dealt| f64::from(blanks.saturating_sub(dealt)) / f64::from(unseen - dealt))
+        .product();
+    1. - miss
+}
+
+/// Returns every card which, dealt next, gives the hero a better kind of hand
+/// than `villain`, in deck order.
+///
+/// # Errors
+///
+/// Will return an error if the board doesn't have three or four cards, or if
+/// any card is used more than once.
+pub fn outs(
+    hole: impl Into<[Card; 2]>,
+    villain: HandKind,
+    board: &[Card],
+) -> Result<Vec<Card>, PokerError> {
+    let hole = hole.into();
+    if !(3..=4).contains(&board.len()) {
+        return Err(PokerError::InvalidBoardSize(board.len()));
+    }
+    let cards = [&hole[..], board].concat();
+    Ok(remaining_deck(&[hole], board, &[])?
+        .into_iter()
+        .filter(|&card| {
+            HandCounts::new(&[&cards[..], &[card]].concat())
+                .best_high()
+                .kind()
+                > villain
+        })
+        .collect())
+}
+
+/// Estimates the hero's equity against a villain assumed to hold a hand of
+/// the given kind, by counting the hero's outs and assuming the villain never
+/// improves.
+///
+/// A hero who already has a better kind of hand is given all of the equity,
+/// and on the river the hero has either won or lost. This is only meant as a
+/// first guess: against made hands it is usually within a few percent of the
+/// exact equity, but it ignores runner-runner draws and the villain's outs.
+///
+/// # Errors
+///
+/// Will return an error if the board doesn't have three to five cards, or if
+/// any card is used more than once.
+pub fn quick_estimate(
+    hole: impl Into<[Card; 2]>,
+    villain: HandKind,
+    board: &[Card],
+) -> Result<f64, PokerError> {
+    let hole = hole.into();
+    if !(3..=5).contains(&board.len()) {
+        return Err(PokerError::InvalidBoardSize(board.len()));
+    }
+    remaining_deck(&[hole], board, &[])?;
+    let made = HandCounts::new(&[&hole[..], board].concat()).best_high();
+    if made.kind() > villain {
+        return Ok(1.);
+    }
+    if board.len() == 5 {
+        return Ok(0.);
+    }
+
+    let outs = outs(hole, villain, board)?.len();
+    Ok(estimate_equity_from_outs(
+        u8::try_from(outs).unwrap_or(u8::MAX),
+        u8::try_from(5 - board.len()).unwrap_or(u8::MAX),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_equity_n, parse_cards};
+
+    /// How far the estimate may be from the exact equity against a villain
+    /// holding two pair, whose full house redraws the estimate ignores.
+    const TOLERANCE: f64 = 0.03;
+
+    fn exact_equity(hero: &str, villain: &str, board: &str) -> f64 {
+        let (hero, villain) = (parse_cards(hero).unwrap(), parse_cards(villain).unwrap());
+        let [hero, _] = compute_equity_n(
+            [[hero[0], hero[1]], [villain[0], villain[1]]],
+            &parse_cards(board).unwrap(),
+            &[],
+        )
+        .unwrap();
+        hero.equity()
+    }
+
+    fn against_two_pair(hero: &str, board: &str) -> f64 {
+        let hero = parse_cards(hero).unwrap();
+        quick_estimate(
+            [hero[0], hero[1]],
+            HandKind::TwoPair,
+            &parse_cards(board).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_equity_from_outs() {
+        assert!((estimate_equity_from_outs(9, 2) - (1. - 38. * 37. / (47. * 46.))).abs() < 1e-12);
+        assert!((estimate_equity_from_outs(9, 1) - 9. / 46.).abs() < 1e-12);
+        assert!((estimate_equity_from_outs(4, 2) - 0.1647).abs() < 1e-4);
+        assert_eq!(estimate_equity_from_outs(0, 2), 0.);
+        assert_eq!(estimate_equity_from_outs(9, 0), 0.);
+        assert_eq!(estimate_equity_from_outs(u8::MAX, 2), 1.);
+    }
+
+    #[test]
+    fn test_flush_draw() {
+        let estimate = against_two_pair("AhKh", "Qh7h2c");
+        assert!((estimate - 0.35).abs() < 0.005);
+        assert!((estimate - exact_equity("AhKh", "Qs7s", "Qh7h2c")).abs() < TOLERANCE);
+
+        let turn = against_two_pair("AhKh", "Qh7h2c3d");
+        assert!((turn - exact_equity("AhKh", "Qs7s", "Qh7h2c3d")).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_gutshot() {
+        let estimate = against_two_pair("9h8c", "Jd7s2c");
+        assert!((estimate - 0.165).abs() < 0.005);
+        assert!((estimate - exact_equity("9h8c", "Js7c", "Jd7s2c")).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_made_hands_and_errors() {
+        assert_eq!(against_two_pair("AhKh", "Qh7h2h"), 1.);
+        assert_eq!(against_two_pair("AhKh", "Qh7h2c3d4s"), 0.);
+        assert_eq!(against_two_pair("7c7d", "7h7s2c3d4s"), 1.);
+
+        let hole = parse_cards("AhKh").unwrap();
+        let hole = [hole[0], hole[1]];
+        let outs = outs(hole, HandKind::Pair, &parse_cards("Qh7h2c").unwrap()).unwrap();
+        assert_eq!(outs.len(), 9);
+        assert!(outs.iter().all(|card| card.suit() == crate::Suit::Hearts));
+
+        assert_eq!(
+            quick_estimate(hole, HandKind::Pair, &[]),
+            Err(PokerError::InvalidBoardSize(0))
+        );
+        assert_eq!(
+            quick_estimate(hole, HandKind::Pair, &parse_cards("Ah7h2c").unwrap()),
+            Err(PokerError::DuplicateCard(hole[0]))
+        );
+    }
+}
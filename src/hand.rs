@@ -0,0 +1,276 @@
+use crate::{parse_cards, Card, PokerError, StartingHand};
+use std::fmt;
+use std::str::FromStr;
+
+/// Two distinct hole cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hand([Card; 2]);
+
+impl Hand {
+    /// # Errors
+    ///
+    /// Will return [`PokerError::DuplicateCard`] if both cards are the same.
+    pub fn new(first: Card, second: Card) -> Result<Self, PokerError> {
+        Self::try_from([first, second])
+    }
+
+    /// Collects exactly `N` distinct cards into an array, which is the usual
+    /// way of building two card hands, five card boards and seven card hands
+    /// from parsed input.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PokerError::DuplicateCard`] if a card appears twice, or
+    /// [`PokerError::InvalidHandSize`] if there aren't exactly `N` cards.
+    pub fn try_from_iter<const N: usize>(
+        cards: impl IntoIterator<Item = Card>,
+    ) -> Result<[Card; N], PokerError> {
+        let cards: Vec<Card> = cards.into_iter().collect();
+        for (i, card) in cards.iter().enumerate() {
+            if cards[..i].contains(card) {
+                return Err(PokerError::DuplicateCard(*card));
+            }
+        }
+
+        cards
+            .try_into()
+            .map_err(|cards: Vec<Card>| PokerError::InvalidHandSize {
+                expected: N,
+                found: cards.len(),
+            })
+    }
+
+    #[must_use]
+    pub const fn cards(self) -> [Card; 2] {
+        self.0
+    }
+}
+
+impl TryFrom<[Card; 2]> for Hand {
+    type Error = PokerError;
+
+    fn try_from(cards: [Card; 2]) -> Result<Self, Self::Error> {
+        Self::try_from_iter(cards).map(Self)
+    }
+}
+
+impl From<Hand> for [Card; 2] {
+    fn from(hand: Hand) -> Self {
+        hand.0
+    }
+}
+
+impl fmt::Display for Hand {
+    /// Writes both cards back to back, such as `"AsKh"`, or with the
+    /// alternate flag, with suit symbols, such as `"A♠K♥"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0[0].fmt(f)?;
+        self.0[1].fmt(f)
+    }
+}
+
+impl FromStr for Hand {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_iter(parse_cards(s)?).map(Self)
+    }
+}
+
+/// Two distinct hole cards in a canonical order: the higher number first, and
+/// for pairs, the higher suit value first. Hole cards which hold the same two
+/// cards are equal however they were dealt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HoleCards([Card; 2]);
+
+impl HoleCards {
+    /// # Errors
+    ///
+    /// Will return [`PokerError::DuplicateCard`] if both cards are the same.
+    pub fn new(first: Card, second: Card) -> Result<Self, PokerError> {
+        Hand::new(first, second).map(Self::from)
+    }
+
+    /// Returns both cards, in the canonical order.
+    #[must_use]
+    pub const fn cards(self) -> [Card; 2] {
+        self.0
+    }
+
+    #[must_use]
+    pub fn is_pair(self) -> bool {
+        self.0[0].number() == self.0[1].number()
+    }
+
+    #[must_use]
+    pub fn is_suited(self) -> bool {
+        self.0[0].suit() == self.0[1].suit()
+    }
+
+    /// Returns which of the 169 classes of starting hand these cards are in.
+    #[must_use]
+    pub fn class(self) -> StartingHand {
+        StartingHand::of(self.0)
+    }
+}
+
+impl From<Hand> for HoleCards {
+    fn from(hand: Hand) -> Self {
+        let [first, second] = hand.cards();
+        let key = |card: Card| (card.number(), card.suit() as u8);
+        if key(first) >= key(second) {
+            Self([first, second])
+        } else {
+            Self([second, first])
+        }
+    }
+}
+
+impl TryFrom<[Card; 2]> for HoleCards {
+    type Error = PokerError;
+
+    fn try_from(cards: [Card; 2]) -> Result<Self, Self::Error> {
+        Hand::try_from(cards).map(Self::from)
+    }
+}
+
+impl From<HoleCards> for [Card; 2] {
+    fn from(hole_cards: HoleCards) -> Self {
+        hole_cards.0
+    }
+}
+
+impl fmt::Display for HoleCards {
+    /// Writes both cards in the canonical order, as [`Hand`] does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Hand(self.0).fmt(f)
+    }
+}
+
+impl FromStr for HoleCards {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Hand>().map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Number, Suit};
+
+    #[test]
+    fn test_collect_hands() {
+        let royal_flush: [Card; 5] =
+            Hand::try_from_iter(parse_cards("AsKsQsJsTs").unwrap()).unwrap();
+        assert_eq!(royal_flush[0], Card::new(Suit::Spades, Number::Ace));
+        assert_eq!(royal_flush[4], Card::new(Suit::Spades, Number::Ten));
+
+        let hole_cards: [Card; 2] = Hand::try_from_iter(parse_cards("7h2c").unwrap()).unwrap();
+        assert_eq!(Hand::try_from(hole_cards).map(Hand::cards), Ok(hole_cards));
+    }
+
+    #[test]
+    fn test_duplicate_card() {
+        assert_eq!(
+            Hand::try_from_iter::<7>(parse_cards("AsKsQsJsTs2dKs").unwrap()),
+            Err(PokerError::DuplicateCard(Card::new(
+                Suit::Spades,
+                Number::King
+            )))
+        );
+
+        let ace = Card::new(Suit::Spades, Number::Ace);
+        assert_eq!(
+            Hand::try_from([ace, ace]),
+            Err(PokerError::DuplicateCard(ace))
+        );
+    }
+
+    #[test]
+    fn test_wrong_number_of_cards() {
+        assert_eq!(
+            Hand::try_from_iter::<7>(parse_cards("AsKsQsJsTs").unwrap()),
+            Err(PokerError::InvalidHandSize {
+                expected: 7,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Hand::try_from_iter::<2>(parse_cards("AsKsQs").unwrap()),
+            Err(PokerError::InvalidHandSize {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_duplicates() {
+        let ace = Card::new(Suit::Spades, Number::Ace);
+        let king = Card::new(Suit::Hearts, Number::King);
+        assert_eq!(Hand::new(ace, king).map(Hand::cards), Ok([ace, king]));
+        assert_eq!(Hand::new(ace, ace), Err(PokerError::DuplicateCard(ace)));
+        assert_eq!("AsAs".parse::<Hand>(), Err(PokerError::DuplicateCard(ace)));
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let hand: Hand = "AsKh".parse().unwrap();
+        assert_eq!(hand.to_string(), "AsKh");
+        assert_eq!(format!("{hand:#}"), "A♠K♥");
+        assert_eq!(hand.to_string().parse(), Ok(hand));
+
+        assert!("AsK".parse::<Hand>().is_err());
+        assert_eq!(
+            "AsKhQd".parse::<Hand>(),
+            Err(PokerError::InvalidHandSize {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_hole_cards_order() {
+        let hole_cards: HoleCards = "KhAs".parse().unwrap();
+        assert_eq!(hole_cards, "AsKh".parse().unwrap());
+        assert_eq!(hole_cards.to_string(), "AsKh");
+        assert_eq!(hole_cards.cards(), "AsKh".parse::<Hand>().unwrap().cards());
+
+        // Pairs are ordered by suit, so either deal of them is the same.
+        assert_eq!(
+            "7h7s".parse::<HoleCards>().unwrap(),
+            "7s7h".parse::<HoleCards>().unwrap()
+        );
+        assert_eq!(
+            "AsAs".parse::<HoleCards>(),
+            Err(PokerError::DuplicateCard(Card::new(
+                Suit::Spades,
+                Number::Ace
+            )))
+        );
+    }
+
+    #[test]
+    fn test_hole_cards_class() {
+        let pair: HoleCards = "8d8c".parse().unwrap();
+        assert!(pair.is_pair() && !pair.is_suited());
+        assert_eq!(pair.class(), StartingHand::Pair(Number::Eight));
+
+        let suited: HoleCards = "5hAh".parse().unwrap();
+        assert!(suited.is_suited() && !suited.is_pair());
+        assert_eq!(
+            suited.class(),
+            StartingHand::Suited(Number::Ace, Number::Five)
+        );
+        assert_eq!(suited.class().to_string(), "A5s");
+
+        let offsuit: HoleCards = "Tc9d".parse().unwrap();
+        assert_eq!(
+            offsuit.class(),
+            StartingHand::Offsuit(Number::Ten, Number::Nine)
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! The distinct classes of five card hands, which are the hands that can be
+//! told apart at showdown.
+
+use crate::{rank_histogram, Card, HandCounts, HandEvaluation, Number, PokerError, Suit};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+/// The number of distinct five card hands, from `7-5-4-3-2` up to a royal
+/// flush.
+pub const DISTINCT_HAND_CLASSES: usize = 7462;
+
+/// Returns the packed [`HandEvaluation::rank`] of every class of hand, from
+/// the weakest to the strongest.
+///
+/// Only the numbers of five cards and whether they share a suit matter to
+/// their evaluation, so this evaluates one hand for each way of choosing five
+/// numbers, and a flush for those with five different numbers, rather than
+/// all of the hands.
+fn class_ranks() -> &'static [u32] {
+    static RANKS: OnceLock<Vec<u32>> = OnceLock::new();
+    RANKS.get_or_init(|| {
+        let mut ranks = vec![];
+        for numbers in Number::ALL.into_iter().combinations_with_replacement(5) {
+            // Cards of the same number are next to each other, so giving each
+            // position its own suit never repeats a card or makes a flush.
+            let cards: Vec<Card> = numbers
+                .iter()
+                .enumerate()
+                .map(|(position, &number)| Card::new(Suit::ALL[position % 4], number))
+                .collect();
+            // There are only four cards of each number.
+            if rank_histogram(&cards).iter().any(|&count| count > 4) {
+                continue;
+            }
+            ranks.push(HandCounts::new(&cards).best_high().rank());
+
+            if numbers.iter().dedup().count() == numbers.len() {
+                let flush: Vec<Card> = numbers
+                    .iter()
+                    .map(|&number| Card::new(Suit::Hearts, number))
+                    .collect();
+                ranks.push(HandCounts::new(&flush).best_high().rank());
+            }
+        }
+        ranks.sort_unstable();
+        ranks.dedup();
+        ranks
+    })
+}
+
+impl HandEvaluation {
+    /// Returns the dense rank of this evaluation among every distinct five
+    /// card hand, from 1 for `7-5-4-3-2` up to 7462 for a royal flush.
+    ///
+    /// Only evaluations which some hand can make have a rank of their own.
+    /// Any other evaluation, such as one built from a bitset with the wrong
+    /// number of cards, is given the rank of the weakest class above it.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn rank5(self) -> u32 {
+        let rank = self.rank();
+        class_ranks().partition_point(|&other| other < rank) as u32 + 1
+    }
+}
+
+/// Evaluates every one of the C(52, 5) five card hands and returns each
+/// distinct evaluation once, from the weakest to the strongest, along with
+/// its name and its dense rank from 1 to 7462.
+///
+/// The name is written with the alternate flag, as `{:#}`, which names every
+/// kicker.
+///
+/// This checks the evaluator against every hand rather than trusting the
+/// lookup behind [`HandEvaluation::rank5`], so it is slow, and is meant for
+/// exporting and validating the table.
+#[must_use]
+pub fn distinct_hand_classes() -> Vec<(HandEvaluation, String, u32)> {
+    let mut classes = BTreeSet::new();
+    let mut hand = [Card::ALL[0]; 5];
+    for (a, &first) in Card::ALL.iter().enumerate() {
+        hand[0] = first;
+        for (b, &second) in Card::ALL.iter().enumerate().skip(a + 1) {
+            hand[1] = second;
+            for (c, &third) in Card::ALL.iter().enumerate().skip(b + 1) {
+                hand[2] = third;
+                for (d, &fourth) in Card::ALL.iter().enumerate().skip(c + 1) {
+                    hand[3] = fourth;
+                    for &fifth in &Card::ALL[d + 1..] {
+                        hand[4] = fifth;
+                        classes.insert(HandCounts::new(&hand).best_high());
+                    }
+                }
+            }
+        }
+    }
+
+    (1..)
+        .zip(classes)
+        .map(|(rank, evaluation)| (evaluation, format!("{evaluation:#}"), rank))
+        .collect()
+}
+
+/// Packs evaluations into a blob of four bytes each, as written by
+/// [`HandEvaluation::to_bytes`].
+///
+/// A table built once can then be embedded with `include_bytes!` and read
+/// back with [`evaluations_from_bytes`].
+#[must_use]
+pub fn evaluations_to_bytes(evaluations: &[HandEvaluation]) -> Vec<u8> {
+    evaluations
+        .iter()
+        .flat_map(|evaluation| evaluation.to_bytes())
+        .collect()
+}
+
+/// Reads back a blob written by [`evaluations_to_bytes`].
+///
+/// # Errors
+///
+/// Will return an error if the length of the blob isn't a multiple of four,
+/// or if any four bytes aren't an evaluation which a hand can make.
+pub fn evaluations_from_bytes(bytes: &[u8]) -> Result<Vec<HandEvaluation>, PokerError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(PokerError::InvalidRequest(format!(
+            "evaluations take four bytes each, but found {} bytes",
+            bytes.len()
+        )));
+    }
+    bytes
+        .chunks_exact(4)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let bytes = [chunk[0], chunk[1], chunk[2], chunk[3]];
+            HandEvaluation::from_bytes(bytes).ok_or_else(|| {
+                PokerError::InvalidRequest(format!(
+                    "invalid evaluation {bytes:02x?} at byte {}",
+                    index * 4
+                ))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{evaluate_cards, parse_cards, HandKind};
+    use std::collections::HashSet;
+
+    fn evaluate(cards: &str) -> HandEvaluation {
+        evaluate_cards(&parse_cards(cards).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_rank5() {
+        assert_eq!(class_ranks().len(), DISTINCT_HAND_CLASSES);
+        assert_eq!(evaluate("7c5d4h3s2c").rank5(), 1);
+        assert_eq!(evaluate("AhKhQhJhTh").rank5(), 7462);
+        // The weakest pair beats all 1277 high card hands.
+        assert_eq!(evaluate("2c2d5h4s3c").rank5(), 1278);
+        assert_eq!(evaluate("AhKhQhJh9h").rank5(), 7462 - 10 - 156 - 156);
+
+        // Extra cards don't change the rank of the best five.
+        assert_eq!(evaluate("AhKhQhJhTh 2c3d").rank5(), 7462);
+        assert!(evaluate("AsAdKsKd2c").rank5() < evaluate("AsAdKsKd3c").rank5());
+    }
+
+    #[test]
+    fn test_distinct_hand_classes() {
+        let classes = distinct_hand_classes();
+        assert_eq!(classes.len(), DISTINCT_HAND_CLASSES);
+
+        let mut by_kind = [0; 9];
+        for (index, (evaluation, _, rank)) in classes.iter().enumerate() {
+            assert_eq!(*rank as usize, index + 1);
+            assert_eq!(evaluation.rank5(), *rank);
+            by_kind[evaluation.kind() as usize] += 1;
+        }
+        assert_eq!(by_kind, [1277, 2860, 858, 858, 10, 1277, 156, 156, 10]);
+        assert_eq!(classes.first().unwrap().0.kind(), HandKind::HighCard);
+
+        let names: HashSet<&str> = classes.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(names.len(), DISTINCT_HAND_CLASSES);
+        assert_eq!(
+            classes[0].1,
+            "High Card, Seven, Five, Four, Three and Two kickers"
+        );
+        assert_eq!(classes[DISTINCT_HAND_CLASSES - 1].1, "Royal Flush");
+    }
+
+    #[test]
+    fn test_evaluation_bytes_round_trip() {
+        let evaluations: Vec<HandEvaluation> = class_ranks()
+            .iter()
+            .map(|&rank| HandEvaluation::from_rank(rank).unwrap())
+            .collect();
+        let bytes = evaluations_to_bytes(&evaluations);
+        assert_eq!(bytes.len(), DISTINCT_HAND_CLASSES * 4);
+
+        let read = evaluations_from_bytes(&bytes).unwrap();
+        assert_eq!(read.len(), DISTINCT_HAND_CLASSES);
+        assert!(read == evaluations);
+        assert_eq!(read[DISTINCT_HAND_CLASSES - 1].rank5(), 7462);
+
+        assert!(matches!(
+            evaluations_from_bytes(&bytes[..6]),
+            Err(PokerError::InvalidRequest(_))
+        ));
+        let mut corrupt = bytes;
+        corrupt[8] = 9;
+        let error = evaluations_from_bytes(&corrupt).err().unwrap();
+        assert!(error.to_string().ends_with("at byte 8"), "{error}");
+    }
+
+    #[test]
+    fn test_hand_names() {
+        let names = [
+            ("9h8h7h6h5h", "Straight Flush, Nine high"),
+            ("5h4h3h2hAh", "Straight Flush, Five high"),
+            ("AsAdAcAhKs", "Four of a Kind, Aces, King kicker"),
+            ("6s6d6cKhKs", "Full House, Sixes full of Kings"),
+            (
+                "AhJh9h7h6h",
+                "Flush, Ace high, Jack, Nine, Seven and Six kickers",
+            ),
+            ("5h4c3d2sAh", "Straight, Five high"),
+            ("TsTdTc9h2s", "Three of a Kind, Tens, Nine and Two kickers"),
+            ("JsJdTcTh2s", "Two Pair, Jacks and Tens, Two kicker"),
+            ("QsQd9c4h2s", "Pair of Queens, Nine, Four and Two kickers"),
+            (
+                "KsJd9c4h2s",
+                "High Card, King, Jack, Nine, Four and Two kickers",
+            ),
+        ];
+        for (cards, name) in names {
+            assert_eq!(format!("{:#}", evaluate(cards)), name);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{compute_equity_memoized, parse_cards, Card, EquityMethod, EquityResult, MonteCarlo};
+use crate::{Hand, PokerError};
+use std::fmt;
+use std::str::FromStr;
+
+/// A betting round, named after the board cards dealt before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// Returns the number of board cards which have been dealt by this
+    /// street.
+    #[must_use]
+    pub const fn board_len(self) -> usize {
+        match self {
+            Self::Preflop => 0,
+            Self::Flop => 3,
+            Self::Turn => 4,
+            Self::River => 5,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "flop" => Some(Self::Flop),
+            "turn" => Some(Self::Turn),
+            "river" => Some(Self::River),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Preflop => "Preflop",
+            Self::Flop => "Flop",
+            Self::Turn => "Turn",
+            Self::River => "River",
+        })
+    }
+}
+
+/// The cards of a hand which went to showdown, written one line at a time:
+///
+/// ```text
+/// # Lines starting with a hash are comments.
+/// Alice: As Ad
+/// Bob: Kh Qh
+/// Flop: Jh 7h 2c
+/// Turn: 3s
+/// River: 9h
+/// ```
+///
+/// Every player's hole cards come first, then the board a street at a time.
+/// The board stops at whichever street the hand ended on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandHistory {
+    /// Each player's name and hole cards, in the order they were given.
+    pub players: Vec<(String, Hand)>,
+    pub board: Vec<Card>,
+}
+
+impl HandHistory {
+    /// Returns the last street which was dealt.
+    #[must_use]
+    pub const fn last_street(&self) -> Street {
+        match self.board.len() {
+            0 => Street::Preflop,
+            3 => Street::Flop,
+            4 => Street::Turn,
+            _ => Street::River,
+        }
+    }
+}
+
+fn history_error(line: usize, message: impl Into<String>) -> PokerError {
+    PokerError::InvalidHistory {
+        line,
+        message: message.into(),
+    }
+}
+
+impl FromStr for HandHistory {
+    type Err = PokerError;
+
+    /// Parses a hand history, reporting the line number of the first problem.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut players: Vec<(String, Hand)> = vec![];
+        let mut board = vec![];
+        let mut used = vec![];
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, cards) = line.split_once(':').ok_or_else(|| {
+                history_error(line_number, "expected a name or street, then a colon")
+            })?;
+            let (name, cards) = (name.trim(), cards.trim());
+            let cards = parse_cards(cards)
+                .map_err(|error| history_error(line_number, error.to_string()))?;
+            if let Some(card) = cards.iter().find(|&card| used.contains(card)) {
+                return Err(history_error(
+                    line_number,
+                    format!("{card} was already dealt"),
+                ));
+            }
+            used.extend(&cards);
+
+            if let Some(street) = Street::from_name(name) {
+                let needed = if street == Street::Flop { 3 } else { 1 };
+                if players.is_empty() {
+                    return Err(history_error(
+                        line_number,
+                        format!("the {street} came before any hole cards"),
+                    ));
+                }
+                if board.len() + needed != street.board_len() {
+                    return Err(history_error(
+                        line_number,
+                        format!("the {street} is out of order"),
+                    ));
+                }
+                if cards.len() != needed {
+                    return Err(history_error(
+                        line_number,
+                        format!(
+                            "the {street} needs {needed} cards, but found {}",
+                            cards.len()
+                        ),
+                    ));
+                }
+                board.extend(cards);
+            } else {
+                if !board.is_empty() {
+                    return Err(history_error(
+                        line_number,
+                        format!("{name}'s hole cards came after the board"),
+                    ));
+                }
+                if players.iter().any(|(player, _)| player == name) {
+                    return Err(history_error(line_number, format!("{name} appears twice")));
+                }
+                let hand = Hand::try_from_iter::<2>(cards)
+                    .and_then(Hand::try_from)
+                    .map_err(|error| history_error(line_number, error.to_string()))?;
+                players.push((name.to_string(), hand));
+            }
+        }
+
+        if players.len() < 2 {
+            return Err(history_error(
+                s.lines().count(),
+                "at least two players need hole cards",
+            ));
+        }
+        Ok(Self { players, board })
+    }
+}
+
+/// The equity of every player as a street was dealt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreetEquity {
+    pub street: Street,
+    /// The results for each player, in the same order as the history.
+    pub results: Vec<EquityResult>,
+    /// Whether a different player is ahead than on the street before.
+    pub lead_changed: bool,
+}
+
+impl StreetEquity {
+    /// Returns the index of the player with the most equity, preferring the
+    /// earliest player when equities are equal.
+    #[must_use]
+    pub fn leader(&self) -> usize {
+        self.results
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.equity().total_cmp(&b.equity()))
+            .map_or(0, |(index, _)| index)
+    }
+}
+
+/// Computes every player's equity on each street the hand reached, from
+/// preflop onwards, and flags the streets where the lead changed hands.
+///
+/// # Errors
+///
+/// Will return an error if the history uses a card more than once.
+pub fn analyze(
+    history: &HandHistory,
+    method: EquityMethod,
+) -> Result<Vec<StreetEquity>, PokerError> {
+    let hands: Vec<[Card; 2]> = history
+        .players
+        .iter()
+        .map(|(_, hand)| hand.cards())
+        .collect();
+
+    let mut streets: Vec<StreetEquity> = vec![];
+    for street in [Street::Preflop, Street::Flop, Street::Turn, Street::River] {
+        if street > history.last_street() {
+            break;
+        }
+        let board = &history.board[..street.board_len()];
+        let results = match method {
+            EquityMethod::Exhaustive => compute_equity_memoized(&hands, board, &[])?,
+            EquityMethod::MonteCarlo { samples, seed } => {
+                let mut simulation = MonteCarlo::new(&hands, board, &[], seed)?;
+                simulation.run(samples);
+                simulation.results().to_vec()
+            }
+        };
+
+        let mut equity = StreetEquity {
+            street,
+            results,
+            lead_changed: false,
+        };
+        equity.lead_changed = streets
+            .last()
+            .is_some_and(|previous| previous.leader() != equity.leader());
+        streets.push(equity);
+    }
+    Ok(streets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACES_CRACKED: &str = include_str!("../tests/fixtures/aces_cracked.txt");
+    const STRAIGHT_OVER_SET: &str = include_str!("../tests/fixtures/straight_over_set.txt");
+
+    fn simulate(history: &HandHistory) -> Vec<StreetEquity> {
+        analyze(
+            history,
+            EquityMethod::MonteCarlo {
+                samples: 20_000,
+                seed: Some(1),
+            },
+        )
+        .unwrap()
+    }
+
+    fn equities(streets: &[StreetEquity], player: usize) -> Vec<f64> {
+        streets
+            .iter()
+            .map(|street| street.results[player].equity())
+            .collect()
+    }
+
+    #[test]
+    fn test_aces_cracked_on_the_river() {
+        let history: HandHistory = ACES_CRACKED.parse().unwrap();
+        assert_eq!(history.players[0].0, "Alice");
+        assert_eq!(history.last_street(), Street::River);
+
+        let streets = simulate(&history);
+        let expected = [0.83, 0.62, 0.795, 0.];
+        for (equity, expected) in equities(&streets, 0).into_iter().zip(expected) {
+            assert!((equity - expected).abs() < 0.02);
+        }
+        let lead_changes: Vec<bool> = streets.iter().map(|street| street.lead_changed).collect();
+        assert_eq!(lead_changes, [false, false, false, true]);
+        assert_eq!(streets[3].leader(), 1);
+    }
+
+    #[test]
+    fn test_hand_ending_on_the_turn() {
+        let history: HandHistory = STRAIGHT_OVER_SET.parse().unwrap();
+        assert_eq!(history.players.len(), 3);
+        assert_eq!(history.last_street(), Street::Turn);
+
+        let streets = simulate(&history);
+        let leaders: Vec<usize> = streets.iter().map(StreetEquity::leader).collect();
+        assert_eq!(leaders, [0, 2, 2]);
+        let expected = [0.19, 0.61, 0.79];
+        for (equity, expected) in equities(&streets, 2).into_iter().zip(expected) {
+            assert!((equity - expected).abs() < 0.02);
+        }
+        let streets: Vec<Street> = streets.iter().map(|street| street.street).collect();
+        assert_eq!(streets, [Street::Preflop, Street::Flop, Street::Turn]);
+
+        // Carol flops a straight, overtaking Alice's preflop lead.
+        let lead_changes: Vec<bool> = simulate(&history)
+            .iter()
+            .map(|street| street.lead_changed)
+            .collect();
+        assert_eq!(lead_changes, [false, true, false]);
+    }
+
+    #[test]
+    fn test_errors_report_the_line() {
+        let error = |history: &str| match history.parse::<HandHistory>() {
+            Err(PokerError::InvalidHistory { line, message }) => (line, message),
+            other => panic!("expected an error, but got {other:?}"),
+        };
+
+        assert_eq!(error("Alice: AsAd\nBob: KhQx").0, 2);
+        assert_eq!(error("Alice: AsAd\n\nBob: KhAs").0, 3);
+        assert_eq!(error("Alice: AsAd\nBob: KhQh\nTurn: 2c").0, 3);
+        assert_eq!(error("Alice: AsAd\nBob: KhQh\nFlop: 2c 3c").0, 3);
+        assert_eq!(error("Alice: AsAd\nFlop: 2c 3c 4c\nBob: KhQh").0, 3);
+        assert_eq!(error("Alice: AsAd\nAlice: KhQh").0, 2);
+        assert_eq!(error("Alice: AsAd KhQh").0, 1);
+        assert_eq!(error("Alice AsAd").0, 1);
+        assert!(error("Alice: AsAd").1.contains("two players"));
+    }
+}
@@ -0,0 +1,115 @@
+//! The independent chip model, which turns tournament stacks into shares of
+//! the prize pool.
+
+/// Returns each player's expected prize, in the same units as `payouts`,
+/// which are the prizes for first place, second place and so on.
+///
+/// This is the Malmuth-Harville model: a player finishes first with a
+/// probability proportional to their stack, and the later places are handed
+/// out the same way among the players who are left. Players without chips
+/// have already busted, so they share the places after everyone else's.
+///
+/// The work grows quickly with the number of paid places, so this is meant
+/// for final tables rather than whole fields.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn icm_equities(stacks: &[f64], payouts: &[f64]) -> Vec<f64> {
+    let mut equities = vec![0.; stacks.len()];
+    let live: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i] > 0.).collect();
+
+    let busted = stacks.len() - live.len();
+    if busted > 0 {
+        let share = payouts.iter().skip(live.len()).take(busted).sum::<f64>() / busted as f64;
+        for (equity, _) in equities
+            .iter_mut()
+            .zip(stacks)
+            .filter(|(_, &stack)| stack <= 0.)
+        {
+            *equity = share;
+        }
+    }
+
+    award_places(stacks, payouts, &live, 1., &mut equities);
+    equities
+}
+
+/// Hands out the next place in `payouts` among the `remaining` players, given
+/// that the places before it were handed out with the given `probability`.
+fn award_places(
+    stacks: &[f64],
+    payouts: &[f64],
+    remaining: &[usize],
+    probability: f64,
+    equities: &mut [f64],
+) {
+    let Some((&payout, payouts)) = payouts.split_first() else {
+        return;
+    };
+    let total: f64 = remaining.iter().map(|&player| stacks[player]).sum();
+    for &player in remaining {
+        let finishes_here = probability * stacks[player] / total;
+        equities[player] += finishes_here * payout;
+
+        let rest: Vec<usize> = remaining
+            .iter()
+            .copied()
+            .filter(|&other| other != player)
+            .collect();
+        award_places(stacks, payouts, &rest, finishes_here, equities);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "{actual} != {expected} in {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_icm_equities() {
+        // Equal stacks share the prize pool equally.
+        assert_close(
+            &icm_equities(&[1000.; 3], &[50., 30., 20.]),
+            &[100. / 3.; 3],
+        );
+        // Winner takes all is the same as counting chips.
+        assert_close(&icm_equities(&[1000., 3000.], &[1.]), &[0.25, 0.75]);
+
+        // 2 / 3 of the time the big stack wins, and otherwise is second.
+        let equities = icm_equities(&[2000., 1000.], &[0.65, 0.35]);
+        assert_close(
+            &equities,
+            &[2. / 3. * 0.65 + 0.35 / 3., 0.65 / 3. + 2. / 3. * 0.35],
+        );
+
+        // With one player to go before the money, the short stack's chips are
+        // worth more than their share of the chips.
+        let equities = icm_equities(&[500., 1000., 1500.], &[0.65, 0.35]);
+        assert!(equities[0] > 500. / 3000.);
+        assert!(equities[2] < 1500. / 3000.);
+        assert!((equities.iter().sum::<f64>() - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_busted_players_finish_last() {
+        assert_close(
+            &icm_equities(&[0., 2000., 1000.], &[0.5, 0.3, 0.2]),
+            &[0.2, 2. / 3. * 0.5 + 0.3 / 3., 0.5 / 3. + 2. / 3. * 0.3],
+        );
+        // Players who bust in the same hand split the places they share.
+        assert_close(
+            &icm_equities(&[0., 0., 1000.], &[0.5, 0.3, 0.2]),
+            &[0.25, 0.25, 0.5],
+        );
+        assert_close(&icm_equities(&[0., 1000.], &[1.]), &[0., 1.]);
+        assert!(icm_equities(&[], &[1.]).is_empty());
+    }
+}
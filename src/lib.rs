@@ -1,7 +1,131 @@
+//! Card types, a seven card hand evaluator and equity calculations.
+//!
+//! Everything but the card types and the evaluator needs the standard library,
+//! and is behind the default `std` feature. Without it, the crate is `no_std`
+//! and doesn't allocate.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use itertools::Itertools;
-use std::fmt;
+
+#[cfg(feature = "std")]
+mod allin;
+mod badugi;
+mod betting;
+mod bits;
+#[cfg(feature = "std")]
+mod board;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod deck;
+#[cfg(feature = "std")]
+mod equity;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+mod estimate;
+#[cfg(feature = "std")]
+mod hand;
+#[cfg(feature = "std")]
+mod hand_classes;
+#[cfg(feature = "std")]
+mod history;
+#[cfg(feature = "std")]
+mod icm;
+#[cfg(feature = "std")]
+mod matchup;
+#[cfg(feature = "std")]
+mod preflop;
+#[cfg(feature = "std")]
+mod pushfold;
+#[cfg(feature = "std")]
+mod range;
+#[cfg(feature = "std")]
+mod request;
+mod rules;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "std")]
+mod spot;
+#[cfg(feature = "std")]
+mod strength;
+#[cfg(feature = "tokio")]
+mod task;
+
+#[cfg(feature = "std")]
+pub use allin::{allin_ev, AllinEv, AllinSpot, Blinds};
+pub use badugi::{evaluate_badugi, BadugiHand};
+pub use betting::{bluff_value_ratio, mdf};
+pub use bits::{
+    highest_rank, rank_bits_by_suit, rank_bits_of, rank_histogram, straight_high_card,
+    suit_histogram, suit_rank_bits_of, top_n_ranks,
+};
+#[cfg(feature = "std")]
+pub use board::Board;
+#[cfg(feature = "std")]
+pub use cache::EquityCache;
+#[cfg(feature = "std")]
+pub use deck::Deck;
+#[cfg(feature = "std")]
+pub use equity::{
+    ahead_behind_breakdown, compute_equity, compute_equity_active, compute_equity_headsup,
+    compute_equity_memoized, compute_equity_multiway, compute_equity_n, compute_equity_run_it_n,
+    compute_equity_verbose, compute_equity_with_options, compute_side_pot_equity, drawing_dead,
+    enumerate_runouts, hand_kind_frequencies, is_freeroll, is_hand_locked, merge_results,
+    remaining_deck, rivers_that_win, runout_count, simulate_showdowns, split_breakdown,
+    DetailedEquityResult, EquityMethod, EquityOptions, EquityResult, HandKindBreakdown, MonteCarlo,
+    RunItResult, RunoutShowdown, SimulationStats,
+};
+#[cfg(feature = "std")]
+pub use error::PokerError;
+#[cfg(feature = "std")]
+pub use estimate::{estimate_equity_from_outs, outs, quick_estimate};
+#[cfg(feature = "std")]
+pub use hand::{Hand, HoleCards};
+#[cfg(feature = "std")]
+pub use hand_classes::{
+    distinct_hand_classes, evaluations_from_bytes, evaluations_to_bytes, DISTINCT_HAND_CLASSES,
+};
+#[cfg(feature = "std")]
+pub use history::{analyze, HandHistory, Street, StreetEquity};
+#[cfg(feature = "std")]
+pub use icm::icm_equities;
+#[cfg(feature = "std")]
+pub use matchup::{matchup_kind, MatchupKind};
+#[cfg(feature = "std")]
+pub use preflop::{compute_equity_canonical, PreflopTable};
+#[cfg(feature = "std")]
+pub use pushfold::pushfold_range;
+#[cfg(feature = "std")]
+pub use range::{
+    compute_range_equity, range_equity_grid, raw_vs_realized, ClassEquity, Range, RangeEquity,
+    StartingHand,
+};
+#[cfg(feature = "std")]
+pub use request::{EquityOutcome, EquityRequest, EquityRequestBuilder, Player};
+#[cfg(feature = "std")]
+pub use rules::compute_equity_with_rules;
+pub use rules::{evaluate_hand_with_rules, Rules, RulesEvaluation, ShortDeck, Standard};
+#[cfg(feature = "std")]
+pub use spot::{parse_spot, Spot};
+#[cfg(feature = "std")]
+pub use strength::preflop_strength;
+#[cfg(feature = "tokio")]
+pub use task::{compute_equity_async, CancelHandle, EquityTask, Progress};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+// Only the variants can be deserialized, so the unchecked constructors stay
+// sound.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    allow(clippy::unsafe_derive_deserialize)
+)]
 #[repr(u8)]
 pub enum Suit {
     Hearts = 0,
@@ -11,30 +135,101 @@ pub enum Suit {
 }
 
 impl Suit {
+    /// Every suit, in the order of their values.
+    pub const ALL: [Self; 4] = [Self::Hearts, Self::Diamonds, Self::Clubs, Self::Spades];
+
+    /// Returns every suit, in the order of their values.
+    #[must_use]
+    #[deprecated(note = "use `Suit::ALL`")]
+    pub const fn all() -> [Self; 4] {
+        Self::ALL
+    }
+
+    /// Iterates over every suit, in the order of their values.
+    #[deprecated(note = "use `Suit::ALL`")]
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the suit with the given value, or `None` if value is not in
+    /// the interval [0, 3].
+    #[must_use]
+    pub const fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Hearts),
+            1 => Some(Self::Diamonds),
+            2 => Some(Self::Clubs),
+            3 => Some(Self::Spades),
+            _ => None,
+        }
+    }
+
     /// # Panics
     ///
-    /// Will panic if value is in the interval [0, 3]
+    /// Will panic if value is not in the interval [0, 3]
     #[must_use]
-    pub fn from_u8(value: u8) -> Self {
-        match value {
-            0 => Self::Hearts,
-            1 => Self::Diamonds,
-            2 => Self::Clubs,
-            3 => Self::Spades,
-            _ => panic!("Invalid suite number: {}", value),
+    #[deprecated(note = "use `Suit::try_from_u8` or `Suit::try_from`, which don't panic")]
+    pub const fn from_u8(value: u8) -> Self {
+        // The message isn't formatted, which keeps the formatting machinery
+        // out of `no_std` builds.
+        match Self::try_from_u8(value) {
+            Some(suit) => suit,
+            None => panic!("suit numbers must be in the interval [0, 3]"),
         }
     }
 
     /// # Safety
     ///
-    /// This must be called with value in the range [0, 3]
+    /// This must be called with value in the range [0, 3]. Debug builds
+    /// panic if it isn't.
     #[must_use]
     pub unsafe fn from_u8_unchecked(value: u8) -> Self {
-        std::mem::transmute(value)
+        debug_assert!(value <= 3, "invalid suit: {value}");
+        core::mem::transmute(value)
+    }
+
+    /// Returns the lowercase letter used for this suit in card notation.
+    #[must_use]
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::Hearts => 'h',
+            Self::Diamonds => 'd',
+            Self::Clubs => 'c',
+            Self::Spades => 's',
+        }
+    }
+
+    /// Returns the symbol used for this suit when printing cards for people,
+    /// such as `'♠'`.
+    #[must_use]
+    pub const fn as_glyph(self) -> char {
+        match self {
+            Self::Hearts => '♥',
+            Self::Diamonds => '♦',
+            Self::Clubs => '♣',
+            Self::Spades => '♠',
+        }
+    }
+
+    /// Parses a suit letter, accepting either case.
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'h' => Some(Self::Hearts),
+            'd' => Some(Self::Diamonds),
+            'c' => Some(Self::Clubs),
+            's' => Some(Self::Spades),
+            _ => None,
+        }
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    allow(clippy::unsafe_derive_deserialize)
+)]
 #[repr(u8)]
 pub enum Number {
     Two = 2,
@@ -53,49 +248,181 @@ pub enum Number {
 }
 
 impl Number {
+    /// Every number, from two up to ace.
+    pub const ALL: [Self; 13] = [
+        Self::Two,
+        Self::Three,
+        Self::Four,
+        Self::Five,
+        Self::Six,
+        Self::Seven,
+        Self::Eight,
+        Self::Nine,
+        Self::Ten,
+        Self::Jack,
+        Self::Queen,
+        Self::King,
+        Self::Ace,
+    ];
+
+    /// Iterates over every number from ace down to two.
+    pub fn iter_desc() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter().rev()
+    }
+
+    /// Returns the number with the given value, or `None` if value is not in
+    /// the interval [2, 14].
+    #[must_use]
+    pub const fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            2 => Some(Self::Two),
+            3 => Some(Self::Three),
+            4 => Some(Self::Four),
+            5 => Some(Self::Five),
+            6 => Some(Self::Six),
+            7 => Some(Self::Seven),
+            8 => Some(Self::Eight),
+            9 => Some(Self::Nine),
+            10 => Some(Self::Ten),
+            11 => Some(Self::Jack),
+            12 => Some(Self::Queen),
+            13 => Some(Self::King),
+            14 => Some(Self::Ace),
+            _ => None,
+        }
+    }
+
     /// # Panics
     ///
     /// Will panic if value is not in the interval [2, 14]
     #[must_use]
-    pub fn from_u8(value: u8) -> Self {
-        match value {
-            2 => Self::Two,
-            3 => Self::Three,
-            4 => Self::Four,
-            5 => Self::Five,
-            6 => Self::Six,
-            7 => Self::Seven,
-            8 => Self::Eight,
-            9 => Self::Nine,
-            10 => Self::Ten,
-            11 => Self::Jack,
-            12 => Self::Queen,
-            13 => Self::King,
-            14 => Self::Ace,
-            _ => panic!("Invalid card number: {}", value),
+    #[deprecated(note = "use `Number::try_from_u8` or `Number::try_from`, which don't panic")]
+    pub const fn from_u8(value: u8) -> Self {
+        match Self::try_from_u8(value) {
+            Some(number) => number,
+            None => panic!("card numbers must be in the interval [2, 14]"),
         }
     }
 
     /// # Safety
     ///
-    /// This must be called with value in the range [2, 14]
+    /// This must be called with value in the range [2, 14]. Debug builds
+    /// panic if it isn't.
     #[must_use]
     pub unsafe fn from_u8_unchecked(value: u8) -> Self {
-        std::mem::transmute(value)
+        debug_assert!((2..=14).contains(&value), "invalid card number: {value}");
+        core::mem::transmute(value)
     }
 
     #[must_use]
     pub const fn as_bit(self) -> u16 {
         1 << (self as u8)
     }
+
+    /// Returns the character used for this number in card notation, using
+    /// `'T'` for ten.
+    #[must_use]
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::Two => '2',
+            Self::Three => '3',
+            Self::Four => '4',
+            Self::Five => '5',
+            Self::Six => '6',
+            Self::Seven => '7',
+            Self::Eight => '8',
+            Self::Nine => '9',
+            Self::Ten => 'T',
+            Self::Jack => 'J',
+            Self::Queen => 'Q',
+            Self::King => 'K',
+            Self::Ace => 'A',
+        }
+    }
+
+    /// Parses a number character, accepting either case for face cards.
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            '2' => Some(Self::Two),
+            '3' => Some(Self::Three),
+            '4' => Some(Self::Four),
+            '5' => Some(Self::Five),
+            '6' => Some(Self::Six),
+            '7' => Some(Self::Seven),
+            '8' => Some(Self::Eight),
+            '9' => Some(Self::Nine),
+            'T' => Some(Self::Ten),
+            'J' => Some(Self::Jack),
+            'Q' => Some(Self::Queen),
+            'K' => Some(Self::King),
+            'A' => Some(Self::Ace),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this number, such as `"Ace"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Two => "Two",
+            Self::Three => "Three",
+            Self::Four => "Four",
+            Self::Five => "Five",
+            Self::Six => "Six",
+            Self::Seven => "Seven",
+            Self::Eight => "Eight",
+            Self::Nine => "Nine",
+            Self::Ten => "Ten",
+            Self::Jack => "Jack",
+            Self::Queen => "Queen",
+            Self::King => "King",
+            Self::Ace => "Ace",
+        }
+    }
+
+    /// Returns the name of more than one card of this number, such as
+    /// `"Sixes"`.
+    #[must_use]
+    pub const fn plural_name(self) -> &'static str {
+        match self {
+            Self::Two => "Twos",
+            Self::Three => "Threes",
+            Self::Four => "Fours",
+            Self::Five => "Fives",
+            Self::Six => "Sixes",
+            Self::Seven => "Sevens",
+            Self::Eight => "Eights",
+            Self::Nine => "Nines",
+            Self::Ten => "Tens",
+            Self::Jack => "Jacks",
+            Self::Queen => "Queens",
+            Self::King => "Kings",
+            Self::Ace => "Aces",
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// A playing card. Cards are ordered by suit and then from two up to ace,
+/// which is the order of [`Card::ALL`] and of [`Card::to_index`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Card {
     value: u8,
 }
 
 impl Card {
+    /// The whole deck, ordered by suit and then from two up to ace within each
+    /// suit.
+    pub const ALL: [Self; 52] = {
+        let mut cards = [Self::new(Suit::Hearts, Number::Two); 52];
+        let mut index = 0;
+        while index < cards.len() {
+            cards[index] = Self::new(Suit::ALL[index / 13], Number::ALL[index % 13]);
+            index += 1;
+        }
+        cards
+    };
+
     #[must_use]
     pub const fn new(suit: Suit, number: Number) -> Self {
         let value = (suit as u8) << 4 | (number as u8);
@@ -111,6 +438,65 @@ impl Card {
     pub fn suit(self) -> Suit {
         unsafe { Suit::from_u8_unchecked(self.value >> 4) }
     }
+
+    /// Returns the character for the card's number in card notation, from
+    /// `'2'` up to `'A'`, with `'T'` for ten.
+    #[must_use]
+    pub fn rank_char(self) -> char {
+        self.number().as_char()
+    }
+
+    /// Returns the lowercase letter for the card's suit in card notation.
+    #[must_use]
+    pub fn suit_char(self) -> char {
+        self.suit().as_char()
+    }
+
+    /// Returns the card's position in [`Card::ALL`], from 0 up to 51, for
+    /// indexing lookup tables and bitsets by card.
+    #[must_use]
+    pub const fn to_index(self) -> u8 {
+        (self.value >> 4) * 13 + (self.value & 0xF) - Number::Two as u8
+    }
+
+    /// Returns the card at the given position in [`Card::ALL`], or `None` if
+    /// index is not in the interval [0, 51].
+    #[must_use]
+    pub const fn try_from_index(index: u8) -> Option<Self> {
+        if index < 52 {
+            Some(Self::ALL[index as usize])
+        } else {
+            None
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if index is not in the interval [0, 51]
+    #[must_use]
+    #[deprecated(note = "use `Card::try_from_index` or `Card::try_from`, which don't panic")]
+    pub const fn from_index(index: u8) -> Self {
+        match Self::try_from_index(index) {
+            Some(card) => card,
+            None => panic!("card indices must be in the interval [0, 51]"),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// This must be called with index in the range [0, 51]. Debug builds
+    /// panic if it isn't.
+    #[must_use]
+    pub unsafe fn from_index_unchecked(index: u8) -> Self {
+        debug_assert!(index < 52, "invalid card index: {index}");
+        *Self::ALL.get_unchecked(index as usize)
+    }
+}
+
+/// Iterates over the whole deck, in the same order as [`Card::ALL`].
+#[deprecated(note = "use `Card::ALL`")]
+pub fn all_cards() -> impl Iterator<Item = Card> {
+    Card::ALL.into_iter()
 }
 
 impl fmt::Debug for Card {
@@ -122,7 +508,95 @@ impl fmt::Debug for Card {
     }
 }
 
+impl fmt::Display for Card {
+    /// Writes the card in the standard notation, such as `"As"`, or with the
+    /// alternate flag, with a suit symbol instead, such as `"A♠"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suit = if f.alternate() {
+            self.suit().as_glyph()
+        } else {
+            self.suit_char()
+        };
+        write!(f, "{}{}", self.rank_char(), suit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Card {
+    /// Returns the card with a suit symbol, such as `"A♠"`. Tens are written
+    /// as `"T"`, just like in the standard notation, so every card is two
+    /// characters wide.
+    #[must_use]
+    pub fn to_unicode(self) -> String {
+        format!("{self:#}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<u8> for Suit {
+    type Error = PokerError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from_u8(value).ok_or(PokerError::InvalidSuit(value))
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<u8> for Number {
+    type Error = PokerError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from_u8(value).ok_or(PokerError::InvalidNumber(value))
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<u8> for Card {
+    type Error = PokerError;
+
+    /// Converts an index from [`Card::to_index`] back into its card.
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        Self::try_from_index(index).ok_or(PokerError::InvalidCardIndex(index))
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Card {
+    type Err = PokerError;
+
+    /// Parses a card in the standard two character notation, such as `"As"`
+    /// or `"Td"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(number), Some(suit), None) => {
+                match (Number::from_char(number), Suit::from_char(suit)) {
+                    (Some(number), Some(suit)) => Ok(Self::new(suit, number)),
+                    _ => Err(PokerError::InvalidCard(s.into())),
+                }
+            }
+            _ => Err(PokerError::InvalidCard(s.into())),
+        }
+    }
+}
+
+/// Parses a run of cards written back to back, such as `"AsKc"` or
+/// `"7d 8d 2c"`. Whitespace between cards is ignored.
+///
+/// # Errors
+///
+/// Will return [`PokerError::InvalidCard`] if any card is malformed.
+#[cfg(feature = "std")]
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, PokerError> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    chars
+        .chunks(2)
+        .map(|chunk| chunk.iter().collect::<String>().parse())
+        .collect()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum HandKind {
     StraightFlush = 8,
@@ -136,12 +610,73 @@ pub enum HandKind {
     HighCard = 0,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+impl HandKind {
+    /// Every kind of hand, from the weakest to the strongest.
+    pub const ALL: [Self; 9] = [
+        Self::HighCard,
+        Self::Pair,
+        Self::TwoPair,
+        Self::ThreeOfAKind,
+        Self::Straight,
+        Self::Flush,
+        Self::FullHouse,
+        Self::FourOfAKind,
+        Self::StraightFlush,
+    ];
+
+    /// Returns the name of this kind of hand, such as `"Four of a Kind"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::StraightFlush => "Straight Flush",
+            Self::FourOfAKind => "Four of a Kind",
+            Self::FullHouse => "Full House",
+            Self::Flush => "Flush",
+            Self::Straight => "Straight",
+            Self::ThreeOfAKind => "Three of a Kind",
+            Self::TwoPair => "Two Pair",
+            Self::Pair => "Pair",
+            Self::HighCard => "High Card",
+        }
+    }
+}
+
+impl fmt::Display for HandKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The strength of the best five cards in a hand. Stronger hands compare
+/// greater, and hands which split the pot compare equal.
+///
+/// Evaluations compare by kind, then by `values` from the first byte to the
+/// last. Each constructor lays its numbers out so that this byte order is
+/// also the order in which they break ties. Sets of numbers, such as the
+/// cards of a flush or the kickers of three of a kind, are bitsets of
+/// [`Number::as_bit`] split across two bytes, high byte first. Comparing two
+/// bitsets with the same number of bits set this way compares their highest
+/// differing number, which is exactly how kickers are compared.
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct HandEvaluation {
     kind: HandKind,
     values: [u8; 3],
 }
 
+impl Ord for HandEvaluation {
+    /// Compares the packed [`rank`](Self::rank)s, which orders evaluations the
+    /// same way as comparing their fields, with a single integer comparison.
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for HandEvaluation {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl HandEvaluation {
     #[must_use]
     pub const fn new_straight_flush(high_card: Number) -> Self {
@@ -167,6 +702,7 @@ impl HandEvaluation {
         }
     }
 
+    /// Creates a flush from a bitset of exactly its five numbers.
     #[must_use]
     pub const fn new_flush(cards: u16) -> Self {
         Self {
@@ -183,6 +719,7 @@ impl HandEvaluation {
         }
     }
 
+    /// Creates three of a kind from a bitset of exactly its two kickers.
     #[must_use]
     pub const fn new_three_of_a_kind(high_card: Number, kickers: u16) -> Self {
         Self {
@@ -203,6 +740,7 @@ impl HandEvaluation {
         }
     }
 
+    /// Creates a pair from a bitset of exactly its three kickers.
     #[must_use]
     pub const fn new_pair(high_card: Number, kickers: u16) -> Self {
         Self {
@@ -215,6 +753,7 @@ impl HandEvaluation {
         }
     }
 
+    /// Creates a high card hand from a bitset of exactly its five numbers.
     #[must_use]
     pub const fn new_high_card(cards: u16) -> Self {
         Self {
@@ -222,190 +761,691 @@ impl HandEvaluation {
             values: [(cards >> 8) as u8, (cards & 0xFF) as u8, 0],
         }
     }
-}
 
-#[must_use]
-fn check_for_straight(mut card_bitset: u16) -> Option<Number> {
-    // Duplicate the ace at the bottom of the bitset, if it is present.
-    if card_bitset & Number::Ace.as_bit() != 0 {
-        card_bitset |= 2;
-    }
-
-    // Check for straights by using 5 bit windows, and seeing if all bits
-    // in the mask are present.
-    let mask = 0b11111;
-    for shift_index in (1..11).rev() {
-        if (card_bitset & (mask << shift_index)) >> shift_index == mask {
-            unsafe {
-                return Some(Number::from_u8_unchecked(shift_index + 4));
+    #[must_use]
+    pub const fn kind(self) -> HandKind {
+        self.kind
+    }
+
+    /// Packs the evaluation into one number, which orders hands exactly as
+    /// comparing the evaluations does. This is handy for lookup tables and for
+    /// passing evaluations to other languages.
+    #[doc(alias = "as_rank")]
+    #[must_use]
+    pub const fn rank(self) -> u32 {
+        u32::from_be_bytes([
+            self.kind as u8,
+            self.values[0],
+            self.values[1],
+            self.values[2],
+        ])
+    }
+
+    /// Reverses [`rank`](Self::rank), returning `None` if the rank isn't one
+    /// which [`evaluate_hand`] can produce.
+    #[must_use]
+    pub fn from_rank(rank: u32) -> Option<Self> {
+        let [kind, first, second, third] = rank.to_be_bytes();
+        let number = Number::try_from_u8;
+        let straight = |high_card: u8| number(high_card).filter(|&number| number >= Number::Five);
+        let bitset = |high: u8, low: u8, len: u32| {
+            let bitset = u16::from_be_bytes([high, low]);
+            let numbers = Number::ALL
+                .iter()
+                .fold(0, |bits, number| bits | number.as_bit());
+            (bitset & !numbers == 0 && bitset.count_ones() == len).then_some(bitset)
+        };
+        // Five numbers in a row would make a straight instead.
+        let unconnected = |high: u8, low: u8| {
+            bitset(high, low, 5).filter(|&bits| straight_high_card(bits).is_none())
+        };
+
+        let evaluation = match kind {
+            8 if third == 0 && second == 0 => Self::new_straight_flush(straight(first)?),
+            7 if third == 0 && first != second => {
+                Self::new_four_of_a_kind(number(first)?, number(second)?)
+            }
+            6 if third == 0 && first != second => {
+                Self::new_full_house(number(first)?, number(second)?)
+            }
+            5 if third == 0 => Self::new_flush(unconnected(first, second)?),
+            4 if third == 0 && second == 0 => Self::new_straight(straight(first)?),
+            3 => {
+                let high_card = number(first)?;
+                let kickers = bitset(second, third, 2)?;
+                if kickers & high_card.as_bit() != 0 {
+                    return None;
+                }
+                Self::new_three_of_a_kind(high_card, kickers)
+            }
+            2 if first > second && first != third && second != third => {
+                Self::new_two_pair(number(first)?, number(second)?, number(third)?)
+            }
+            1 => {
+                let high_card = number(first)?;
+                let kickers = bitset(second, third, 3)?;
+                if kickers & high_card.as_bit() != 0 {
+                    return None;
+                }
+                Self::new_pair(high_card, kickers)
+            }
+            0 if third == 0 => Self::new_high_card(unconnected(first, second)?),
+            _ => return None,
+        };
+        Some(evaluation)
+    }
+
+    /// Writes the evaluation as four bytes: the kind, then the three values
+    /// which break ties. This is [`rank`](Self::rank) in big endian order, so
+    /// the bytes of two evaluations compare the same way the evaluations do.
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.rank().to_be_bytes()
+    }
+
+    /// Reads an evaluation written by [`to_bytes`](Self::to_bytes),
+    /// returning `None` if the bytes aren't an evaluation which
+    /// [`evaluate_hand`] can produce.
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 4]) -> Option<Self> {
+        Self::from_rank(u32::from_be_bytes(bytes))
+    }
+
+    /// Splits the evaluation into the numbers which make the hand and the
+    /// kickers which break ties between hands of the same kind.
+    ///
+    /// Evaluations built from a bitset which breaks its constructor's
+    /// contract, such as an empty flush, split into unspecified numbers.
+    #[must_use]
+    pub fn parts(self) -> HandParts {
+        let [first, second, third] = self.values;
+        let number = |value: u8| Number::try_from_u8(value).unwrap_or(Number::Two);
+        let bitset = u16::from_be_bytes([first, second]);
+        let (primary, secondary, kickers) = match self.kind {
+            HandKind::StraightFlush | HandKind::Straight => (number(first), None, 0),
+            HandKind::FourOfAKind => (number(first), None, number(second).as_bit()),
+            HandKind::FullHouse => (number(first), Some(number(second)), 0),
+            HandKind::Flush | HandKind::HighCard => {
+                let high_card = highest_rank(bitset).unwrap_or(Number::Two);
+                (high_card, None, bitset & !high_card.as_bit())
+            }
+            HandKind::ThreeOfAKind | HandKind::Pair => {
+                (number(first), None, u16::from_be_bytes([second, third]))
             }
+            HandKind::TwoPair => (number(first), Some(number(second)), number(third).as_bit()),
+        };
+        HandParts {
+            kind: self.kind,
+            primary,
+            secondary,
+            kickers,
         }
     }
-    None
+
+    /// Returns the number the hand is named after: the top of a straight,
+    /// the highest card of a flush or of no pair, or the number of the quads,
+    /// the trips or the highest pair.
+    #[must_use]
+    pub fn primary(self) -> Number {
+        self.parts().primary
+    }
+
+    /// Iterates over the kickers, from the highest down. Straights, flushes
+    /// and full houses use all five cards, so their only kickers are the
+    /// lower cards of a flush or of a hand with no pair.
+    pub fn kickers(self) -> impl Iterator<Item = Number> {
+        let kickers = self.parts().kickers;
+        Number::iter_desc().filter(move |number| kickers & number.as_bit() != 0)
+    }
 }
 
-#[must_use]
-fn check_for_three_of_a_kind(count_by_number: &[i32; 15]) -> Option<Number> {
-    for number in (Number::Two as u8..=Number::Ace as u8).rev() {
-        if count_by_number[number as usize] == 3 {
-            unsafe { return Some(Number::from_u8_unchecked(number)) }
+/// The pieces of a [`HandEvaluation`], as returned by
+/// [`HandEvaluation::parts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HandParts {
+    pub kind: HandKind,
+    /// The number the hand is named after, as [`HandEvaluation::primary`].
+    pub primary: Number,
+    /// The pair of a full house, or the lower pair of two pair.
+    pub secondary: Option<Number>,
+    /// The kickers, as a bitset of [`Number::as_bit`].
+    pub kickers: u16,
+}
+
+impl fmt::Display for HandEvaluation {
+    /// Describes the hand in plain English for showing to players, such as
+    /// `"Full House, Kings full of Tens"` or `"Pair of Aces, King kicker"`.
+    ///
+    /// Only the highest kicker is named, so hands which differ only in their
+    /// lower kickers read the same. The alternate flag, as in `{:#}`, names
+    /// every kicker instead, such as `"Pair of Aces, King, Four and Two
+    /// kickers"`, so that each distinct evaluation has a distinct name.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = self.parts();
+        let primary = parts.primary;
+        let secondary = parts.secondary.unwrap_or(Number::Two);
+
+        match parts.kind {
+            HandKind::StraightFlush if primary == Number::Ace => return f.write_str("Royal Flush"),
+            HandKind::StraightFlush | HandKind::Straight | HandKind::Flush => {
+                write!(f, "{}, {} high", parts.kind, primary.name())?;
+            }
+            HandKind::HighCard => write!(f, "High Card, {}", primary.name())?,
+            HandKind::FourOfAKind | HandKind::ThreeOfAKind => {
+                write!(f, "{}, {}", parts.kind, primary.plural_name())?;
+            }
+            HandKind::FullHouse => write!(
+                f,
+                "Full House, {} full of {}",
+                primary.plural_name(),
+                secondary.plural_name()
+            )?,
+            HandKind::TwoPair => write!(
+                f,
+                "Two Pair, {} and {}",
+                primary.plural_name(),
+                secondary.plural_name()
+            )?,
+            HandKind::Pair => write!(f, "Pair of {}", primary.plural_name())?,
         }
+
+        // A flush is already named by its highest card, so its kickers are
+        // only spelled out in full.
+        let mut kickers = self.kickers().peekable();
+        let Some(first) = kickers.next() else {
+            return Ok(());
+        };
+        if !f.alternate() {
+            if parts.kind == HandKind::Flush {
+                return Ok(());
+            }
+            return write!(f, ", {} kicker", first.name());
+        }
+        write!(f, ", {}", first.name())?;
+        if kickers.peek().is_none() {
+            return f.write_str(" kicker");
+        }
+        while let Some(kicker) = kickers.next() {
+            let separator = if kickers.peek().is_some() {
+                ", "
+            } else {
+                " and "
+            };
+            write!(f, "{separator}{}", kicker.name())?;
+        }
+        f.write_str(" kickers")
     }
-    None
 }
 
-#[must_use]
-fn check_for_pair(count_by_number: &[i32; 15]) -> Option<Number> {
-    for number in (Number::Two as u8..=Number::Ace as u8).rev() {
-        if count_by_number[number as usize] == 2 {
-            unsafe { return Some(Number::from_u8_unchecked(number)) }
-        }
+#[cfg(feature = "std")]
+impl HandEvaluation {
+    /// Describes the hand in plain English, exactly as
+    /// [`Display`](fmt::Display) does.
+    #[must_use]
+    pub fn describe(self) -> String {
+        self.to_string()
     }
-    None
 }
 
+/// Returns the five numbers making the highest straight in a bitset of
+/// numbers, from the top of the straight down.
+///
+/// In the wheel, the ace plays as the lowest card, so it comes last:
+/// `[Five, Four, Three, Two, Ace]`.
 #[must_use]
-fn highest_card_in_set(cards: u16) -> Number {
-    #[allow(clippy::cast_possible_truncation)]
-    unsafe {
-        Number::from_u8_unchecked((15 - cards.leading_zeros()) as u8)
-    }
+pub fn straight_cards(card_bitset: u16) -> Option<[Number; 5]> {
+    let high_card = straight_high_card(card_bitset)? as u8;
+    // Only the wheel goes below two, where the ace plays.
+    Some(
+        [0, 1, 2, 3, 4]
+            .map(|offset| Number::try_from_u8(high_card - offset).unwrap_or(Number::Ace)),
+    )
 }
 
 #[must_use]
-pub fn evaluate_hand(cards: [Card; 7]) -> HandEvaluation {
-    let mut count_by_suit = [0, 0, 0, 0];
-    let mut count_by_number = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-    let mut number_bitset: u16 = 0;
-    let mut number_by_suit_bitset: [u16; 4] = [0, 0, 0, 0];
+fn check_for_three_of_a_kind(count_by_number: &[u8; 15]) -> Option<Number> {
+    Number::iter_desc().find(|&number| count_by_number[number as usize] == 3)
+}
 
-    for card in cards {
-        let (suit, number) = (card.suit(), card.number());
-        count_by_suit[suit as usize] += 1;
-        count_by_number[number as usize] += 1;
-        number_bitset |= card.number().as_bit();
-        number_by_suit_bitset[suit as usize] |= card.number().as_bit();
-    }
+#[must_use]
+fn check_for_pair(count_by_number: &[u8; 15]) -> Option<Number> {
+    Number::iter_desc().find(|&number| count_by_number[number as usize] == 2)
+}
+
+/// How many cards of each suit and number a hand has, which is everything
+/// the evaluators need to know about it.
+#[derive(Clone, Copy)]
+struct HandCounts {
+    count_by_suit: [u8; 4],
+    count_by_number: [u8; 15],
+    number_bitset: u16,
+    number_by_suit_bitset: [u16; 4],
+}
 
-    // Check for straight flushes.
-    for suit_bitset in number_by_suit_bitset {
-        if let Some(high_card) = check_for_straight(suit_bitset) {
-            return HandEvaluation::new_straight_flush(high_card);
+impl HandCounts {
+    fn new(cards: &[Card]) -> Self {
+        Self {
+            count_by_suit: suit_histogram(cards),
+            count_by_number: rank_histogram(cards),
+            number_bitset: rank_bits_of(cards),
+            number_by_suit_bitset: rank_bits_by_suit(cards),
         }
     }
 
-    // Check for four of a kind.
-    for number in (Number::Two as u8..=Number::Ace as u8).rev() {
-        if count_by_number[number as usize] == 4 {
-            let high_card = unsafe { Number::from_u8_unchecked(number) };
-            let kicker = highest_card_in_set(number_bitset & !high_card.as_bit());
-            return HandEvaluation::new_four_of_a_kind(high_card, kicker);
+    /// Returns the counts with one more card, which is cheaper than counting
+    /// every card again when many hands share most of their cards.
+    #[cfg(feature = "std")]
+    fn with(mut self, card: Card) -> Self {
+        self.add(card);
+        self
+    }
+
+    fn add(&mut self, card: Card) {
+        let (suit, number) = (card.suit() as usize, card.number());
+        self.count_by_suit[suit] += 1;
+        self.count_by_number[number as usize] += 1;
+        self.number_bitset |= number.as_bit();
+        self.number_by_suit_bitset[suit] |= number.as_bit();
+    }
+
+    /// Takes away a card which was counted. Each card is only ever counted
+    /// once, so its number goes from its suit entirely.
+    fn remove(&mut self, card: Card) {
+        let (suit, number) = (card.suit() as usize, card.number());
+        self.count_by_suit[suit] -= 1;
+        self.count_by_number[number as usize] -= 1;
+        if self.count_by_number[number as usize] == 0 {
+            self.number_bitset &= !number.as_bit();
         }
+        self.number_by_suit_bitset[suit] &= !number.as_bit();
     }
 
-    // Check for full house.
-    let three_of_a_kind = check_for_three_of_a_kind(&count_by_number);
-    if let Some(three_of_a_kind_number) = three_of_a_kind {
-        for number in (Number::Two as u8..=Number::Ace as u8).rev() {
-            if number != three_of_a_kind_number as u8 && count_by_number[number as usize] >= 2 {
-                unsafe {
-                    return HandEvaluation::new_full_house(
-                        three_of_a_kind_number,
-                        Number::from_u8_unchecked(number),
-                    );
-                }
+    fn best_high(&self) -> HandEvaluation {
+        let count_by_number = &self.count_by_number;
+        let number_bitset = self.number_bitset;
+
+        // Check for straight flushes.
+        for suit_bitset in self.number_by_suit_bitset {
+            if let Some(high_card) = straight_high_card(suit_bitset) {
+                return HandEvaluation::new_straight_flush(high_card);
             }
         }
-    }
 
-    // Check for flush.
-    for suit in 0..4 {
-        if count_by_suit[suit] >= 5 {
-            let mut suited_cards = number_by_suit_bitset[suit];
-            while count_by_suit[suit] > 5 {
-                suited_cards &= suited_cards - 1;
-                count_by_suit[suit] -= 1;
+        // Check for four of a kind.
+        if let Some(high_card) =
+            Number::iter_desc().find(|&number| count_by_number[number as usize] == 4)
+        {
+            // Evaluations always have at least five cards, so one of them
+            // is left over as the kicker.
+            let kicker = highest_rank(number_bitset & !high_card.as_bit())
+                .expect("four of a kind needs a fifth card as a kicker");
+            return HandEvaluation::new_four_of_a_kind(high_card, kicker);
+        }
+
+        // Check for full house.
+        let three_of_a_kind = check_for_three_of_a_kind(count_by_number);
+        if let Some(three_of_a_kind_number) = three_of_a_kind {
+            if let Some(low_card) = Number::iter_desc().find(|&number| {
+                number != three_of_a_kind_number && count_by_number[number as usize] >= 2
+            }) {
+                return HandEvaluation::new_full_house(three_of_a_kind_number, low_card);
             }
-            return HandEvaluation::new_flush(suited_cards);
         }
-    }
 
-    // Check for straight.
-    if let Some(high_card) = check_for_straight(number_bitset) {
-        return HandEvaluation::new_straight(high_card);
-    }
+        // Check for flush.
+        for (&count, &suited_cards) in self.count_by_suit.iter().zip(&self.number_by_suit_bitset) {
+            if count >= 5 {
+                return HandEvaluation::new_flush(top_n_ranks(suited_cards, 5));
+            }
+        }
 
-    // Check for three of a kind.
-    if let Some(high_card) = three_of_a_kind {
-        let mut kickers = number_bitset;
-        kickers &= !high_card.as_bit();
-        kickers &= kickers - 1;
-        kickers &= kickers - 1;
-        return HandEvaluation::new_three_of_a_kind(high_card, kickers);
-    }
+        // Check for straight.
+        if let Some(high_card) = straight_high_card(number_bitset) {
+            return HandEvaluation::new_straight(high_card);
+        }
 
-    // Check for two pair and pair.
-    if let Some(high_card) = check_for_pair(&count_by_number) {
-        for number in (Number::Two as u8..high_card as u8).rev() {
-            if count_by_number[number as usize] == 2 {
-                let low_card = unsafe { Number::from_u8_unchecked(number) };
+        // Check for three of a kind.
+        if let Some(high_card) = three_of_a_kind {
+            let kickers = top_n_ranks(number_bitset & !high_card.as_bit(), 2);
+            return HandEvaluation::new_three_of_a_kind(high_card, kickers);
+        }
 
+        // Check for two pair and pair.
+        if let Some(high_card) = check_for_pair(count_by_number) {
+            if let Some(low_card) = Number::iter_desc()
+                .skip_while(|&number| number >= high_card)
+                .find(|&number| count_by_number[number as usize] == 2)
+            {
                 let mut bitset = number_bitset;
                 bitset &= !high_card.as_bit();
                 bitset &= !low_card.as_bit();
 
-                let kicker = highest_card_in_set(bitset);
+                let kicker = highest_rank(bitset).expect("two pair needs a fifth card as a kicker");
                 return HandEvaluation::new_two_pair(high_card, low_card, kicker);
             }
+
+            // There is only a single pair, so keep the three highest other
+            // cards as kickers.
+            let kickers = top_n_ranks(number_bitset & !high_card.as_bit(), 3);
+            return HandEvaluation::new_pair(high_card, kickers);
         }
 
-        // There is only a single pair.
-        // So, remove the bottom 2 cards from the hand and return.
-        let mut kickers = number_bitset;
-        kickers &= !high_card.as_bit();
-        kickers &= kickers - 1;
-        kickers &= kickers - 1;
-        return HandEvaluation::new_pair(high_card, kickers);
+        // At this point, the only thing left is a high card hand.
+        HandEvaluation::new_high_card(top_n_ranks(number_bitset, 5))
     }
 
-    // At this point, the only thing left is a high card hand.
-    // So, remove the bottom 2 cards from the hand and return.
-    let mut five_highest_cards = number_bitset;
-    five_highest_cards &= five_highest_cards - 1;
-    five_highest_cards &= five_highest_cards - 1;
-    HandEvaluation::new_high_card(five_highest_cards)
-}
+    /// Returns the best hand of one kind which the cards make, if they make
+    /// one at all, with `straight` deciding which numbers make a straight.
+    ///
+    /// Unlike [`best_high`](Self::best_high), a kind is found even when the
+    /// cards also make a stronger kind, so that the kinds can be tried in
+    /// whichever order a game ranks them.
+    fn best_of_kind(
+        &self,
+        kind: HandKind,
+        straight: fn(u16) -> Option<Number>,
+    ) -> Option<HandEvaluation> {
+        let count_by_number = &self.count_by_number;
+        let number_bitset = self.number_bitset;
+        let with_count = |count: u8, except: Option<Number>| {
+            Number::iter_desc()
+                .find(|&number| Some(number) != except && count_by_number[number as usize] >= count)
+        };
+
+        match kind {
+            HandKind::StraightFlush => self
+                .number_by_suit_bitset
+                .iter()
+                .find_map(|&suit_bitset| straight(suit_bitset))
+                .map(HandEvaluation::new_straight_flush),
+            HandKind::FourOfAKind => {
+                let high_card = with_count(4, None)?;
+                let kicker = highest_rank(number_bitset & !high_card.as_bit())?;
+                Some(HandEvaluation::new_four_of_a_kind(high_card, kicker))
+            }
+            HandKind::FullHouse => {
+                let high_card = with_count(3, None)?;
+                let low_card = with_count(2, Some(high_card))?;
+                Some(HandEvaluation::new_full_house(high_card, low_card))
+            }
+            HandKind::Flush => self
+                .count_by_suit
+                .iter()
+                .zip(&self.number_by_suit_bitset)
+                .find(|&(&count, _)| count >= 5)
+                .map(|(_, &suited_cards)| HandEvaluation::new_flush(top_n_ranks(suited_cards, 5))),
+            HandKind::Straight => straight(number_bitset).map(HandEvaluation::new_straight),
+            HandKind::ThreeOfAKind => {
+                let high_card = with_count(3, None)?;
+                let kickers = top_n_ranks(number_bitset & !high_card.as_bit(), 2);
+                Some(HandEvaluation::new_three_of_a_kind(high_card, kickers))
+            }
+            HandKind::TwoPair => {
+                let high_card = with_count(2, None)?;
+                let low_card = with_count(2, Some(high_card))?;
+                let kicker =
+                    highest_rank(number_bitset & !high_card.as_bit() & !low_card.as_bit())?;
+                Some(HandEvaluation::new_two_pair(high_card, low_card, kicker))
+            }
+            HandKind::Pair => {
+                let high_card = with_count(2, None)?;
+                let kickers = top_n_ranks(number_bitset & !high_card.as_bit(), 3);
+                Some(HandEvaluation::new_pair(high_card, kickers))
+            }
+            HandKind::HighCard => {
+                Some(HandEvaluation::new_high_card(top_n_ranks(number_bitset, 5)))
+            }
+        }
+    }
+
+    /// Returns the best ace-to-five low which qualifies with five different
+    /// numbers of eight or lower, counting aces as ones.
+    const fn best_low(&self) -> Option<HandEvaluation> {
+        let eight_or_lower = (Number::Nine.as_bit() - 1) & !(Number::Two.as_bit() - 1);
+        let mut low_cards = self.number_bitset & eight_or_lower;
+        if self.number_bitset & Number::Ace.as_bit() != 0 {
+            // Aces play below the twos, where the bit below them is free.
+            low_cards |= Number::Two.as_bit() >> 1;
+        }
+        if low_cards.count_ones() < 5 {
+            return None;
+        }
 
-pub struct ComputeResult {
-    pub win_count: u64,
-    pub loss_count: u64,
-    pub tie_count: u64,
-    pub count: u64,
+        // Keep the five lowest numbers, by dropping the highest.
+        while low_cards.count_ones() > 5 {
+            low_cards &= !(1 << low_cards.ilog2());
+        }
+        Some(HandEvaluation::new_high_card(low_cards))
+    }
 }
 
 #[must_use]
-pub fn compute_result(hand1: [Card; 2], hand2: [Card; 2]) -> ComputeResult {
-    let mut deck = vec![];
-    for suit in 0..4 {
-        for number in Number::Two as u8..=Number::Ace as u8 {
-            let card = Card::new(Suit::from_u8(suit), Number::from_u8(number));
-            if card != hand1[0] && card != hand1[1] && card != hand2[0] && card != hand2[1] {
-                deck.push(card);
-            }
+pub fn evaluate_hand(cards: [Card; 7]) -> HandEvaluation {
+    HandCounts::new(&cards).best_high()
+}
+
+/// The counts of suits and numbers behind an evaluation, which cards can be
+/// added to and taken away from one at a time.
+///
+/// Building the state from the hole cards and the flop once, then adding and
+/// removing each turn and river, evaluates every runout without counting all
+/// seven cards again for each of them, as [`evaluate_hand`] does.
+#[derive(Clone, Copy)]
+pub struct EvalState {
+    counts: HandCounts,
+}
+
+impl EvalState {
+    /// Counts the given cards, which must all be different.
+    #[must_use]
+    pub fn new(cards: &[Card]) -> Self {
+        Self {
+            counts: HandCounts::new(cards),
         }
     }
 
+    /// Adds a card which isn't already counted.
+    pub fn add_card(&mut self, card: Card) {
+        self.counts.add(card);
+    }
+
+    /// Takes away a card which is counted, such as the river after
+    /// evaluating it, to make room for the next one.
+    pub fn remove_card(&mut self, card: Card) {
+        self.counts.remove(card);
+    }
+
+    /// Returns the number of cards counted.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.counts
+            .count_by_suit
+            .iter()
+            .map(|&count| count as usize)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evaluates the best five of the cards counted, exactly as
+    /// [`evaluate_hand`] evaluates the same cards, or returns `None` unless
+    /// five to seven cards are counted.
+    #[must_use]
+    pub fn evaluate(&self) -> Option<HandEvaluation> {
+        (5..=7)
+            .contains(&self.len())
+            .then(|| self.counts.best_high())
+    }
+}
+
+/// Evaluates a seven card hand as [`evaluate_hand`] does, and also returns the
+/// two cards which don't play, so that the other five are the best hand.
+///
+/// When several choices of five cards make the same hand, such as when the
+/// board plays, the lowest cards are the ones discarded.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn evaluate_hand_with_discards(cards: [Card; 7]) -> (HandEvaluation, Vec<Card>) {
+    let (evaluation, sorted, discards) = split_best_five(cards);
+    (evaluation, discards.map(|index| sorted[index]).to_vec())
+}
+
+/// Evaluates a seven card hand as [`evaluate_hand`] does, and also returns the
+/// five cards which make the best hand, from the highest number down, for
+/// showing which cards won.
+///
+/// When several choices of five cards make the same hand, such as when the
+/// board plays, the highest cards are the ones kept.
+#[must_use]
+pub fn evaluate_hand_detailed(cards: [Card; 7]) -> (HandEvaluation, [Card; 5]) {
+    let (evaluation, sorted, discards) = split_best_five(cards);
+    (evaluation, five_without(sorted, discards))
+}
+
+/// Evaluates a seven card hand, and finds the positions of the two cards
+/// which don't play among the cards sorted from the highest number down.
+fn split_best_five(cards: [Card; 7]) -> (HandEvaluation, [Card; 7], [usize; 2]) {
+    let evaluation = evaluate_hand(cards);
+    let mut sorted = cards;
+    sorted.sort_unstable_by_key(|card| core::cmp::Reverse((card.number(), card.suit() as u8)));
+
+    // Try the pairs of lowest cards first. The best hand is always made by
+    // some five of the cards, so one of the pairs is always found.
+    let discards = (0..7)
+        .rev()
+        .flat_map(|second| (0..second).rev().map(move |first| [first, second]))
+        .find(|&discards| {
+            HandCounts::new(&five_without(sorted, discards)).best_high() == evaluation
+        })
+        .unwrap_or([5, 6]);
+    (evaluation, sorted, discards)
+}
+
+/// Returns the five of seven cards which aren't at the two given positions,
+/// keeping their order.
+fn five_without(cards: [Card; 7], discards: [usize; 2]) -> [Card; 5] {
+    let mut five = [cards[0]; 5];
+    let kept = (0..7).filter(|index| !discards.contains(index));
+    for (slot, index) in five.iter_mut().zip(kept) {
+        *slot = cards[index];
+    }
+    five
+}
+
+/// Evaluates the five community cards on their own, which is the hand every
+/// player has when they play the board.
+///
+/// A player whose hand evaluates the same as this has no hole card which
+/// plays, and so at best chops with everyone else still in the hand.
+#[must_use]
+pub fn board_best(board: [Card; 5]) -> HandEvaluation {
+    HandCounts::new(&board).best_high()
+}
+
+/// Evaluates the best five cards out of any number of cards from five to
+/// seven, such as two hole cards and a flop or a turn, ranking them exactly as
+/// [`evaluate_hand`] ranks seven cards.
+///
+/// Returns `None` for fewer than five or more than seven cards.
+#[must_use]
+pub fn evaluate_cards(cards: &[Card]) -> Option<HandEvaluation> {
+    (5..=7)
+        .contains(&cards.len())
+        .then(|| HandCounts::new(cards).best_high())
+}
+
+/// Evaluates the best high hand and the best ace-to-five low of the same
+/// cards at once, for hi-lo games.
+///
+/// Any number of cards from five to seven may be passed, and `None` is
+/// returned for any other number.
+///
+/// The low only qualifies with five different numbers of eight or lower,
+/// with aces counting as ones. Straights and flushes don't count against a
+/// low. Lows compare the opposite way to high hands, so the best low of
+/// several is the one which compares least.
+///
+/// Omaha hands must use exactly two hole cards, so each combination of two
+/// hole cards and three board cards should be evaluated separately.
+#[must_use]
+pub fn evaluate_hi_lo(cards: &[Card]) -> Option<(HandEvaluation, Option<HandEvaluation>)> {
+    (5..=7).contains(&cards.len()).then(|| {
+        let counts = HandCounts::new(cards);
+        (counts.best_high(), counts.best_low())
+    })
+}
+
+/// Puts two hole cards together with a complete board, ready for
+/// [`evaluate_hand`]. The hole cards come first.
+#[must_use]
+pub const fn combine(hole: [Card; 2], board: [Card; 5]) -> [Card; 7] {
+    let [h1, h2] = hole;
+    let [b1, b2, b3, b4, b5] = board;
+    [h1, h2, b1, b2, b3, b4, b5]
+}
+
+/// Puts four Omaha hole cards together with a complete board, so that
+/// `cards[..4]` are the hole cards and `cards[4..]` are the board.
+///
+/// An Omaha hand must use exactly two hole cards and three board cards, so
+/// these can't be passed to [`evaluate_hand`] directly.
+#[must_use]
+pub const fn combine_omaha(hole: [Card; 4], board: [Card; 5]) -> [Card; 9] {
+    let [h1, h2, h3, h4] = hole;
+    let [b1, b2, b3, b4, b5] = board;
+    [h1, h2, h3, h4, b1, b2, b3, b4, b5]
+}
+
+/// Compares two hands by strength, so that `hands.sort_by(compare_hands)`
+/// orders them from weakest to strongest.
+#[must_use]
+pub fn compare_hands(a: &[Card; 7], b: &[Card; 7]) -> core::cmp::Ordering {
+    evaluate_hand(*a).cmp(&evaluate_hand(*b))
+}
+
+/// Evaluates every seven card hand which can be dealt from `deck`.
+#[cfg(feature = "std")]
+fn seven_card_evaluations(deck: Vec<Card>) -> impl Iterator<Item = HandEvaluation> {
+    deck.into_iter()
+        .combinations(7)
+        .map(|cards| evaluate_hand(cards.try_into().expect("every combination has seven cards")))
+}
+
+/// Evaluates every one of the C(52, 7) = 133,784,560 seven card hands, which is
+/// what building or cross-checking a lookup table needs.
+#[cfg(feature = "std")]
+pub fn all_seven_card_evaluations() -> impl Iterator<Item = HandEvaluation> {
+    seven_card_evaluations(Card::ALL.to_vec())
+}
+
+/// The results of [`compute_result`], which are the same counts that
+/// [`compute_equity`] gives for each hand.
+#[cfg(feature = "std")]
+pub type ComputeResult = EquityResult;
+
+/// Counts how often `hand1` wins, loses and ties against `hand2` over every
+/// board.
+///
+/// The hands aren't checked, so hands which share a card give meaningless
+/// counts. This is the fast path for callers which already know the hands
+/// are valid; otherwise, use [`compute_result_checked`].
+#[cfg(feature = "std")]
+#[must_use]
+pub fn compute_result(hand1: [Card; 2], hand2: [Card; 2]) -> ComputeResult {
+    let deck = Deck::new().without(&hand1).without(&hand2);
+
     let mut tie_count = 0;
     let mut win_count = 0;
     let mut loss_count = 0;
     let mut count = 0;
 
-    for (c1, c2, c3, c4, c5) in deck.into_iter().tuple_combinations() {
-        let hand_a = [c1, c2, c3, c4, c5, hand1[0], hand1[1]];
-        let hand_b = [c1, c2, c3, c4, c5, hand2[0], hand2[1]];
-
-        let a_result = evaluate_hand(hand_a);
-        let b_result = evaluate_hand(hand_b);
+    for board in deck.into_iter().tuple_combinations::<(_, _, _, _, _)>() {
+        let board = board.into();
+        let a_result = evaluate_hand(combine(hand1, board));
+        let b_result = evaluate_hand(combine(hand2, board));
         match a_result.cmp(&b_result) {
             std::cmp::Ordering::Equal => tie_count += 1,
             std::cmp::Ordering::Greater => win_count += 1,
@@ -422,7 +1462,22 @@ pub fn compute_result(hand1: [Card; 2], hand2: [Card; 2]) -> ComputeResult {
     }
 }
 
-#[cfg(test)]
+/// Computes the same counts as [`compute_result`], after checking that the
+/// hands are made of four different cards.
+///
+/// # Errors
+///
+/// Will return [`PokerError::DuplicateCard`] if a card appears twice.
+#[cfg(feature = "std")]
+pub fn compute_result_checked(
+    hand1: [Card; 2],
+    hand2: [Card; 2],
+) -> Result<ComputeResult, PokerError> {
+    Hand::try_from_iter::<4>(hand1.into_iter().chain(hand2))?;
+    Ok(compute_result(hand1, hand2))
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -434,6 +1489,474 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_card_chars() {
+        let ten = Card::new(Suit::Spades, Number::Ten);
+        assert_eq!((ten.rank_char(), ten.suit_char()), ('T', 's'));
+        let ace = Card::new(Suit::Hearts, Number::Ace);
+        assert_eq!((ace.rank_char(), ace.suit_char()), ('A', 'h'));
+        let two = Card::new(Suit::Clubs, Number::Two);
+        assert_eq!((two.rank_char(), two.suit_char()), ('2', 'c'));
+
+        // The characters are the card's notation.
+        for card in Card::ALL {
+            assert_eq!(
+                card.to_string(),
+                format!("{}{}", card.rank_char(), card.suit_char())
+            );
+        }
+    }
+
+    #[test]
+    fn test_card_index() {
+        for (index, card) in (0..).zip(Card::ALL) {
+            assert_eq!(card.to_index(), index);
+            assert_eq!(Card::try_from_index(index), Some(card));
+            assert_eq!(Card::try_from(index), Ok(card));
+            assert_eq!(unsafe { Card::from_index_unchecked(index) }, card);
+        }
+        assert_eq!(Card::new(Suit::Hearts, Number::Two).to_index(), 0);
+        assert_eq!(Card::new(Suit::Spades, Number::Ace).to_index(), 51);
+
+        // Cards sort in the same order as their indices.
+        let mut cards = Card::ALL;
+        cards.reverse();
+        cards.sort_unstable();
+        assert_eq!(cards, Card::ALL);
+        assert!(Card::new(Suit::Hearts, Number::Ace) < Card::new(Suit::Diamonds, Number::Two));
+        let set: std::collections::BTreeSet<Card> =
+            parse_cards("AsKdAs2h").unwrap().into_iter().collect();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            parse_cards("2hKdAs").unwrap()
+        );
+
+        assert_eq!(Card::try_from_index(52), None);
+        assert_eq!(Card::try_from(52), Err(PokerError::InvalidCardIndex(52)));
+    }
+
+    #[test]
+    #[should_panic(expected = "card indices must be in the interval [0, 51]")]
+    #[allow(deprecated)]
+    fn test_card_index_out_of_range() {
+        let _ = Card::from_index(52);
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            "As".parse::<Card>(),
+            Ok(Card::new(Suit::Spades, Number::Ace))
+        );
+        // Either case is accepted for both characters.
+        assert_eq!("tD".parse::<Card>(), "Td".parse::<Card>());
+        for card in Card::ALL {
+            assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+        }
+
+        for invalid in ["", "A", "Asx", "1s", "10s", "Ax", "A♠"] {
+            assert_eq!(
+                invalid.parse::<Card>(),
+                Err(PokerError::InvalidCard(invalid.into())),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unicode_cards() {
+        let glyphs = [
+            (Suit::Hearts, "A♥"),
+            (Suit::Diamonds, "A♦"),
+            (Suit::Clubs, "A♣"),
+            (Suit::Spades, "A♠"),
+        ];
+        for (suit, expected) in glyphs {
+            let card = Card::new(suit, Number::Ace);
+            assert_eq!(card.to_unicode(), expected);
+            assert_eq!(format!("{card:#}"), expected);
+        }
+        assert_eq!(Card::new(Suit::Spades, Number::Ten).to_unicode(), "T♠");
+        assert_eq!(Card::new(Suit::Spades, Number::Ten).to_string(), "Ts");
+    }
+
+    #[test]
+    fn test_evaluate_hand_with_discards() {
+        let seven = |cards: &str| -> [Card; 7] { parse_cards(cards).unwrap().try_into().unwrap() };
+
+        let cards = seven("AhKhQhJh9h2c3d");
+        let (evaluation, discards) = evaluate_hand_with_discards(cards);
+        assert!(evaluation == evaluate_hand(cards));
+        assert_eq!(evaluation.kind(), HandKind::Flush);
+        assert_eq!(discards, parse_cards("3d2c").unwrap());
+
+        // The discards and the five cards which play make up the whole hand.
+        let cards = seven("AsAdKcKh7s7d2c");
+        let (evaluation, discards) = evaluate_hand_with_discards(cards);
+        assert_eq!(discards.len(), 2);
+        let five: Vec<Card> = cards
+            .into_iter()
+            .filter(|card| !discards.contains(card))
+            .collect();
+        assert_eq!(five.len(), 5);
+        assert!(evaluate_cards(&five) == Some(evaluation));
+        // The sevens are the lowest pair, but the two is too low to be the kicker.
+        assert!(discards.contains(&parse_cards("2c").unwrap()[0]));
+
+        // A wheel keeps the ace and drops the higher cards which don't connect.
+        let (evaluation, discards) = evaluate_hand_with_discards(seven("Ah2c3d4s5hKcQd"));
+        assert!(evaluation == HandEvaluation::new_straight(Number::Five));
+        assert_eq!(discards, parse_cards("KcQd").unwrap());
+    }
+
+    #[test]
+    fn test_compute_result_checked() {
+        let hand = |cards: &str| -> [Card; 2] { parse_cards(cards).unwrap().try_into().unwrap() };
+        let ace = Card::new(Suit::Spades, Number::Ace);
+        assert_eq!(
+            compute_result_checked(hand("AsKs"), hand("QdAs")),
+            Err(PokerError::DuplicateCard(ace))
+        );
+        assert_eq!(
+            compute_result_checked(hand("AsAs"), hand("QdQc")),
+            Err(PokerError::DuplicateCard(ace))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_fewer_cards() {
+        // Flop and turn hands rank the same as when the cards they lack
+        // don't improve them.
+        let flop = parse_cards("AsKd Ah7c2s").unwrap();
+        let turn = parse_cards("AsKd Ah7c2s 9h").unwrap();
+        let river: [Card; 7] = parse_cards("AsKd Ah7c2s 9h 3d")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let pair = evaluate_hand(river);
+        assert_eq!(pair.kind(), HandKind::Pair);
+        assert!(evaluate_cards(&turn) == Some(pair));
+        assert!(evaluate_cards(&flop) < Some(pair));
+        assert_eq!(
+            evaluate_cards(&flop).unwrap().to_string(),
+            "Pair of Aces, King kicker"
+        );
+
+        let straight = parse_cards("5h4c3d2sAh").unwrap();
+        assert!(evaluate_cards(&straight) == Some(HandEvaluation::new_straight(Number::Five)));
+
+        // Four cards aren't a hand yet, and no game deals anyone eight.
+        assert!(evaluate_cards(&flop[..4]).is_none());
+        assert!(evaluate_cards(&[&river[..], &straight[..1]].concat()).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_hand_detailed() {
+        let seven = |cards: &str| -> [Card; 7] { parse_cards(cards).unwrap().try_into().unwrap() };
+
+        let (evaluation, five) = evaluate_hand_detailed(seven("9h2c8hKd7h6h5h"));
+        assert!(evaluation == HandEvaluation::new_straight_flush(Number::Nine));
+        assert_eq!(five.to_vec(), parse_cards("9h8h7h6h5h").unwrap());
+
+        // When the board plays, the highest of the cards which tie are kept.
+        let (evaluation, five) = evaluate_hand_detailed(seven("2c3dAsKsQsJsTs"));
+        assert_eq!(evaluation.to_string(), "Royal Flush");
+        assert_eq!(five.to_vec(), parse_cards("AsKsQsJsTs").unwrap());
+
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut deck = Card::ALL;
+        for _ in 0..1_000 {
+            let (cards, _) = deck.partial_shuffle(&mut rng, 7);
+            let cards: [Card; 7] = cards.try_into().unwrap();
+            let (evaluation, five) = evaluate_hand_detailed(cards);
+            assert!(evaluation == evaluate_hand(cards));
+            assert!(evaluate_cards(&five) == Some(evaluation));
+            assert!(five.iter().all(|card| cards.contains(card)));
+        }
+    }
+
+    #[test]
+    fn test_eval_state_matches_evaluate_hand() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut deck = Card::ALL;
+        for _ in 0..2_000 {
+            let (cards, _) = deck.partial_shuffle(&mut rng, 9);
+            let (known, runout) = cards.split_at(5);
+
+            // Every turn and river from the four cards left, reusing the state.
+            let mut state = EvalState::new(known);
+            for (turn, river) in runout.iter().tuple_combinations() {
+                state.add_card(*turn);
+                state.add_card(*river);
+                assert_eq!(state.len(), 7);
+                let seven = [
+                    known[0], known[1], known[2], known[3], known[4], *turn, *river,
+                ];
+                assert!(state.evaluate() == Some(evaluate_hand(seven)));
+                state.remove_card(*river);
+                state.remove_card(*turn);
+            }
+            assert!(state.evaluate() == evaluate_cards(known));
+        }
+
+        // Taking away one card of a pair keeps the other.
+        let mut state = EvalState::new(&parse_cards("AsAdKc9h7s2d").unwrap());
+        state.remove_card(parse_cards("Ad").unwrap()[0]);
+        assert!(state.evaluate() == evaluate_cards(&parse_cards("AsKc9h7s2d").unwrap()));
+        assert!(!state.is_empty());
+
+        // Four cards aren't a hand yet.
+        state.remove_card(parse_cards("2d").unwrap()[0]);
+        assert!(state.evaluate().is_none());
+    }
+
+    #[test]
+    fn test_board_best() {
+        let board: [Card; 5] = parse_cards("9c8d7h6s5c").unwrap().try_into().unwrap();
+        let straight = board_best(board);
+        assert!(straight == HandEvaluation::new_straight(Number::Nine));
+
+        // Neither hand improves on the straight, so they chop.
+        let [first, second] = [parse_cards("2c2d"), parse_cards("AhKd")]
+            .map(|hand| evaluate_hand(combine(hand.unwrap().try_into().unwrap(), board)));
+        assert!(first == straight && second == straight);
+
+        // A ten plays, making a higher straight.
+        let ten = evaluate_hand(combine(
+            parse_cards("Th2d").unwrap().try_into().unwrap(),
+            board,
+        ));
+        assert!(ten > straight);
+    }
+
+    #[test]
+    fn test_combine() {
+        let hole = parse_cards("AsKd").unwrap();
+        let board = parse_cards("Qh7h2c9s3d").unwrap();
+        let hole = [hole[0], hole[1]];
+        let board = [board[0], board[1], board[2], board[3], board[4]];
+
+        let cards = combine(hole, board);
+        assert_eq!(cards[..2], hole);
+        assert_eq!(cards[2..], board);
+        assert!(Card::ALL
+            .into_iter()
+            .all(|card| cards.contains(&card) == (hole.contains(&card) || board.contains(&card))));
+
+        let omaha = parse_cards("AsKdJcTc").unwrap();
+        let cards = combine_omaha([omaha[0], omaha[1], omaha[2], omaha[3]], board);
+        assert_eq!(cards[..4], omaha[..]);
+        assert_eq!(cards[4..], board);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_all_cards() {
+        let mut deck = vec![];
+        for suit in Suit::ALL {
+            for number in Number::ALL {
+                deck.push(Card::new(suit, number));
+            }
+        }
+        assert_eq!(Card::ALL.to_vec(), deck);
+        assert_eq!(all_cards().collect::<Vec<_>>(), deck);
+        assert!(Card::ALL.iter().tuple_combinations().all(|(a, b)| a != b));
+
+        assert_eq!(Number::iter_desc().next(), Some(Number::Ace));
+        assert_eq!(Number::iter_desc().last(), Some(Number::Two));
+        assert!(Suit::ALL
+            .iter()
+            .enumerate()
+            .all(|(value, &suit)| suit as usize == value));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_suit_iter() {
+        let suits: Vec<Suit> = Suit::iter().collect();
+        assert_eq!(
+            suits,
+            [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+        );
+        assert_eq!(Suit::all(), Suit::ALL);
+        assert!(suits
+            .iter()
+            .enumerate()
+            .all(|(value, &suit)| suit as usize == value));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert_eq!(Suit::try_from(2), Ok(Suit::Clubs));
+        assert_eq!(Suit::try_from(4), Err(PokerError::InvalidSuit(4)));
+        assert_eq!(Number::try_from(10), Ok(Number::Ten));
+        assert_eq!(Number::try_from(15), Err(PokerError::InvalidNumber(15)));
+        assert_eq!(Number::try_from(0), Err(PokerError::InvalidNumber(0)));
+        assert_eq!(
+            "Ax".parse::<Card>(),
+            Err(PokerError::InvalidCard("Ax".into()))
+        );
+        assert_eq!(
+            PokerError::InvalidNumber(15).to_string(),
+            "invalid card number: 15 (expected a value from 2 to 14)"
+        );
+    }
+
+    #[test]
+    fn test_hand_parts() {
+        let evaluate = |cards: &str| evaluate_cards(&parse_cards(cards).unwrap()).unwrap();
+        let parts = |cards: &str| {
+            let evaluation = evaluate(cards);
+            (
+                evaluation.kind(),
+                evaluation.primary(),
+                evaluation.parts().secondary,
+                evaluation.kickers().collect::<Vec<_>>(),
+            )
+        };
+        use Number::*;
+
+        assert_eq!(
+            parts("KsKdKcTsTd"),
+            (HandKind::FullHouse, King, Some(Ten), vec![])
+        );
+        assert_eq!(
+            parts("AsAd9c4h2s"),
+            (HandKind::Pair, Ace, None, vec![Nine, Four, Two])
+        );
+        assert_eq!(
+            parts("JsJdTcTh2s"),
+            (HandKind::TwoPair, Jack, Some(Ten), vec![Two])
+        );
+        assert_eq!(
+            parts("AhJh9h7h6h"),
+            (HandKind::Flush, Ace, None, vec![Jack, Nine, Seven, Six])
+        );
+        assert_eq!(
+            parts("5h4c3d2sAh"),
+            (HandKind::Straight, Five, None, vec![])
+        );
+        assert_eq!(
+            parts("7s7d7c7hAs"),
+            (HandKind::FourOfAKind, Seven, None, vec![Ace])
+        );
+        // Only the best five cards play.
+        assert_eq!(
+            parts("QsQdQc9h8s3d2c"),
+            (HandKind::ThreeOfAKind, Queen, None, vec![Nine, Eight])
+        );
+
+        let parts = evaluate("QsQd9c4h2s").parts();
+        assert_eq!(parts.kickers, Nine.as_bit() | Four.as_bit() | Two.as_bit());
+    }
+
+    #[test]
+    fn test_describe() {
+        let descriptions = [
+            ("AhKhQhJhTh", "Royal Flush"),
+            ("9h8h7h6h5h", "Straight Flush, Nine high"),
+            ("AsAdAcAhKs", "Four of a Kind, Aces, King kicker"),
+            ("KsKdKcTsTd", "Full House, Kings full of Tens"),
+            ("AhJh9h7h6h", "Flush, Ace high"),
+            ("5h4c3d2sAh", "Straight, Five high"),
+            ("TsTdTc9h2s", "Three of a Kind, Tens, Nine kicker"),
+            ("JsJdTcTh2s", "Two Pair, Jacks and Tens, Two kicker"),
+            ("AsAdKc4h2s", "Pair of Aces, King kicker"),
+            ("KsJd9c4h2s", "High Card, King, Jack kicker"),
+        ];
+        for (cards, description) in descriptions {
+            let evaluation = evaluate_cards(&parse_cards(cards).unwrap()).unwrap();
+            assert_eq!(evaluation.describe(), description);
+            assert_eq!(evaluation.to_string(), description);
+        }
+
+        // The alternate form names every kicker.
+        let full_names = [
+            ("AhKhQhJhTh", "Royal Flush"),
+            ("AsAdAcAhKs", "Four of a Kind, Aces, King kicker"),
+            ("KsKdKcTsTd", "Full House, Kings full of Tens"),
+            (
+                "AhJh9h7h6h",
+                "Flush, Ace high, Jack, Nine, Seven and Six kickers",
+            ),
+            ("5h4c3d2sAh", "Straight, Five high"),
+            ("TsTdTc9h2s", "Three of a Kind, Tens, Nine and Two kickers"),
+            ("JsJdTcTh2s", "Two Pair, Jacks and Tens, Two kicker"),
+            ("AsAdKc4h2s", "Pair of Aces, King, Four and Two kickers"),
+            (
+                "KsJd9c4h2s",
+                "High Card, King, Jack, Nine, Four and Two kickers",
+            ),
+        ];
+        for (cards, name) in full_names {
+            let evaluation = evaluate_cards(&parse_cards(cards).unwrap()).unwrap();
+            assert_eq!(format!("{evaluation:#}"), name);
+        }
+    }
+
+    #[test]
+    fn test_hand_kind_names() {
+        let names = [
+            (HandKind::StraightFlush, "Straight Flush"),
+            (HandKind::FourOfAKind, "Four of a Kind"),
+            (HandKind::FullHouse, "Full House"),
+            (HandKind::Flush, "Flush"),
+            (HandKind::Straight, "Straight"),
+            (HandKind::ThreeOfAKind, "Three of a Kind"),
+            (HandKind::TwoPair, "Two Pair"),
+            (HandKind::Pair, "Pair"),
+            (HandKind::HighCard, "High Card"),
+        ];
+        assert!(HandKind::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        for (kind, name) in names {
+            assert!(HandKind::ALL.contains(&kind));
+            assert_eq!(kind.name(), name);
+            assert_eq!(kind.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_straight_cards() {
+        let broadway = [
+            Number::Ace,
+            Number::King,
+            Number::Queen,
+            Number::Jack,
+            Number::Ten,
+        ];
+        let bitset = broadway
+            .iter()
+            .fold(Number::Two.as_bit(), |bitset, number| {
+                bitset | number.as_bit()
+            });
+        assert_eq!(straight_cards(bitset), Some(broadway));
+
+        let wheel = [
+            Number::Five,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+            Number::Ace,
+        ];
+        let bitset = wheel.iter().fold(Number::King.as_bit(), |bitset, number| {
+            bitset | number.as_bit()
+        });
+        assert_eq!(straight_cards(bitset), Some(wheel));
+
+        // A six on top of the wheel makes the higher straight.
+        assert_eq!(
+            straight_cards(bitset | Number::Six.as_bit()).map(|cards| cards[4]),
+            Some(Number::Two)
+        );
+        assert_eq!(
+            straight_cards(Number::Ace.as_bit() | Number::King.as_bit()),
+            None
+        );
+    }
+
     #[test]
     fn test_card_evaluations() {
         let royal_flush = HandEvaluation::new_straight_flush(Number::Ace);
@@ -485,8 +2008,8 @@ mod tests {
             | Number::Four.as_bit()
             | Number::Five.as_bit();
 
-        assert_eq!(check_for_straight(ace_high_mask), Some(Number::Ace));
-        assert_eq!(check_for_straight(five_high_mask), Some(Number::Five));
+        assert_eq!(straight_high_card(ace_high_mask), Some(Number::Ace));
+        assert_eq!(straight_high_card(five_high_mask), Some(Number::Five));
     }
 
     #[test]
@@ -753,4 +2276,308 @@ mod tests {
         ];
         assert!((0..all_hands.len() - 1).all(|i| all_hands[i] >= all_hands[i + 1]));
     }
+
+    #[test]
+    fn test_seven_card_evaluations() {
+        // Every hand from ten cards of one suit is a flush of some kind.
+        let spades = parse_cards("2s3s4s5s6s7s8s9sTsJs").unwrap();
+        let kinds: Vec<HandKind> = seven_card_evaluations(spades)
+            .map(HandEvaluation::kind)
+            .collect();
+        assert_eq!(kinds.len(), 120);
+        assert!(kinds
+            .iter()
+            .all(|kind| matches!(kind, HandKind::Flush | HandKind::StraightFlush)));
+        assert!(kinds.contains(&HandKind::StraightFlush));
+
+        // A whole suit deals C(13, 7) hands, and the full deck C(52, 7).
+        let hearts = parse_cards("2h3h4h5h6h7h8h9hThJhQhKhAh").unwrap();
+        assert_eq!(seven_card_evaluations(hearts).count(), 1716);
+        assert_eq!(all_seven_card_evaluations().take(1000).count(), 1000);
+    }
+
+    #[test]
+    fn test_sort_by_hand_strength() {
+        let hand = |s: &str| -> [Card; 7] { Hand::try_from_iter(parse_cards(s).unwrap()).unwrap() };
+
+        let full_house = hand("KcKh9c8c8s8h5h");
+        let pair = hand("AcAdKcTd9c8c6h");
+        let royal_flush = hand("AcKcQcJcTc8h5h");
+        let high_card = hand("KcQdTd9c8c6h4c");
+        let straight = hand("AcKcQcJcTh8h5h");
+
+        let mut hands = [full_house, pair, royal_flush, high_card, straight];
+        hands.sort_by(compare_hands);
+        assert_eq!(hands, [high_card, pair, straight, full_house, royal_flush]);
+
+        assert_eq!(compare_hands(&pair, &pair), std::cmp::Ordering::Equal);
+
+        let ranks = hands.map(|hand| evaluate_hand(hand).rank());
+        assert!(ranks.windows(2).all(|window| window[0] < window[1]));
+        assert_eq!(
+            evaluate_hand(hand("AcAdKcTd9c8c6h")).rank(),
+            evaluate_hand(hand("AhAsKdTc9d8h6c")).rank()
+        );
+    }
+
+    #[test]
+    fn test_evaluate_hi_lo() {
+        let bits = |numbers: &[u8]| numbers.iter().fold(0, |bits, number| bits | 1 << number);
+
+        let cards = parse_cards("Ah2c3d4s8hKhKd").unwrap();
+        let (high, low) = evaluate_hi_lo(&cards).unwrap();
+        assert!(high == evaluate_hand(Hand::try_from_iter(cards).unwrap()));
+        assert_eq!(high.kind(), HandKind::Pair);
+        let eight_low = low.unwrap();
+        assert!(eight_low == HandEvaluation::new_high_card(bits(&[8, 4, 3, 2, 1])));
+
+        // The wheel is both a straight and the best low, and a six doesn't
+        // spoil it.
+        let (high, wheel) = evaluate_hi_lo(&parse_cards("Ah2c3d4s5h").unwrap()).unwrap();
+        assert!(high == HandEvaluation::new_straight(Number::Five));
+        let (high, low) = evaluate_hi_lo(&parse_cards("Ah2c3d4s5h6h").unwrap()).unwrap();
+        assert!(high == HandEvaluation::new_straight(Number::Six));
+        assert!(low == wheel);
+        assert!(wheel.unwrap() < eight_low);
+
+        // Pairs don't count twice, and nines are too high.
+        let (high, low) = evaluate_hi_lo(&parse_cards("AhKhQh5c5dJs9s").unwrap()).unwrap();
+        assert_eq!(high.kind(), HandKind::Pair);
+        assert!(low.is_none());
+        assert!(evaluate_hi_lo(&parse_cards("Ah2c3d4s4h9h9d").unwrap())
+            .unwrap()
+            .1
+            .is_none());
+
+        // Only five to seven cards can be evaluated.
+        assert!(evaluate_hi_lo(&[]).is_none());
+        assert!(evaluate_hi_lo(&parse_cards("AsAhAdAc").unwrap()).is_none());
+        assert!(evaluate_hi_lo(&parse_cards("AsAhKdKc").unwrap()).is_none());
+        let board = parse_cards("2c3d4h5s6c").unwrap();
+        let omaha = combine_omaha(
+            Hand::try_from_iter(parse_cards("AsAhKdKc").unwrap()).unwrap(),
+            Hand::try_from_iter(board).unwrap(),
+        );
+        assert!(evaluate_hi_lo(&omaha).is_none());
+    }
+
+    #[test]
+    fn test_rank_round_trip() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut deck = Card::ALL;
+        let random_hands = (0..50_000).map(|_| {
+            let (hand, _) = deck.partial_shuffle(&mut rng, 7);
+            evaluate_hand(Hand::try_from_iter(hand.iter().copied()).unwrap())
+        });
+        let hearts = parse_cards("2h3h4h5h6h7h8h9hThJhQhKhAh").unwrap();
+        let mut kinds = vec![];
+        for evaluation in random_hands.chain(seven_card_evaluations(hearts)) {
+            assert!(HandEvaluation::from_rank(evaluation.rank()) == Some(evaluation));
+            kinds.push(evaluation.kind());
+        }
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), 9);
+
+        let pair =
+            evaluate_hand(Hand::try_from_iter(parse_cards("AcAdKcTd9c8c6h").unwrap()).unwrap());
+        assert!(HandEvaluation::from_rank(pair.rank() | 1 << 30).is_none());
+        // Kickers which include the pair, or too many of them.
+        assert!(HandEvaluation::from_rank(pair.rank() | u32::from(Number::Ace.as_bit())).is_none());
+        assert!(HandEvaluation::from_rank(pair.rank() | u32::from(Number::Two.as_bit())).is_none());
+        // A straight can't be five high or lower, and a flush can't be a
+        // straight.
+        assert!(HandEvaluation::from_rank(0x0404_0000).is_none());
+        assert!(HandEvaluation::from_rank(0x0405_0000).is_some());
+        let straight = u32::from(u16::from_be_bytes([0x00, 0x7c])) << 8;
+        assert!(HandEvaluation::from_rank(0x0500_0000 | straight).is_none());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut deck = Card::ALL;
+        let mut previous: Option<HandEvaluation> = None;
+        for _ in 0..50_000 {
+            let (hand, _) = deck.partial_shuffle(&mut rng, 7);
+            let evaluation = evaluate_hand(Hand::try_from_iter(hand.iter().copied()).unwrap());
+            let bytes = evaluation.to_bytes();
+            assert!(HandEvaluation::from_bytes(bytes) == Some(evaluation));
+            if let Some(previous) = previous {
+                assert_eq!(bytes.cmp(&previous.to_bytes()), evaluation.cmp(&previous));
+            }
+            previous = Some(evaluation);
+        }
+
+        let quads = HandEvaluation::new_four_of_a_kind(Number::Ace, Number::King);
+        assert_eq!(quads.to_bytes(), [7, 14, 13, 0]);
+        assert!(HandEvaluation::from_bytes([7, 14, 14, 0]).is_none());
+        assert!(HandEvaluation::from_bytes([9, 0, 0, 0]).is_none());
+    }
+}
+
+/// Seven card hands where the best five cards are easy to get wrong.
+#[cfg(all(test, feature = "std"))]
+mod edge_case_tests {
+    use super::*;
+
+    fn evaluate(cards: &str) -> HandEvaluation {
+        evaluate_hand(Hand::try_from_iter(parse_cards(cards).unwrap()).unwrap())
+    }
+
+    fn bits(numbers: &[Number]) -> u16 {
+        numbers
+            .iter()
+            .fold(0, |bits, number| bits | number.as_bit())
+    }
+
+    #[test]
+    fn test_steel_wheel() {
+        let wheel = evaluate("Ah2h3h4h5hKcQd");
+        assert!(wheel == HandEvaluation::new_straight_flush(Number::Five));
+        assert!(evaluate("2h3h4h5h6hKcQd") > wheel);
+        assert!(wheel > evaluate("AcAdAhAsKcQdJh"));
+
+        // With the six as well, the ace no longer plays.
+        assert!(evaluate("Ah2h3h4h5h6hKc") == HandEvaluation::new_straight_flush(Number::Six));
+    }
+
+    #[test]
+    fn test_two_possible_straights() {
+        assert!(evaluate("9c8d7h6s5c4d3h") == HandEvaluation::new_straight(Number::Nine));
+        assert!(evaluate("Ac2d3h4s5c6dKh") == HandEvaluation::new_straight(Number::Six));
+        assert!(evaluate("AcKdQhJsTc9d2h") == HandEvaluation::new_straight(Number::Ace));
+        assert!(evaluate("Ac2d3h4s5cKdKh") == HandEvaluation::new_straight(Number::Five));
+    }
+
+    #[test]
+    fn test_flush_with_extra_suited_cards() {
+        let top_five = bits(&[
+            Number::Ace,
+            Number::King,
+            Number::Queen,
+            Number::Nine,
+            Number::Seven,
+        ]);
+        let seven_spades = evaluate("AsKsQs9s7s4s2s");
+        assert!(seven_spades == HandEvaluation::new_flush(top_five));
+        assert!(evaluate("AsKsQs9s7s4s2d") == seven_spades);
+        assert!(evaluate("AsKsQs9s7s3d2d") == seven_spades);
+
+        // The fifth highest suited card breaks the tie, across the two bytes.
+        assert!(evaluate("AsKsQs9s8s4s2s") > seven_spades);
+        assert!(evaluate("AsKsQs9s6s5s4s") < seven_spades);
+    }
+
+    #[test]
+    fn test_full_house_with_two_triples() {
+        let kings_full = evaluate("KcKhKd8c8s8h5h");
+        assert!(kings_full == HandEvaluation::new_full_house(Number::King, Number::Eight));
+        assert!(evaluate("8c8s8hKcKhKd5h") == kings_full);
+
+        // A single higher card doesn't change it, but a higher pair does.
+        assert!(evaluate("KcKhKd8c8s8hAh") == kings_full);
+        assert!(
+            evaluate("KcKhKdAcAs8s8h") == HandEvaluation::new_full_house(Number::King, Number::Ace)
+        );
+    }
+
+    #[test]
+    fn test_quads_kicker() {
+        let quads = |cards| {
+            evaluate(cards) == HandEvaluation::new_four_of_a_kind(Number::Five, Number::Ace)
+        };
+        assert!(quads("5c5d5h5sAcAd2h"));
+        assert!(quads("5c5d5h5sAcKdKh"));
+        assert!(quads("5c5d5h5sAcAdAh"));
+        assert!(
+            evaluate("5c5d5h5sKcKdKh")
+                == HandEvaluation::new_four_of_a_kind(Number::Five, Number::King)
+        );
+    }
+
+    #[test]
+    fn test_kickers_split_across_bytes() {
+        // Numbers from the eight up are in the high byte of the kickers, and
+        // the rest are in the low byte, so the ace and the four are split.
+        let ace_four = evaluate("7c7d7hAs4c3d2h");
+        assert!(
+            ace_four
+                == HandEvaluation::new_three_of_a_kind(
+                    Number::Seven,
+                    bits(&[Number::Ace, Number::Four])
+                )
+        );
+        // These differ from it only in the low byte.
+        assert!(Some(ace_four) > evaluate_cards(&parse_cards("7c7d7hAs3c2d").unwrap()));
+        assert!(evaluate("7c7d7hAs5c3d2h") > ace_four);
+        // The high byte decides first.
+        assert!(evaluate("7c7d7hKsQc3d2h") < ace_four);
+
+        let pair = evaluate("7c7dAhKs2c3d4h");
+        assert!(pair > evaluate("7c7dAhQsJc9d4h"));
+        assert!(pair < evaluate("7c7dAhKs5c3d4h"));
+    }
+}
+
+/// These only use what is available without the standard library, so they also
+/// run with `--no-default-features`.
+#[cfg(test)]
+mod core_tests {
+    use super::*;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid card number: 15")]
+    fn test_unchecked_number_is_checked_in_debug() {
+        let _ = unsafe { Number::from_u8_unchecked(15) };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid suit: 4")]
+    fn test_unchecked_suit_is_checked_in_debug() {
+        let _ = unsafe { Suit::from_u8_unchecked(4) };
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_evaluate_without_std() {
+        let hand = |cards: [(Number, Suit); 7]| {
+            evaluate_hand(cards.map(|(number, suit)| Card::new(suit, number)))
+        };
+
+        let straight_flush = hand([
+            (Number::Nine, Suit::Clubs),
+            (Number::Eight, Suit::Clubs),
+            (Number::Seven, Suit::Clubs),
+            (Number::Six, Suit::Clubs),
+            (Number::Five, Suit::Clubs),
+            (Number::Ace, Suit::Hearts),
+            (Number::Ace, Suit::Spades),
+        ]);
+        let two_pair = hand([
+            (Number::Nine, Suit::Clubs),
+            (Number::Nine, Suit::Hearts),
+            (Number::Seven, Suit::Clubs),
+            (Number::Two, Suit::Diamonds),
+            (Number::Five, Suit::Clubs),
+            (Number::Ace, Suit::Hearts),
+            (Number::Ace, Suit::Spades),
+        ]);
+
+        assert_eq!(straight_flush.kind(), HandKind::StraightFlush);
+        assert!(straight_flush == HandEvaluation::new_straight_flush(Number::Nine));
+        assert_eq!(two_pair.kind(), HandKind::TwoPair);
+        assert!(two_pair == HandEvaluation::new_two_pair(Number::Ace, Number::Nine, Number::Seven));
+        assert!(straight_flush > two_pair);
+        assert_eq!(Suit::from_u8(3), Suit::Spades);
+        assert_eq!(Number::from_u8(14), Number::Ace);
+        assert_eq!(Suit::try_from_u8(4), None);
+        assert_eq!(Number::try_from_u8(1), None);
+    }
 }
@@ -1,38 +1,260 @@
-use poker_calculator::{compute_result, Card, ComputeResult, Number, Suit};
-
-fn main() {
-    let ComputeResult {
-        win_count,
-        loss_count,
-        tie_count,
-        count,
-    } = compute_result(
-        [
-            Card::new(Suit::Hearts, Number::Queen),
-            Card::new(Suit::Hearts, Number::King),
-        ],
-        [
-            Card::new(Suit::Spades, Number::Two),
-            Card::new(Suit::Hearts, Number::Two),
-        ],
-    );
-
-    #[allow(clippy::cast_precision_loss)]
-    let win_percent = win_count as f64 / count as f64 * 100.;
-
-    #[allow(clippy::cast_precision_loss)]
-    let loss_percent = loss_count as f64 / count as f64 * 100.;
-
-    #[allow(clippy::cast_precision_loss)]
-    let tie_percent = tie_count as f64 / count as f64 * 100.;
-
-    println!(
-        "Win: {:.2}\nLose: {:.2}\nTie: {:.2}\n",
-        win_percent, loss_percent, tie_percent
-    );
-
-    println!(
-        "Win: {}\nLose: {}\nTie: {}\nTotal: {}",
-        win_count, loss_count, tie_count, count
-    );
+use poker_calculator::{
+    compute_equity_with_options, distinct_hand_classes, evaluate_cards, parse_cards, parse_spot,
+    Board, Card, EquityOptions, Hand, HandEvaluation, HandKind, HandKindBreakdown,
+};
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "usage: poker_calculator <HAND> <HAND>... [--board <CARDS>] [--dead <CARDS>] [--hand-kinds]
+       poker_calculator --spot <SPOT> [--hand-kinds]
+       poker_calculator --hand-classes
+
+Computes the exact equity of each hand, for example:
+
+    poker_calculator AsKc QhQs --board Jd9c2s --dead Th
+    poker_calculator --spot \"AsKc QhQs | Jd9c2s | Th\"
+
+With --hand-kinds, also shows how often each hand wins and loses with each
+kind of hand.
+
+With --hand-classes, instead prints every distinct five card hand as CSV,
+from the weakest to the strongest.";
+
+/// A matchup given on the command line.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    hands: Vec<Hand>,
+    board: Board,
+    dead: Vec<Card>,
+    hand_kinds: bool,
+    hand_classes: bool,
+}
+
+/// Parses the arguments which follow the program name.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        hands: vec![],
+        board: Board::default(),
+        dead: vec![],
+        hand_kinds: false,
+        hand_classes: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--board" | "--dead" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{arg} needs a value, such as Jd9c2s"))?;
+                let invalid = |error| format!("invalid cards {value:?} for {arg}: {error}");
+                if arg == "--board" {
+                    parsed.board = value.parse().map_err(invalid)?;
+                } else {
+                    parsed.dead = parse_cards(&value).map_err(invalid)?;
+                }
+            }
+            "--spot" => {
+                let value = args.next().ok_or_else(|| {
+                    format!("{arg} needs a value, such as \"AsKc QhQs | Jd9c2s\"")
+                })?;
+                let spot = parse_spot(&value)
+                    .map_err(|error| format!("invalid spot {value:?}: {error}"))?;
+                for hand in spot.hands {
+                    parsed.hands.push(
+                        hand.try_into()
+                            .map_err(|error| format!("invalid spot {value:?}: {error}"))?,
+                    );
+                }
+                parsed.board = Board::new(&spot.board)
+                    .map_err(|error| format!("invalid spot {value:?}: {error}"))?;
+                parsed.dead = spot.dead;
+            }
+            "--hand-kinds" => parsed.hand_kinds = true,
+            "--hand-classes" => parsed.hand_classes = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag:?}")),
+            hand => parsed.hands.push(
+                hand.parse()
+                    .map_err(|error| format!("invalid hand {hand:?}: {error}"))?,
+            ),
+        }
+    }
+
+    if parsed.hands.len() < 2 && !parsed.hand_classes {
+        return Err("at least two hands are needed".into());
+    }
+    Ok(parsed)
+}
+
+/// Lays out how often a hand won and lost with each kind of hand it made,
+/// skipping the kinds it never made.
+fn hand_kind_table(breakdown: &HandKindBreakdown) -> String {
+    let header = format!("    {:<16}{:>8}{:>8}\n", "", "won", "lost");
+    let rows = HandKind::ALL
+        .into_iter()
+        .rev()
+        .filter(|&kind| breakdown.won[kind as usize] + breakdown.lost[kind as usize] > 0)
+        .map(|kind| {
+            format!(
+                "    {:<16}{:>7.1}%{:>7.1}%\n",
+                kind.name(),
+                breakdown.won_with(kind) * 100.,
+                breakdown.lost_with(kind) * 100.
+            )
+        });
+    std::iter::once(header).chain(rows).collect()
+}
+
+/// Describes the hand a player has made so far, once there are enough cards
+/// on the board to make one.
+fn made_hand(hand: Hand, board: &[Card]) -> Option<String> {
+    let cards = [&hand.cards()[..], board].concat();
+    evaluate_cards(&cards).map(HandEvaluation::describe)
+}
+
+/// Formats one distinct hand as a CSV row of its dense rank, its name and its
+/// packed [`HandEvaluation::rank`].
+fn hand_class_row(evaluation: HandEvaluation, name: &str, rank: u32) -> String {
+    format!("{rank},\"{name}\",{}", evaluation.rank())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.hand_classes {
+        println!("rank,name,evaluation");
+        for (evaluation, name, rank) in distinct_hand_classes() {
+            println!("{}", hand_class_row(evaluation, &name, rank));
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let hands: Vec<[Card; 2]> = args.hands.iter().map(|hand| hand.cards()).collect();
+    let options = EquityOptions {
+        track_hand_kinds: args.hand_kinds,
+    };
+    let results = match compute_equity_with_options(&hands, &args.board, &args.dead, options) {
+        Ok(results) => results,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for (hand, detailed) in args.hands.iter().zip(&results) {
+        let result = detailed.result;
+        println!(
+            "{hand}: {:.2}% equity (win / lose / tie: {result})",
+            result.equity() * 100.
+        );
+        if let Some(description) = made_hand(*hand, &args.board) {
+            println!("    {description}");
+        }
+        if let Some(breakdown) = detailed.hand_kinds {
+            print!("{}", hand_kind_table(&breakdown));
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_calculator::compute_equity;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_matchup() {
+        let args = parse("AsKc QhQs --board Jd9c2s --dead Th").unwrap();
+        assert_eq!(
+            args,
+            Args {
+                hands: vec!["AsKc".parse().unwrap(), "QhQs".parse().unwrap()],
+                board: "Jd9c2s".parse().unwrap(),
+                dead: parse_cards("Th").unwrap(),
+                hand_kinds: false,
+                hand_classes: false,
+            }
+        );
+
+        let hands: Vec<[Card; 2]> = args.hands.iter().map(|hand| hand.cards()).collect();
+        let results = compute_equity(&hands, &args.board, &args.dead).unwrap();
+        assert_eq!(results[0].count, 946);
+        assert_eq!(
+            results[0].win_count + results[0].tie_count + results[0].loss_count,
+            946
+        );
+    }
+
+    #[test]
+    fn test_hand_kind_table() {
+        let args = parse("AhQc KsKd --board 2h7hJhKc --hand-kinds").unwrap();
+        assert!(args.hand_kinds);
+
+        let hands: Vec<[Card; 2]> = args.hands.iter().map(|hand| hand.cards()).collect();
+        let options = EquityOptions {
+            track_hand_kinds: true,
+        };
+        let results = compute_equity_with_options(&hands, &args.board, &[], options).unwrap();
+        let table = hand_kind_table(&results[0].hand_kinds.unwrap());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("    Flush") && lines[1].contains("72.7%"));
+        assert!(lines[2].starts_with("    Straight"));
+        assert!(!table.contains("Full House"));
+    }
+
+    #[test]
+    fn test_parse_spot() {
+        let args = parse_args(["--spot", "AsKc QhQs | Jd9c2s | Th"].map(String::from)).unwrap();
+        assert_eq!(args, parse("AsKc QhQs --board Jd9c2s --dead Th").unwrap());
+
+        let args = parse_args(["--spot", "AsKc QhQs", "--hand-kinds"].map(String::from)).unwrap();
+        assert_eq!(args, parse("AsKc QhQs --hand-kinds").unwrap());
+
+        let error = parse_args(["--spot", "AsKc Qh"].map(String::from)).unwrap_err();
+        assert!(error.contains("invalid spot"));
+    }
+
+    #[test]
+    fn test_hand_classes() {
+        let args = parse("--hand-classes").unwrap();
+        assert!(args.hand_classes && args.hands.is_empty());
+
+        let royal_flush = HandEvaluation::new_straight_flush(poker_calculator::Number::Ace);
+        assert_eq!(
+            hand_class_row(royal_flush, &royal_flush.to_string(), royal_flush.rank5()),
+            format!("7462,\"Royal Flush\",{}", royal_flush.rank())
+        );
+    }
+
+    #[test]
+    fn test_made_hand() {
+        let hand = "AsKc".parse().unwrap();
+        assert_eq!(made_hand(hand, &[]), None);
+        assert_eq!(
+            made_hand(hand, &parse_cards("Ad9c2s").unwrap()).as_deref(),
+            Some("Pair of Aces, King kicker")
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse("AsKc").unwrap_err().contains("two hands"));
+        assert!(parse("AsKc QhQx").unwrap_err().contains("\"QhQx\""));
+        assert!(parse("AsKc QhQs --board").unwrap_err().contains("--board"));
+        assert!(parse("AsKc QhQs --board Jd9c")
+            .unwrap_err()
+            .contains("\"Jd9c\""));
+        assert!(parse("AsKc QhQs --turn 2c").unwrap_err().contains("--turn"));
+    }
 }
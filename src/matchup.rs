@@ -0,0 +1,185 @@
+use crate::{compute_equity_memoized, Card, Hand, Number, PokerError};
+
+/// How far from an even split a race may be while still counting as a
+/// coinflip.
+const COINFLIP_MARGIN: f64 = 0.08;
+
+/// The usual names for a heads-up preflop matchup, from the hero's side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchupKind {
+    /// Neither hand is much of a favourite, such as a pair against two
+    /// overcards.
+    Coinflip,
+    /// The hands share a card, and the hero's other card is higher.
+    Dominating,
+    /// The hands share a card, and the hero's other card is lower.
+    Dominated,
+    /// Both hands are pairs, and the hero's is higher.
+    Overpair,
+    /// Both hands are pairs, and the hero's is lower.
+    Underpair,
+    /// The hands don't interact, and the hero is clearly ahead.
+    RaceFavorite,
+    /// The hands don't interact, and the hero is clearly behind.
+    RaceUnderdog,
+}
+
+impl MatchupKind {
+    /// Returns the same matchup seen from the villain's side.
+    #[must_use]
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Coinflip => Self::Coinflip,
+            Self::Dominating => Self::Dominated,
+            Self::Dominated => Self::Dominating,
+            Self::Overpair => Self::Underpair,
+            Self::Underpair => Self::Overpair,
+            Self::RaceFavorite => Self::RaceUnderdog,
+            Self::RaceUnderdog => Self::RaceFavorite,
+        }
+    }
+}
+
+/// Returns the numbers of a hand, highest first.
+fn numbers(hand: [Card; 2]) -> (Number, Number) {
+    let [first, second] = hand.map(Card::number);
+    (first.max(second), first.min(second))
+}
+
+/// Classifies the matchup from the shape of the hands alone, when that is
+/// enough to name it.
+fn structural_kind(hero: [Card; 2], villain: [Card; 2]) -> Option<MatchupKind> {
+    let (hero_high, hero_low) = numbers(hero);
+    let (villain_high, villain_low) = numbers(villain);
+    let hero_pair = hero_high == hero_low;
+    let villain_pair = villain_high == villain_low;
+
+    match (hero_pair, villain_pair) {
+        (true, true) if hero_high == villain_high => Some(MatchupKind::Coinflip),
+        (true, true) if hero_high > villain_high => Some(MatchupKind::Overpair),
+        (true, true) => Some(MatchupKind::Underpair),
+        (true, false) if hero_high == villain_high || hero_high == villain_low => {
+            Some(MatchupKind::Dominating)
+        }
+        (true, false) if hero_high < villain_low => Some(MatchupKind::Coinflip),
+        (false, true) => structural_kind(villain, hero).map(MatchupKind::reversed),
+        (false, false) => {
+            // With one number in common, the other card decides the hand.
+            let kickers = if hero_high == villain_high {
+                Some((hero_low, villain_low))
+            } else if hero_high == villain_low {
+                Some((hero_low, villain_high))
+            } else if hero_low == villain_high {
+                Some((hero_high, villain_low))
+            } else if hero_low == villain_low {
+                Some((hero_high, villain_high))
+            } else {
+                None
+            };
+            kickers.map(
+                |(hero_kicker, villain_kicker)| match hero_kicker.cmp(&villain_kicker) {
+                    std::cmp::Ordering::Greater => MatchupKind::Dominating,
+                    std::cmp::Ordering::Less => MatchupKind::Dominated,
+                    std::cmp::Ordering::Equal => MatchupKind::Coinflip,
+                },
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Names a heads-up preflop matchup from the hero's side.
+///
+/// Pairs against pairs, pairs against two overcards and hands sharing a
+/// number are named from their shape. Every other matchup is a race, which is
+/// named by computing the exact equity: a coinflip when the hero's equity is
+/// within 8% of an even split, and otherwise a favourite or an underdog.
+///
+/// # Errors
+///
+/// Will return [`PokerError::DuplicateCard`] if the hands share a card.
+pub fn matchup_kind(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+) -> Result<MatchupKind, PokerError> {
+    let (hero, villain) = (hero.into(), villain.into());
+    Hand::try_from_iter::<4>(hero.into_iter().chain(villain))?;
+    if let Some(kind) = structural_kind(hero, villain) {
+        return Ok(kind);
+    }
+
+    let results = compute_equity_memoized(&[hero, villain], &[], &[])?;
+    let equity = results[0].equity();
+
+    let kind = if (equity - 0.5).abs() <= COINFLIP_MARGIN {
+        MatchupKind::Coinflip
+    } else if equity > 0.5 {
+        MatchupKind::RaceFavorite
+    } else {
+        MatchupKind::RaceUnderdog
+    };
+    Ok(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+
+    fn hand(s: &str) -> [Card; 2] {
+        let cards = parse_cards(s).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn test_pair_against_overcards_is_a_coinflip() {
+        assert_eq!(
+            matchup_kind(hand("QsQd"), hand("AhKc")),
+            Ok(MatchupKind::Coinflip)
+        );
+        assert_eq!(
+            matchup_kind(hand("AhKh"), hand("2c2d")),
+            Ok(MatchupKind::Coinflip)
+        );
+    }
+
+    #[test]
+    fn test_shared_card_is_domination() {
+        assert_eq!(
+            matchup_kind(hand("AcQd"), hand("AsKh")),
+            Ok(MatchupKind::Dominated)
+        );
+        assert_eq!(
+            matchup_kind(hand("AsKh"), hand("QdAc")),
+            Ok(MatchupKind::Dominating)
+        );
+        assert_eq!(
+            matchup_kind(hand("KsKh"), hand("AcKd")),
+            Ok(MatchupKind::Dominating)
+        );
+        assert_eq!(
+            matchup_kind(hand("AsKh"), hand("AcKd")),
+            Ok(MatchupKind::Coinflip)
+        );
+    }
+
+    #[test]
+    fn test_pairs() {
+        assert_eq!(
+            matchup_kind(hand("KsKh"), hand("7c7d")),
+            Ok(MatchupKind::Overpair)
+        );
+        assert_eq!(
+            matchup_kind(hand("7c7d"), hand("KsKh")),
+            Ok(MatchupKind::Underpair)
+        );
+    }
+
+    #[test]
+    fn test_shared_card_is_an_error() {
+        assert_eq!(
+            matchup_kind(hand("AsKh"), hand("AsQd")),
+            Err(PokerError::DuplicateCard(parse_cards("As").unwrap()[0]))
+        );
+    }
+}
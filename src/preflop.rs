@@ -0,0 +1,211 @@
+use crate::{compute_equity_memoized, Card, EquityResult, PokerError};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// A heads-up preflop matchup with its suits relabelled and its hands put in
+/// a canonical order. Matchups which only differ by renaming suits or by
+/// swapping the two hands share a key, and always have the same equities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct MatchupKey([u8; 4]);
+
+/// Returns the key for a matchup, and whether the hands had to be swapped to
+/// reach the canonical order.
+fn canonical_matchup(hero: [Card; 2], villain: [Card; 2]) -> (MatchupKey, bool) {
+    [([hero, villain], false), ([villain, hero], true)]
+        .into_iter()
+        .flat_map(|(hands, swapped)| {
+            (0..4_u8).permutations(4).map(move |permutation| {
+                let mut key = [0; 4];
+                for (value, card) in key.iter_mut().zip(hands.iter().flatten()) {
+                    *value = permutation[card.suit() as usize] << 4 | card.number() as u8;
+                }
+
+                // The order of the cards within a hand doesn't matter.
+                key[..2].sort_unstable();
+                key[2..].sort_unstable();
+                (MatchupKey(key), swapped)
+            })
+        })
+        .min()
+        .expect("there is always at least one permutation")
+}
+
+/// A cache of heads-up preflop equities, shared between every matchup which
+/// is identical up to renaming suits or swapping the hands.
+///
+/// There are only 47,008 such distinct matchups, so a long-running server
+/// quickly ends up answering nearly every preflop query from the table.
+#[derive(Default)]
+pub struct PreflopTable {
+    entries: HashMap<MatchupKey, [EquityResult; 2]>,
+}
+
+impl PreflopTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Looks up the results for a matchup, in the order the hands were given.
+    #[must_use]
+    pub fn get(&self, hero: [Card; 2], villain: [Card; 2]) -> Option<[EquityResult; 2]> {
+        let (key, swapped) = canonical_matchup(hero, villain);
+        let [first, second] = *self.entries.get(&key)?;
+        Some(if swapped {
+            [second, first]
+        } else {
+            [first, second]
+        })
+    }
+
+    /// Stores the results for a matchup, given in the same order as the hands.
+    pub fn insert(&mut self, hero: [Card; 2], villain: [Card; 2], results: [EquityResult; 2]) {
+        let (key, swapped) = canonical_matchup(hero, villain);
+        let [first, second] = results;
+        self.entries.insert(
+            key,
+            if swapped {
+                [second, first]
+            } else {
+                [first, second]
+            },
+        );
+    }
+}
+
+/// Returns the table shared by every call to [`compute_equity_canonical`].
+fn shared_table() -> &'static Mutex<PreflopTable> {
+    static TABLE: OnceLock<Mutex<PreflopTable>> = OnceLock::new();
+    TABLE.get_or_init(Mutex::default)
+}
+
+/// Computes the exact preflop equity of a heads-up matchup, as
+/// [`compute_equity`](crate::compute_equity) does, remembering the results
+/// for the rest of the process.
+///
+/// Results are shared through a [`PreflopTable`], so a matchup which only
+/// differs from an earlier one by renaming suits or swapping the hands is
+/// answered without computing it again.
+///
+/// # Errors
+///
+/// Will return an error if the hands share a card.
+pub fn compute_equity_canonical(
+    hero: impl Into<[Card; 2]>,
+    villain: impl Into<[Card; 2]>,
+) -> Result<[EquityResult; 2], PokerError> {
+    let (hero, villain) = (hero.into(), villain.into());
+    let table = shared_table();
+    // A panic elsewhere can't leave the table half updated, so its contents
+    // are still good after poisoning.
+    let cached = table
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(hero, villain);
+    if let Some(results) = cached {
+        return Ok(results);
+    }
+
+    // The lock isn't held while computing, so that other matchups can be
+    // looked up in the meantime.
+    let results = compute_equity_memoized(&[hero, villain], &[], &[])?;
+    let results = [results[0], results[1]];
+    table
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(hero, villain, results);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+
+    fn hand(s: &str) -> [Card; 2] {
+        let cards = parse_cards(s).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    const fn result(win_count: u64, loss_count: u64) -> EquityResult {
+        EquityResult {
+            win_count,
+            loss_count,
+            tie_count: 0,
+            count: win_count + loss_count,
+        }
+    }
+
+    #[test]
+    fn test_suit_permutations_share_an_entry() {
+        let mut table = PreflopTable::new();
+        table.insert(hand("AsKs"), hand("QdQc"), [result(1, 2), result(2, 1)]);
+
+        assert_eq!(
+            table.get(hand("AhKh"), hand("QcQs")),
+            Some([result(1, 2), result(2, 1)])
+        );
+        assert_eq!(
+            table.get(hand("KdAd"), hand("QhQs")),
+            Some([result(1, 2), result(2, 1)])
+        );
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn test_swapped_hands_share_an_entry() {
+        let mut table = PreflopTable::new();
+        table.insert(hand("AsKs"), hand("QdQc"), [result(1, 2), result(2, 1)]);
+
+        assert_eq!(
+            table.get(hand("QhQd"), hand("AcKc")),
+            Some([result(2, 1), result(1, 2)])
+        );
+    }
+
+    #[test]
+    fn test_compute_equity_canonical() {
+        let (hero, villain) = (hand("AsKs"), hand("Js9s"));
+        let results = compute_equity_canonical(hero, villain).unwrap();
+        assert_eq!(
+            results[..],
+            compute_equity_memoized(&[hero, villain], &[], &[]).unwrap()[..]
+        );
+
+        // The same matchup with the suits renamed and the hands swapped is
+        // now in the shared table, so it is answered from there.
+        let (isomorphic_hero, isomorphic_villain) = (hand("AhKh"), hand("9hJh"));
+        let cached = shared_table()
+            .lock()
+            .unwrap()
+            .get(isomorphic_villain, isomorphic_hero);
+        assert_eq!(cached, Some([results[1], results[0]]));
+        assert_eq!(
+            compute_equity_canonical(isomorphic_villain, isomorphic_hero).unwrap(),
+            [results[1], results[0]]
+        );
+
+        assert!(compute_equity_canonical(hero, hand("AsQd")).is_err());
+    }
+
+    #[test]
+    fn test_different_suit_structures_do_not_collide() {
+        let mut table = PreflopTable::new();
+        table.insert(hand("AsKs"), hand("QdQc"), [result(1, 2), result(2, 1)]);
+
+        // The queens now share a suit with the ace and king.
+        assert_eq!(table.get(hand("AsKs"), hand("QsQc")), None);
+        assert_eq!(table.get(hand("AsKh"), hand("QdQc")), None);
+    }
+}
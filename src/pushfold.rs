@@ -0,0 +1,69 @@
+use crate::Range;
+
+/// Approximate heads-up shoving ranges for the small blind, by effective
+/// stack in big blinds. Between these depths, the nearest one is used.
+const SHOVE_RANGES: [(f64, &str); 8] = [
+    (
+        2.,
+        "22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32",
+    ),
+    (
+        4.,
+        "22+, A2+, K2+, Q2+, J2+, T2s+, T5o+, 92s+, 96o+, 82s+, 86o+, 72s+, 75o+, 62s+, 65o, \
+         52s+, 42s+, 32s",
+    ),
+    (
+        6.,
+        "22+, A2+, K2+, Q2s+, Q5o+, J2s+, J7o+, T3s+, T7o+, 95s+, 97o+, 85s+, 87o, 74s+, 64s+, \
+         53s+, 43s",
+    ),
+    (
+        8.,
+        "22+, A2+, K2s+, K5o+, Q4s+, Q8o+, J6s+, J8o+, T6s+, T8o+, 96s+, 98o, 86s+, 75s+, 65s, 54s",
+    ),
+    (
+        10.,
+        "22+, A2+, K2s+, K7o+, Q6s+, Q9o+, J7s+, J9o+, T7s+, T9o, 97s+, 87s, 76s",
+    ),
+    (
+        12.,
+        "22+, A2s+, A4o+, K4s+, K9o+, Q8s+, QTo+, J8s+, JTo, T8s+, 98s",
+    ),
+    (15., "22+, A2s+, A7o+, K8s+, KTo+, Q9s+, QJo, J9s+, T9s"),
+    (20., "33+, A4s+, A9o+, KTs+, KQo, QJs"),
+];
+
+/// Returns an approximate Nash equilibrium range for shoving from the small
+/// blind heads-up with `stack_bb` big blinds, taken from a coarse table at
+/// the nearest stack depth. Shorter stacks shove wider.
+#[must_use]
+pub fn pushfold_range(stack_bb: f64) -> Range {
+    let (_, range) = SHOVE_RANGES
+        .iter()
+        .min_by(|(a, _), (b, _)| (a - stack_bb).abs().total_cmp(&(b - stack_bb).abs()))
+        .unwrap_or(&SHOVE_RANGES[0]);
+    range.parse().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorter_stacks_shove_wider() {
+        assert!(pushfold_range(5.).len() > pushfold_range(15.).len());
+        assert_eq!(pushfold_range(1.).len(), 1326);
+        assert_eq!(pushfold_range(15.4), pushfold_range(15.));
+        assert_eq!(pushfold_range(100.), pushfold_range(20.));
+
+        let ranges: Vec<Range> = SHOVE_RANGES
+            .iter()
+            .map(|(_, range)| range.parse().unwrap())
+            .collect();
+        for pair in ranges.windows(2) {
+            let (wider, narrower) = (&pair[0], &pair[1]);
+            assert!(narrower.combos().iter().all(|&combo| wider.contains(combo)));
+            assert!(narrower.len() < wider.len());
+        }
+    }
+}
@@ -0,0 +1,960 @@
+use crate::equity::remaining_deck;
+use crate::{
+    compute_equity_memoized, parse_cards, straight_cards, Card, EquityMethod, EquityResult,
+    HandCounts, HandKind, HoleCards, MonteCarlo, Number, PokerError, Suit,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the 169 classes of starting hand, ignoring the exact suits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StartingHand {
+    Pair(Number),
+    /// A high card and a lower card of the same suit.
+    Suited(Number, Number),
+    /// A high card and a lower card of different suits.
+    Offsuit(Number, Number),
+}
+
+impl StartingHand {
+    /// Returns the class a pair of hole cards belongs to.
+    #[must_use]
+    pub fn of(hand: [Card; 2]) -> Self {
+        let high = hand[0].number().max(hand[1].number());
+        let low = hand[0].number().min(hand[1].number());
+        if high == low {
+            Self::Pair(high)
+        } else if hand[0].suit() == hand[1].suit() {
+            Self::Suited(high, low)
+        } else {
+            Self::Offsuit(high, low)
+        }
+    }
+
+    /// Returns every combination of hole cards in this class: 6 for a pair, 4
+    /// when suited and 12 when offsuit.
+    #[must_use]
+    pub fn combos(self) -> Vec<[Card; 2]> {
+        let mut combos = vec![];
+        for (first, first_suit) in Suit::ALL.into_iter().enumerate() {
+            for (second, second_suit) in Suit::ALL.into_iter().enumerate() {
+                match self {
+                    Self::Pair(number) if first < second => combos.push([
+                        Card::new(first_suit, number),
+                        Card::new(second_suit, number),
+                    ]),
+                    Self::Suited(high, low) if first == second => {
+                        combos.push([Card::new(first_suit, high), Card::new(second_suit, low)]);
+                    }
+                    Self::Offsuit(high, low) if first != second => {
+                        combos.push([Card::new(first_suit, high), Card::new(second_suit, low)]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        combos
+    }
+
+    /// Returns every combination of hole cards in this class, as
+    /// [`combos`](Self::combos) does.
+    #[must_use]
+    pub fn hole_cards(self) -> Vec<HoleCards> {
+        // The two cards of a combo always differ, so none are dropped.
+        self.combos()
+            .into_iter()
+            .filter_map(|combo| HoleCards::try_from(combo).ok())
+            .collect()
+    }
+
+    /// Iterates over all 169 classes: the pairs from aces down, then for each
+    /// high card from aces down, its suited and then its offsuit classes.
+    pub fn all() -> impl Iterator<Item = Self> {
+        all_classes()
+    }
+}
+
+impl FromStr for StartingHand {
+    type Err = PokerError;
+
+    /// Parses one class, such as `"QQ"`, `"AKs"` or `"AKo"`. Unlike in a
+    /// [`Range`], `"AK"` is rejected, since it stands for two classes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_classes(s).as_deref() {
+            Some(&[class]) => Ok(class),
+            _ => Err(PokerError::InvalidRange {
+                token: s.into(),
+                position: 0,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for StartingHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pair(number) => write!(f, "{0}{0}", number.as_char()),
+            Self::Suited(high, low) => write!(f, "{}{}s", high.as_char(), low.as_char()),
+            Self::Offsuit(high, low) => write!(f, "{}{}o", high.as_char(), low.as_char()),
+        }
+    }
+}
+
+/// Returns all 169 classes: the pairs from aces down, then for each high card
+/// from aces down, its suited and then its offsuit classes.
+pub fn all_classes() -> impl Iterator<Item = StartingHand> {
+    let numbers = Number::iter_desc;
+    let pairs = numbers().map(StartingHand::Pair);
+    let unpaired = numbers().flat_map(move |high| {
+        let kickers = move || numbers().filter(move |&low| low < high);
+        kickers()
+            .map(move |low| StartingHand::Suited(high, low))
+            .chain(kickers().map(move |low| StartingHand::Offsuit(high, low)))
+    });
+    pairs.chain(unpaired)
+}
+
+/// Splits numbers, given from highest to lowest, into runs of consecutive
+/// numbers. Each run is given by its highest and lowest number.
+fn consecutive_runs(numbers: &[Number]) -> Vec<(Number, Number)> {
+    let mut runs: Vec<(Number, Number)> = vec![];
+    for &number in numbers {
+        match runs.last_mut() {
+            Some((_, lowest)) if *lowest as u8 == number as u8 + 1 => *lowest = number,
+            _ => runs.push((number, number)),
+        }
+    }
+    runs
+}
+
+/// Parses a class such as `"QQ"`, `"AKs"`, `"AKo"` or `"AK"`, where the last
+/// stands for both the suited and offsuit classes.
+fn parse_classes(s: &str) -> Option<Vec<StartingHand>> {
+    let mut chars = s.chars();
+    let first = Number::from_char(chars.next()?)?;
+    let second = Number::from_char(chars.next()?)?;
+    let suffix = chars.next();
+    if chars.next().is_some() {
+        return None;
+    }
+
+    let (high, low) = (first.max(second), first.min(second));
+    match suffix {
+        None if high == low => Some(vec![StartingHand::Pair(high)]),
+        _ if high == low => None,
+        None => Some(vec![
+            StartingHand::Suited(high, low),
+            StartingHand::Offsuit(high, low),
+        ]),
+        Some('s') => Some(vec![StartingHand::Suited(high, low)]),
+        Some('o') => Some(vec![StartingHand::Offsuit(high, low)]),
+        Some(_) => None,
+    }
+}
+
+/// Replaces the kicker of a suited or offsuit class, keeping the rest.
+const fn with_kicker(class: StartingHand, kicker: Number) -> StartingHand {
+    match class {
+        StartingHand::Pair(_) => StartingHand::Pair(kicker),
+        StartingHand::Suited(high, _) => StartingHand::Suited(high, kicker),
+        StartingHand::Offsuit(high, _) => StartingHand::Offsuit(high, kicker),
+    }
+}
+
+/// Expands every class between `from` and `to`, which must either both be
+/// pairs or share their high card and suitedness.
+fn class_span(from: StartingHand, to: StartingHand) -> Option<Vec<StartingHand>> {
+    let (lowest, highest) = match (from, to) {
+        (StartingHand::Pair(a), StartingHand::Pair(b)) => (a.min(b), a.max(b)),
+        (StartingHand::Suited(high, a), StartingHand::Suited(other, b))
+        | (StartingHand::Offsuit(high, a), StartingHand::Offsuit(other, b))
+            if high == other =>
+        {
+            (a.min(b), a.max(b))
+        }
+        _ => return None,
+    };
+    Some(
+        Number::ALL
+            .into_iter()
+            .filter(|number| (lowest..=highest).contains(number))
+            .map(|number| with_kicker(from, number))
+            .collect(),
+    )
+}
+
+/// Parses a single comma separated token of range notation.
+fn parse_token(token: &str) -> Option<Vec<[Card; 2]>> {
+    let classes = if let Some((from, to)) = token.split_once('-') {
+        let (from, to) = (parse_classes(from)?, parse_classes(to)?);
+        if from.len() != to.len() {
+            return None;
+        }
+        let mut classes = vec![];
+        for (&from, &to) in from.iter().zip(&to) {
+            classes.extend(class_span(from, to)?);
+        }
+        classes
+    } else if let Some(base) = token.strip_suffix('+') {
+        let mut classes = vec![];
+        for class in parse_classes(base)? {
+            let highest = match class {
+                StartingHand::Pair(_) => Number::Ace,
+                StartingHand::Suited(high, _) | StartingHand::Offsuit(high, _) => {
+                    Number::try_from_u8(high as u8 - 1)?
+                }
+            };
+            classes.extend(class_span(class, with_kicker(class, highest))?);
+        }
+        classes
+    } else if let Some(classes) = parse_classes(token) {
+        classes
+    } else {
+        // A specific combination of hole cards, such as `"AhKh"`.
+        let cards = parse_cards(token).ok()?;
+        return match cards[..] {
+            [first, second] if first != second => Some(vec![[first, second]]),
+            _ => None,
+        };
+    };
+    Some(classes.into_iter().flat_map(StartingHand::combos).collect())
+}
+
+/// A set of hole card combinations, written in the usual comma separated
+/// notation: `"QQ+, AKs, KQo, 98s-95s, AhKh"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Range {
+    combos: Vec<[Card; 2]>,
+}
+
+impl Range {
+    #[must_use]
+    pub fn combos(&self) -> &[[Card; 2]] {
+        &self.combos
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Builds a range holding every combination of the given classes.
+    pub fn from_classes(classes: impl IntoIterator<Item = StartingHand>) -> Self {
+        let mut range = Self::default();
+        for class in classes {
+            for combo in class.combos() {
+                range.insert(combo);
+            }
+        }
+        range
+    }
+
+    #[must_use]
+    pub fn contains(&self, combo: [Card; 2]) -> bool {
+        let [first, second] = combo;
+        self.combos.contains(&combo) || self.combos.contains(&[second, first])
+    }
+
+    /// Counts the combinations which can still be dealt, since neither of
+    /// their cards is dead, for example because it is on the board or in
+    /// another hand.
+    #[must_use]
+    pub fn combo_count(&self, dead: &[Card]) -> usize {
+        self.combos
+            .iter()
+            .filter(|combo| !shares_card(combo.as_slice(), dead))
+            .count()
+    }
+
+    /// Counts the combinations which hold `card`, which are the ones it
+    /// blocks.
+    #[must_use]
+    pub fn combos_containing(&self, card: Card) -> usize {
+        self.combos
+            .iter()
+            .filter(|combo| combo.contains(&card))
+            .count()
+    }
+
+    /// Counts the combinations which can still be dealt on the board by the
+    /// kind of hand they make with it. Before the flop, a combination is
+    /// either a pair or a high card.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PokerError::InvalidBoardSize`] if the board doesn't have
+    /// zero, three, four or five cards, or [`PokerError::DuplicateCard`] if a
+    /// board card appears twice.
+    pub fn categorize_on_board(
+        &self,
+        board: &[Card],
+    ) -> Result<BTreeMap<HandKind, u32>, PokerError> {
+        if !matches!(board.len(), 0 | 3..=5) {
+            return Err(PokerError::InvalidBoardSize(board.len()));
+        }
+        remaining_deck(&[], board, &[])?;
+        Ok(categorize(self, board))
+    }
+
+    /// Returns the classes which have every one of their combinations in the
+    /// range, with pairs first and then from the highest card down.
+    #[must_use]
+    pub fn classes(&self) -> Vec<StartingHand> {
+        all_classes()
+            .filter(|class| class.combos().into_iter().all(|combo| self.contains(combo)))
+            .collect()
+    }
+
+    fn insert(&mut self, combo: [Card; 2]) {
+        if !self.contains(combo) {
+            self.combos.push(combo);
+        }
+    }
+}
+
+impl From<[Card; 2]> for Range {
+    /// Builds a range holding a single combination.
+    fn from(combo: [Card; 2]) -> Self {
+        Self {
+            combos: vec![combo],
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = PokerError;
+
+    /// Parses a range, reporting the first malformed token along with the
+    /// character offset it starts at.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Self::default();
+        if s.trim().is_empty() {
+            return Ok(range);
+        }
+
+        let mut offset = 0;
+        for token in s.split(',') {
+            let trimmed = token.trim();
+            let position = offset + (token.chars().count() - token.trim_start().chars().count());
+            offset += token.chars().count() + 1;
+
+            let combos = parse_token(trimmed).ok_or_else(|| PokerError::InvalidRange {
+                token: trimmed.into(),
+                position,
+            })?;
+            for combo in combos {
+                range.insert(combo);
+            }
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for Range {
+    /// Writes the range in compact notation, such as `"QQ+, ATs-A8s, KQo"`,
+    /// listing any combinations which don't make up a whole class one by one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let classes = self.classes();
+        let mut tokens = vec![];
+
+        let pairs: Vec<Number> = classes
+            .iter()
+            .filter_map(|&class| match class {
+                StartingHand::Pair(number) => Some(number),
+                _ => None,
+            })
+            .collect();
+        for (top, bottom) in consecutive_runs(&pairs) {
+            let (top, bottom) = (top.as_char(), bottom.as_char());
+            tokens.push(if top == bottom {
+                format!("{top}{top}")
+            } else if top == 'A' {
+                format!("{bottom}{bottom}+")
+            } else {
+                format!("{top}{top}-{bottom}{bottom}")
+            });
+        }
+
+        for high in Number::iter_desc().take_while(|&high| high > Number::Two) {
+            let kickers = |suited: bool| -> Vec<Number> {
+                classes
+                    .iter()
+                    .filter_map(|&class| match class {
+                        StartingHand::Suited(h, low) if suited && h == high => Some(low),
+                        StartingHand::Offsuit(h, low) if !suited && h == high => Some(low),
+                        _ => None,
+                    })
+                    .collect()
+            };
+            let (suited, offsuit) = (kickers(true), kickers(false));
+
+            // Write both at once, such as `"AQ+"`, when they match.
+            let groups = if suited == offsuit {
+                vec![("", suited)]
+            } else {
+                vec![("s", suited), ("o", offsuit)]
+            };
+            for (suffix, kickers) in groups {
+                for (top, bottom) in consecutive_runs(&kickers) {
+                    let (h, t, b) = (high.as_char(), top.as_char(), bottom.as_char());
+                    tokens.push(if top == bottom {
+                        format!("{h}{t}{suffix}")
+                    } else if top as u8 + 1 == high as u8 {
+                        format!("{h}{b}{suffix}+")
+                    } else {
+                        format!("{h}{t}{suffix}-{h}{b}{suffix}")
+                    });
+                }
+            }
+        }
+
+        for &[first, second] in &self.combos {
+            if !classes.contains(&StartingHand::of([first, second])) {
+                tokens.push(format!("{first}{second}"));
+            }
+        }
+
+        write!(f, "{}", tokens.join(", "))
+    }
+}
+
+/// The equity of one range against a single class of the opposing range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClassEquity {
+    pub hand: StartingHand,
+    /// The number of combinations of this class in the range.
+    pub combos: usize,
+    /// How many of those combinations can never be dealt, because they share a
+    /// card with the board, the dead cards or every opposing combination.
+    pub blocked: usize,
+    /// The results of the opposing range against this class.
+    pub result: EquityResult,
+}
+
+/// The equity of a hero range against a villain range, from the hero's point
+/// of view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeEquity {
+    pub result: EquityResult,
+    /// The hero's results against each class of the villain's range.
+    pub classes: Vec<ClassEquity>,
+}
+
+/// Counts the combinations of `range` by the kind of hand they make, as
+/// [`Range::categorize_on_board`] does, on a board which is known to be
+/// valid.
+pub fn categorize(range: &Range, board: &[Card]) -> BTreeMap<HandKind, u32> {
+    let mut kinds = BTreeMap::new();
+    for combo in &range.combos {
+        if shares_card(combo, board) {
+            continue;
+        }
+        let kind = if board.is_empty() {
+            if combo[0].number() == combo[1].number() {
+                HandKind::Pair
+            } else {
+                HandKind::HighCard
+            }
+        } else {
+            HandCounts::new(&[combo.as_slice(), board].concat())
+                .best_high()
+                .kind()
+        };
+        *kinds.entry(kind).or_default() += 1;
+    }
+    kinds
+}
+
+fn shares_card(a: &[Card], b: &[Card]) -> bool {
+    a.iter().any(|card| b.contains(card))
+}
+
+/// Computes the equity of every hero combination against every villain
+/// combination which can be dealt alongside it, weighting each matchup
+/// equally.
+///
+/// With [`EquityMethod::MonteCarlo`] the samples are split evenly between the
+/// matchups, each of which is seeded from the given seed.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if a card is
+/// used more than once on the board and in the dead cards.
+pub fn compute_range_equity(
+    hero: &Range,
+    villain: &Range,
+    board: &[Card],
+    dead: &[Card],
+    method: EquityMethod,
+) -> Result<RangeEquity, PokerError> {
+    remaining_deck(&[], board, dead)?;
+    let live = |combo: &[Card; 2]| !shares_card(combo, board) && !shares_card(combo, dead);
+
+    let mut classes: BTreeMap<StartingHand, ClassEquity> = BTreeMap::new();
+    let mut matchups = vec![];
+    for villain_combo in villain.combos() {
+        let hand = StartingHand::of(*villain_combo);
+        let class = classes.entry(hand).or_insert_with(|| ClassEquity {
+            hand,
+            combos: 0,
+            blocked: 0,
+            result: EquityResult::default(),
+        });
+        class.combos += 1;
+
+        let before = matchups.len();
+        if live(villain_combo) {
+            for hero_combo in hero.combos().iter().filter(|combo| live(combo)) {
+                if !shares_card(hero_combo, villain_combo) {
+                    matchups.push([*hero_combo, *villain_combo]);
+                }
+            }
+        }
+        if matchups.len() == before {
+            class.blocked += 1;
+        }
+    }
+
+    let mut rng = match method {
+        EquityMethod::Exhaustive => None,
+        EquityMethod::MonteCarlo { seed, .. } => {
+            Some(StdRng::seed_from_u64(seed.unwrap_or_else(rand::random)))
+        }
+    };
+
+    let mut result = EquityResult::default();
+    let matchup_count = matchups.len() as u64;
+    for (index, hands) in (0..).zip(&matchups) {
+        let hero_result = match (method, rng.as_mut()) {
+            (EquityMethod::MonteCarlo { samples, .. }, Some(rng)) => {
+                let mut simulation = MonteCarlo::new(hands, board, dead, Some(rng.gen()))?;
+                let extra = u64::from(index < samples % matchup_count);
+                simulation.run(samples / matchup_count + extra);
+                simulation.results()[0]
+            }
+            _ => compute_equity_memoized(hands, board, dead)?[0],
+        };
+
+        result += hero_result;
+        if let Some(class) = classes.get_mut(&StartingHand::of(hands[1])) {
+            class.result += hero_result;
+        }
+    }
+
+    Ok(RangeEquity {
+        result,
+        classes: classes.into_values().collect(),
+    })
+}
+
+/// Computes the hero's equity with each of their combinations against the
+/// whole villain range, such as for coloring the cells of a grid of starting
+/// hands.
+///
+/// Each equity is the same as [`compute_range_equity`] computes exactly with
+/// that combination as the hero's range. Combinations which can't be dealt
+/// against any villain combination, because of the board, the dead cards or
+/// the villain's cards, are left out. With the `rayon` feature, the
+/// combinations are computed in parallel.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if a card is
+/// used more than once on the board and in the dead cards.
+pub fn range_equity_grid(
+    hero: &Range,
+    villain: &Range,
+    board: &[Card],
+    dead: &[Card],
+) -> Result<HashMap<[Card; 2], f64>, PokerError> {
+    remaining_deck(&[], board, dead)?;
+    let cell = |&combo: &[Card; 2]| {
+        let equity = compute_range_equity(
+            &Range::from(combo),
+            villain,
+            board,
+            dead,
+            EquityMethod::Exhaustive,
+        )?;
+        Ok((equity.result.count > 0).then(|| (combo, equity.result.equity())))
+    };
+
+    #[cfg(feature = "rayon")]
+    let cells: Vec<Option<([Card; 2], f64)>> = hero
+        .combos()
+        .par_iter()
+        .map(cell)
+        .collect::<Result<_, PokerError>>()?;
+    #[cfg(not(feature = "rayon"))]
+    let cells: Vec<Option<([Card; 2], f64)>> = hero
+        .combos()
+        .iter()
+        .map(cell)
+        .collect::<Result<_, PokerError>>()?;
+    Ok(cells.into_iter().flatten().collect())
+}
+
+/// Returns whether a hand has already made a pair or better with the board,
+/// rather than needing more cards to improve. A pair only counts when one of
+/// the hole cards plays, but a straight or flush counts even on the board.
+fn is_made_hand(hand: [Card; 2], board: &[Card]) -> bool {
+    let paired = hand[0].number() == hand[1].number()
+        || board
+            .iter()
+            .any(|card| hand.iter().any(|hole| hole.number() == card.number()));
+
+    let mut numbers = 0;
+    let mut count_by_suit = [0; 4];
+    for card in hand.iter().chain(board) {
+        numbers |= card.number().as_bit();
+        count_by_suit[card.suit() as usize] += 1;
+    }
+    paired || straight_cards(numbers).is_some() || count_by_suit.iter().any(|&count| count >= 5)
+}
+
+/// Returns the hero's raw equity against the villain, followed by a rough
+/// estimate of the equity the hero actually realizes.
+///
+/// The estimate assumes that draws often don't get to showdown, so the equity
+/// of every hero combination which hasn't made a pair or better on the board
+/// is multiplied by `factor`. A factor of 1 realizes all of the raw equity.
+/// Equity is computed exactly, weighting each matchup equally.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, or if it uses
+/// the same card more than once.
+#[allow(clippy::cast_precision_loss)]
+pub fn raw_vs_realized(
+    hero: &Range,
+    villain: &Range,
+    board: &[Card],
+    factor: f64,
+) -> Result<(f64, f64), PokerError> {
+    remaining_deck(&[], board, &[])?;
+
+    let (mut raw, mut realized, mut count) = (0., 0., 0);
+    for &hero_combo in hero.combos() {
+        if shares_card(&hero_combo, board) {
+            continue;
+        }
+        let scale = if is_made_hand(hero_combo, board) {
+            1.
+        } else {
+            factor
+        };
+        for villain_combo in villain.combos() {
+            if shares_card(villain_combo, board) || shares_card(villain_combo, &hero_combo) {
+                continue;
+            }
+            let result = compute_equity_memoized(&[hero_combo, *villain_combo], board, &[])?[0];
+            let won = result.win_count as f64 + result.tie_count as f64 / 2.;
+            raw += won;
+            realized += won * scale;
+            count += result.count;
+        }
+    }
+
+    if count == 0 {
+        return Ok((0., 0.));
+    }
+    Ok((raw / count as f64, realized / count as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(s: &str) -> Range {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_starting_hand() {
+        for class in StartingHand::all() {
+            assert_eq!(class.to_string().parse(), Ok(class));
+        }
+        assert_eq!(StartingHand::all().count(), 169);
+        assert_eq!(
+            "QJo".parse(),
+            Ok(StartingHand::Offsuit(Number::Queen, Number::Jack))
+        );
+        assert_eq!("77".parse(), Ok(StartingHand::Pair(Number::Seven)));
+
+        for invalid in ["AK", "77s", "AKx", "AKs+", "A"] {
+            assert_eq!(
+                invalid.parse::<StartingHand>(),
+                Err(PokerError::InvalidRange {
+                    token: invalid.into(),
+                    position: 0
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_hole_cards_of_class() {
+        let class: StartingHand = "AKs".parse().unwrap();
+        let hole_cards = class.hole_cards();
+        assert_eq!(hole_cards.len(), 4);
+        assert!(hole_cards
+            .iter()
+            .all(|hole| hole.is_suited() && hole.class() == class));
+        assert_eq!(StartingHand::Pair(Number::Two).hole_cards().len(), 6);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(range("QQ+").len(), 18);
+        assert_eq!(range("AK").len(), 16);
+        assert_eq!(range("AKs, AKo").len(), 16);
+        assert_eq!(range("ATs+").len(), 16);
+        assert_eq!(range("88-55").len(), 24);
+        assert_eq!(range("KQs-K9s").len(), 16);
+        let cards = parse_cards("AhKh").unwrap();
+        assert_eq!(range("AhKh").combos(), &[[cards[0], cards[1]]]);
+
+        // Overlapping tokens only count each combination once.
+        assert_eq!(range("AKs, AhKh, KhAh").len(), 4);
+    }
+
+    #[test]
+    fn test_display_range() {
+        let original = range("QQ+, AK, ATs-A8s, 76s, Kh9c");
+        assert_eq!(original.to_string(), "QQ+, AKs, ATs-A8s, AKo, 76s, Kh9c");
+        assert_eq!(range("AQ+, 55-33, KK").to_string(), "KK, 55-33, AQ+");
+        assert_eq!(range("").to_string(), "");
+
+        let reparsed = range(&original.to_string());
+        assert_eq!(reparsed.len(), original.len());
+        assert_eq!(reparsed.classes(), original.classes());
+    }
+
+    #[test]
+    fn test_classes_ignore_partial_classes() {
+        let range = range("JJ, AhKh, AdKd");
+        assert_eq!(range.classes(), vec![StartingHand::Pair(Number::Jack)]);
+        assert_eq!(
+            Range::from_classes(range.classes()).len(),
+            StartingHand::Pair(Number::Jack).combos().len()
+        );
+        assert_eq!(all_classes().count(), 169);
+    }
+
+    #[test]
+    fn test_combo_count() {
+        let board = parse_cards("Qh7h2c").unwrap();
+        assert_eq!(range("QQ+").combo_count(&board), 15);
+        assert_eq!(range("AKs").combo_count(&board), 4);
+        assert_eq!(range("AKs").combo_count(&[]), 4);
+
+        // Every suit of KQs shares a card with the board or the dead cards.
+        let board = parse_cards("KhQd9c").unwrap();
+        assert_eq!(range("KQs").combo_count(&board), 2);
+        let dead = [board, parse_cards("KcQs").unwrap()].concat();
+        assert_eq!(range("KQs").combo_count(&dead), 0);
+        assert_eq!(range("KQs, KQo").combo_count(&dead), 4);
+
+        // Holding an ace blocks half the aces and a quarter of the big slicks.
+        let range = range("QQ+, AK");
+        assert_eq!(range.combo_count(&[]), 34);
+        let ace = parse_cards("Ah").unwrap();
+        assert_eq!(range.combos_containing(ace[0]), 7);
+        assert_eq!(range.combo_count(&ace), 27);
+        assert_eq!(range.combo_count(&parse_cards("Qh").unwrap()), 31);
+    }
+
+    #[test]
+    fn test_categorize_on_board() {
+        let board = parse_cards("Qh7h2c").unwrap();
+        let kinds = range("QQ+, AK, 77, 22, AhJh")
+            .categorize_on_board(&board)
+            .unwrap();
+        assert_eq!(
+            kinds,
+            BTreeMap::from([
+                (HandKind::HighCard, 17),
+                (HandKind::Pair, 12),
+                (HandKind::ThreeOfAKind, 9),
+            ])
+        );
+
+        let kinds = range("AhJh, KhJh").categorize_on_board(&parse_cards("Qh7h2c3h").unwrap());
+        assert_eq!(kinds, Ok(BTreeMap::from([(HandKind::Flush, 2)])));
+
+        let kinds = range("QQ+, AK").categorize_on_board(&[]);
+        assert_eq!(
+            kinds,
+            Ok(BTreeMap::from([
+                (HandKind::HighCard, 16),
+                (HandKind::Pair, 18)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_categorize_on_invalid_boards() {
+        // These used to evaluate four cards as a hand, and panic.
+        assert_eq!(
+            range("AK").categorize_on_board(&parse_cards("AdKd").unwrap()),
+            Err(PokerError::InvalidBoardSize(2))
+        );
+        assert_eq!(
+            range("AA").categorize_on_board(&parse_cards("AdAc").unwrap()),
+            Err(PokerError::InvalidBoardSize(2))
+        );
+        assert_eq!(
+            range("AA").categorize_on_board(&parse_cards("Ad").unwrap()),
+            Err(PokerError::InvalidBoardSize(1))
+        );
+        assert_eq!(
+            range("AA").categorize_on_board(&parse_cards("Ad7c2s3s4s5s").unwrap()),
+            Err(PokerError::InvalidBoardSize(6))
+        );
+        assert_eq!(
+            range("AA").categorize_on_board(&parse_cards("Ad7c7c").unwrap()),
+            Err(PokerError::DuplicateCard(parse_cards("7c").unwrap()[0]))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_the_token() {
+        assert_eq!(
+            "QQ+, AXs".parse::<Range>(),
+            Err(PokerError::InvalidRange {
+                token: "AXs".into(),
+                position: 5,
+            })
+        );
+        assert_eq!(
+            "AA,KQs-J9s".parse::<Range>(),
+            Err(PokerError::InvalidRange {
+                token: "KQs-J9s".into(),
+                position: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_starting_hand_classes() {
+        let hand = parse_cards("KhAh").unwrap();
+        let class = StartingHand::of([hand[0], hand[1]]);
+        assert_eq!(class, StartingHand::Suited(Number::Ace, Number::King));
+        assert_eq!(class.to_string(), "AKs");
+        assert_eq!(StartingHand::Pair(Number::Ten).combos().len(), 6);
+        assert_eq!(
+            StartingHand::Offsuit(Number::Ace, Number::King)
+                .combos()
+                .len(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_range_equity_reports_blocked_combos() {
+        let board = parse_cards("2c 7d 9s").unwrap();
+        let equity = compute_range_equity(
+            &range("AhKh"),
+            &range("QQ+, AK"),
+            &board,
+            &[],
+            EquityMethod::Exhaustive,
+        )
+        .unwrap();
+
+        let blocked: Vec<_> = equity
+            .classes
+            .iter()
+            .map(|class| (class.hand.to_string(), class.combos, class.blocked))
+            .collect();
+        assert_eq!(
+            blocked,
+            vec![
+                ("QQ".into(), 6, 0),
+                ("KK".into(), 6, 3),
+                ("AA".into(), 6, 3),
+                ("AKs".into(), 4, 1),
+                ("AKo".into(), 12, 6),
+            ]
+        );
+
+        // Every live matchup deals from the same 45 remaining cards.
+        assert_eq!(equity.result.count, 21 * 990);
+    }
+
+    #[test]
+    fn test_range_equity_grid() {
+        let board = parse_cards("Ks 7d 2c").unwrap();
+        let (hero, villain) = (range("AA, KK, AKs"), range("QQ+, AhKh"));
+        let grid = range_equity_grid(&hero, &villain, &board, &[]).unwrap();
+
+        // The king of spades is on the board, so AsKs and three of the kings
+        // are left out.
+        assert_eq!(grid.len(), hero.combos().len() - 4);
+        let ks = board[0];
+        assert!(grid.keys().all(|combo| !combo.contains(&ks)));
+
+        // Each cell is the equity of its combination alone.
+        let mut sequential = HashMap::new();
+        for &combo in hero.combos() {
+            let equity = compute_range_equity(
+                &Range::from(combo),
+                &villain,
+                &board,
+                &[],
+                EquityMethod::Exhaustive,
+            )
+            .unwrap();
+            if equity.result.count > 0 {
+                sequential.insert(combo, equity.result.equity());
+            }
+        }
+        assert_eq!(grid, sequential);
+
+        // Top set is far ahead, and well ahead of top pair.
+        let kings = [
+            Card::new(Suit::Hearts, Number::King),
+            Card::new(Suit::Clubs, Number::King),
+        ];
+        assert!(grid[&kings] > 0.9);
+        let ace_king = range("AcKc").combos()[0];
+        assert!(grid[&ace_king] < grid[&kings]);
+
+        // The villain holds the only ace of hearts and king of hearts which
+        // could be dealt with AhKh.
+        let grid = range_equity_grid(&range("AhKh"), &range("AhKh"), &board, &[]).unwrap();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_raw_vs_realized() {
+        let board = parse_cards("Qh7h2c").unwrap();
+        let (hero, villain) = (range("AhKh, 99"), range("KK"));
+
+        let (raw, realized) = raw_vs_realized(&hero, &villain, &board, 1.).unwrap();
+        assert!(raw > 0.05 && raw < 0.3);
+        assert!((raw - realized).abs() < 1e-12);
+
+        // Only the flush draw loses equity, since the nines are already made.
+        let (_, realized) = raw_vs_realized(&hero, &villain, &board, 0.5).unwrap();
+        assert!(realized < raw);
+        assert!(realized > raw / 2.);
+
+        assert!(is_made_hand(range("99").combos()[0], &board));
+        assert!(is_made_hand(range("Q8s").combos()[0], &board));
+        assert!(!is_made_hand(range("AhKh").combos()[0], &board));
+        assert!(is_made_hand(
+            range("AhKh").combos()[0],
+            &parse_cards("Qh7h2h").unwrap()
+        ));
+    }
+}
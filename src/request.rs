@@ -0,0 +1,628 @@
+use crate::equity::remaining_deck;
+use crate::{
+    compute_equity, compute_range_equity, parse_cards, runout_count, Card, EquityMethod,
+    EquityResult, Hand, MonteCarlo, PokerError, Range,
+};
+
+/// One player of a scenario, holding either known hole cards or a range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Player {
+    Hand([Card; 2]),
+    Range(Range),
+}
+
+/// A scenario which has been checked to make sense, along with how its
+/// equity should be computed. Requests are put together with
+/// [`EquityRequest::new`].
+///
+/// With the `serde` feature, a request is serialized as its
+/// [`EquityRequestBuilder`], and is checked by
+/// [`build`](EquityRequestBuilder::build) when it is deserialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "EquityRequestBuilder", into = "EquityRequestBuilder")
+)]
+pub struct EquityRequest {
+    players: Vec<Player>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    method: EquityMethod,
+}
+
+/// The results of an [`EquityRequest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EquityOutcome {
+    /// The results for each player, in the order they were added.
+    pub results: Vec<EquityResult>,
+    /// How the results were computed. Sampled results include the seed which
+    /// was used, so that they can be reproduced. A request for at least as
+    /// many samples as there are runouts is enumerated instead, and reports
+    /// [`EquityMethod::Exhaustive`].
+    pub method: EquityMethod,
+}
+
+/// Puts together an [`EquityRequest`] a piece at a time. Nothing is checked
+/// until [`build`](Self::build), apart from whether the notation parses.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct EquityRequestBuilder {
+    players: Vec<Player>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    samples: Option<u64>,
+    seed: Option<u64>,
+}
+
+impl EquityRequestBuilder {
+    /// Adds a player holding a hand such as `"AsKc"`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the hand isn't two cards.
+    pub fn hand(self, hand: &str) -> Result<Self, PokerError> {
+        let hand: Hand = hand.parse()?;
+        Ok(self.hand_cards(hand.cards()))
+    }
+
+    /// Adds a player holding the given hole cards.
+    #[must_use]
+    pub fn hand_cards(mut self, hand: [Card; 2]) -> Self {
+        self.players.push(Player::Hand(hand));
+        self
+    }
+
+    /// Adds a player holding a range such as `"QQ+, AKs"`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the range notation can't be parsed.
+    pub fn hand_range(self, range: &str) -> Result<Self, PokerError> {
+        Ok(self.range(range.parse()?))
+    }
+
+    /// Adds a player holding the given range.
+    #[must_use]
+    pub fn range(mut self, range: Range) -> Self {
+        self.players.push(Player::Range(range));
+        self
+    }
+
+    /// Sets the board to cards such as `"7d8d2c"`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cards can't be parsed.
+    pub fn board(self, board: &str) -> Result<Self, PokerError> {
+        Ok(self.board_cards(&parse_cards(board)?))
+    }
+
+    #[must_use]
+    pub fn board_cards(mut self, board: &[Card]) -> Self {
+        self.board = board.to_vec();
+        self
+    }
+
+    /// Sets the cards which are known to be out of the deck.
+    #[must_use]
+    pub fn dead(mut self, dead: &[Card]) -> Self {
+        self.dead = dead.to_vec();
+        self
+    }
+
+    /// Estimates the equity from `samples` random runouts, rather than
+    /// enumerating every runout.
+    #[must_use]
+    pub const fn monte_carlo(mut self, samples: u64) -> Self {
+        self.samples = Some(samples);
+        self
+    }
+
+    /// Fixes the seed of a Monte Carlo estimate, so that it can be
+    /// reproduced.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Checks the whole scenario at once and turns it into a request.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    ///
+    /// - there are fewer than two players,
+    /// - a range is used with more than two players,
+    /// - a range has no combinations left once the known cards are removed,
+    /// - the board has more than five cards,
+    /// - a card is used more than once,
+    /// - there aren't enough cards left to deal every player and the board,
+    /// - or a seed is set without Monte Carlo, or Monte Carlo has no samples.
+    pub fn build(self) -> Result<EquityRequest, PokerError> {
+        let Self {
+            players,
+            board,
+            dead,
+            samples,
+            seed,
+        } = self;
+
+        if players.len() < 2 {
+            return Err(PokerError::InvalidRequest(format!(
+                "at least two players are needed, but found {}",
+                players.len()
+            )));
+        }
+        let has_range = players
+            .iter()
+            .any(|player| matches!(player, Player::Range(_)));
+        if has_range && players.len() > 2 {
+            return Err(PokerError::InvalidRequest(
+                "ranges can only be used heads-up".into(),
+            ));
+        }
+
+        let hands: Vec<[Card; 2]> = players
+            .iter()
+            .filter_map(|player| match player {
+                Player::Hand(hand) => Some(*hand),
+                Player::Range(_) => None,
+            })
+            .collect();
+        let deck = remaining_deck(&hands, &board, &dead)?;
+        let known: Vec<Card> = [hands.concat(), board.clone(), dead.clone()].concat();
+        for (index, player) in players.iter().enumerate() {
+            if let Player::Range(range) = player {
+                if range.combo_count(&known) == 0 {
+                    return Err(PokerError::InvalidRequest(format!(
+                        "player {} has no combinations left in their range",
+                        index + 1
+                    )));
+                }
+            }
+        }
+
+        let needed = (players.len() - hands.len()) * 2 + 5 - board.len();
+        if needed > deck.len() {
+            return Err(PokerError::NotEnoughCards {
+                needed,
+                remaining: deck.len(),
+            });
+        }
+
+        let method = match (samples, seed) {
+            (None, None) => EquityMethod::Exhaustive,
+            (None, Some(_)) => {
+                return Err(PokerError::InvalidRequest(
+                    "a seed can only be used with Monte Carlo".into(),
+                ))
+            }
+            (Some(0), _) => {
+                return Err(PokerError::InvalidRequest(
+                    "Monte Carlo needs at least one sample".into(),
+                ))
+            }
+            (Some(samples), seed) => EquityMethod::MonteCarlo { samples, seed },
+        };
+        Ok(EquityRequest {
+            players,
+            board,
+            dead,
+            method,
+        })
+    }
+}
+
+impl TryFrom<EquityRequestBuilder> for EquityRequest {
+    type Error = PokerError;
+
+    fn try_from(builder: EquityRequestBuilder) -> Result<Self, Self::Error> {
+        builder.build()
+    }
+}
+
+impl From<EquityRequest> for EquityRequestBuilder {
+    fn from(request: EquityRequest) -> Self {
+        let (samples, seed) = match request.method {
+            EquityMethod::Exhaustive => (None, None),
+            EquityMethod::MonteCarlo { samples, seed } => (Some(samples), seed),
+        };
+        Self {
+            players: request.players,
+            board: request.board,
+            dead: request.dead,
+            samples,
+            seed,
+        }
+    }
+}
+
+/// Swaps the wins and losses of a result, turning the hero's result into
+/// the villain's.
+const fn mirror(result: EquityResult) -> EquityResult {
+    EquityResult {
+        win_count: result.loss_count,
+        loss_count: result.win_count,
+        tie_count: result.tie_count,
+        count: result.count,
+    }
+}
+
+impl EquityRequest {
+    /// Starts putting together a request, which is checked once it is
+    /// complete by [`EquityRequestBuilder::build`].
+    #[must_use]
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> EquityRequestBuilder {
+        EquityRequestBuilder::default()
+    }
+
+    /// The same as [`EquityRequest::new`].
+    #[must_use]
+    pub fn builder() -> EquityRequestBuilder {
+        Self::new()
+    }
+
+    #[must_use]
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    #[must_use]
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    #[must_use]
+    pub fn dead(&self) -> &[Card] {
+        &self.dead
+    }
+
+    #[must_use]
+    pub const fn method(&self) -> EquityMethod {
+        self.method
+    }
+
+    /// Returns every player's hole cards, or `None` if any of them holds a
+    /// range.
+    #[must_use]
+    pub fn hands(&self) -> Option<Vec<[Card; 2]>> {
+        self.players
+            .iter()
+            .map(|player| match player {
+                Player::Hand(hand) => Some(*hand),
+                Player::Range(_) => None,
+            })
+            .collect()
+    }
+
+    /// Computes the equity of every player, with whichever of
+    /// [`compute_equity`], [`MonteCarlo`] and [`compute_range_equity`] suits
+    /// the request.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, if any card
+    /// is used more than once, or if there aren't enough cards left to finish
+    /// the board. These are checked again while counting the runouts, to
+    /// decide whether sampling is worthwhile, and by the engine the request is
+    /// dispatched to, but [`EquityRequestBuilder::build`] already rejects
+    /// them, so a request which was built successfully doesn't fail.
+    pub fn compute(&self) -> Result<EquityOutcome, PokerError> {
+        let (board, dead) = (&self.board, &self.dead);
+        // Sampling at least as many runouts as there are is both slower and
+        // less accurate than counting each of them once.
+        let method = match self.method {
+            EquityMethod::MonteCarlo { samples, .. } if samples >= self.exhaustive_runouts()? => {
+                EquityMethod::Exhaustive
+            }
+            method => method,
+        };
+
+        if let Some(hands) = self.hands() {
+            return match method {
+                EquityMethod::Exhaustive => Ok(EquityOutcome {
+                    results: compute_equity(&hands, board, dead)?,
+                    method,
+                }),
+                EquityMethod::MonteCarlo { samples, seed } => {
+                    let mut simulation = MonteCarlo::new(&hands, board, dead, seed)?;
+                    simulation.run(samples);
+                    Ok(EquityOutcome {
+                        results: simulation.results().to_vec(),
+                        method: EquityMethod::MonteCarlo {
+                            samples,
+                            seed: Some(simulation.seed()),
+                        },
+                    })
+                }
+            };
+        }
+
+        let method = match method {
+            EquityMethod::MonteCarlo { samples, seed } => EquityMethod::MonteCarlo {
+                samples,
+                seed: Some(seed.unwrap_or_else(rand::random)),
+            },
+            EquityMethod::Exhaustive => EquityMethod::Exhaustive,
+        };
+        let [hero, villain] = self.ranges();
+        let hero = compute_range_equity(&hero, &villain, board, dead, method)?.result;
+        Ok(EquityOutcome {
+            results: vec![hero, mirror(hero)],
+            method,
+        })
+    }
+
+    /// Returns every player as a range, with known hole cards as a range of
+    /// one combination. Only requests with two players can hold ranges.
+    fn ranges(&self) -> [Range; 2] {
+        [&self.players[0], &self.players[1]].map(|player| match player {
+            Player::Hand(hand) => Range::from(*hand),
+            Player::Range(range) => range.clone(),
+        })
+    }
+
+    /// Returns how many runouts enumerating the request would count, over
+    /// every matchup of the players' ranges.
+    fn exhaustive_runouts(&self) -> Result<u64, PokerError> {
+        let (board, dead) = (&self.board, &self.dead);
+        if let Some(hands) = self.hands() {
+            return runout_count(&hands, board, dead);
+        }
+
+        let [hero, villain] = self.ranges();
+        let live = |combo: &[Card; 2]| {
+            !combo
+                .iter()
+                .any(|card| board.contains(card) || dead.contains(card))
+        };
+        let mut matchups = hero
+            .combos()
+            .iter()
+            .filter(|combo| live(combo))
+            .flat_map(|&hero| {
+                villain
+                    .combos()
+                    .iter()
+                    .filter(move |villain| {
+                        live(villain) && !villain.iter().any(|card| hero.contains(card))
+                    })
+                    .map(move |&villain| [hero, villain])
+            });
+        let Some(first) = matchups.next() else {
+            return Ok(0);
+        };
+        // Every matchup leaves the same number of cards in the deck.
+        Ok((1 + matchups.count() as u64) * runout_count(&first, board, dead)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(builder: Result<EquityRequestBuilder, PokerError>) -> PokerError {
+        builder.and_then(EquityRequestBuilder::build).unwrap_err()
+    }
+
+    fn invalid_request(builder: Result<EquityRequestBuilder, PokerError>) -> String {
+        match error(builder) {
+            PokerError::InvalidRequest(message) => message,
+            other => panic!("expected an invalid request, but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_exhaustive_matches_compute_equity() {
+        assert_eq!(EquityRequest::new(), EquityRequest::builder());
+        let request = EquityRequest::new()
+            .hand("AhQc")
+            .and_then(|builder| builder.hand("KsKd"))
+            .and_then(|builder| builder.board("2h7hJhKc"))
+            .and_then(EquityRequestBuilder::build)
+            .unwrap();
+        assert_eq!(request.method(), EquityMethod::Exhaustive);
+        assert_eq!(request.board().len(), 4);
+
+        let outcome = request.compute().unwrap();
+        let hands = request.hands().unwrap();
+        assert_eq!(
+            outcome.results,
+            compute_equity(&hands, request.board(), &[]).unwrap()
+        );
+        assert_eq!(outcome.results[0].win_count, 11);
+    }
+
+    #[test]
+    fn test_monte_carlo_reports_its_seed() {
+        let build = |seed: Option<u64>| -> Result<EquityRequest, PokerError> {
+            let builder = EquityRequest::builder()
+                .hand("AsKc")?
+                .hand("QdQh")?
+                .hand("7s8s")?
+                .monte_carlo(2_000);
+            seed.map_or(builder.clone(), |seed| builder.seed(seed))
+                .build()
+        };
+
+        let seeded = build(Some(42)).unwrap().compute().unwrap();
+        assert_eq!(
+            seeded.method,
+            EquityMethod::MonteCarlo {
+                samples: 2_000,
+                seed: Some(42)
+            }
+        );
+        assert_eq!(seeded, build(Some(42)).unwrap().compute().unwrap());
+        assert!(seeded.results.iter().all(|result| result.count == 2_000));
+
+        let unseeded = build(None).unwrap().compute().unwrap();
+        let EquityMethod::MonteCarlo {
+            seed: Some(seed), ..
+        } = unseeded.method
+        else {
+            panic!("expected the seed to be reported");
+        };
+        assert_eq!(unseeded, build(Some(seed)).unwrap().compute().unwrap());
+    }
+
+    #[test]
+    fn test_oversampling_falls_back_to_exhaustive() {
+        // There are only 44 rivers, so sampling more of them is pointless.
+        let builder = EquityRequest::builder()
+            .hand("AhQc")
+            .and_then(|builder| builder.hand("KsKd"))
+            .and_then(|builder| builder.board("2h7hJhKc"))
+            .unwrap();
+        let exact = builder.clone().build().unwrap().compute().unwrap();
+        let oversampled = builder
+            .clone()
+            .monte_carlo(44)
+            .build()
+            .unwrap()
+            .compute()
+            .unwrap();
+        assert_eq!(oversampled, exact);
+        assert_eq!(oversampled.method, EquityMethod::Exhaustive);
+
+        let sampled = builder.monte_carlo(43).build().unwrap().compute().unwrap();
+        assert!(matches!(sampled.method, EquityMethod::MonteCarlo { .. }));
+
+        // Kings against two combinations of aces have 2 * 44 rivers.
+        let builder = EquityRequest::builder()
+            .hand("KsKd")
+            .and_then(|builder| builder.hand_range("AhAc, AsAd"))
+            .and_then(|builder| builder.board("2h7hJh5c"))
+            .unwrap();
+        let exact = builder.clone().build().unwrap().compute().unwrap();
+        let oversampled = builder.monte_carlo(88).build().unwrap().compute().unwrap();
+        assert_eq!(oversampled, exact);
+        assert_eq!(oversampled.results[0].count, 88);
+    }
+
+    #[test]
+    fn test_hand_against_range() {
+        let request = EquityRequest::builder()
+            .hand("AhKh")
+            .and_then(|builder| builder.hand_range("QQ+, AK"))
+            .and_then(|builder| builder.board("2c7d9s"))
+            .and_then(EquityRequestBuilder::build)
+            .unwrap();
+        assert_eq!(request.hands(), None);
+
+        let outcome = request.compute().unwrap();
+        let [hero, villain] = [outcome.results[0], outcome.results[1]];
+        assert_eq!(hero.win_count, villain.loss_count);
+        assert_eq!(hero.tie_count, villain.tie_count);
+        assert!(hero.equity() > 0.2 && hero.equity() < 0.5);
+    }
+
+    #[test]
+    fn test_build_validates_the_scenario() {
+        let builder = EquityRequest::builder;
+
+        assert!(invalid_request(Ok(builder())).contains("two players"));
+        assert!(invalid_request(builder().hand("AsKs")).contains("two players"));
+        assert!(invalid_request(
+            builder()
+                .hand("AsKs")
+                .and_then(|builder| builder.hand("QdQc"))
+                .and_then(|builder| builder.hand_range("JJ"))
+        )
+        .contains("heads-up"));
+        assert!(invalid_request(
+            builder()
+                .hand("AsAh")
+                .and_then(|builder| builder.hand_range("AA"))
+                .map(|builder| builder.dead(&parse_cards("Ad").unwrap()))
+        )
+        .contains("player 2"));
+        assert!(invalid_request(
+            builder()
+                .hand("AsKs")
+                .and_then(|builder| builder.hand("QdQc"))
+                .map(|builder| builder.seed(1))
+        )
+        .contains("seed"));
+        assert!(invalid_request(
+            builder()
+                .hand("AsKs")
+                .and_then(|builder| builder.hand("QdQc"))
+                .map(|builder| builder.monte_carlo(0))
+        )
+        .contains("sample"));
+
+        assert_eq!(
+            error(
+                builder()
+                    .hand("AsKs")
+                    .and_then(|builder| builder.hand("QdQc"))
+                    .and_then(|builder| builder.board("2c3c4c5c6c7c"))
+            ),
+            PokerError::InvalidBoardSize(6)
+        );
+        assert_eq!(
+            error(
+                builder()
+                    .hand("AsKs")
+                    .and_then(|builder| builder.hand("QdQc"))
+                    .and_then(|builder| builder.board("2c3cKs"))
+            ),
+            PokerError::DuplicateCard(parse_cards("Ks").unwrap()[0])
+        );
+        assert_eq!(
+            error(
+                builder()
+                    .hand("AsKs")
+                    .and_then(|builder| builder.hand_range("QQ"))
+                    .map(|builder| builder.dead(&parse_cards("As").unwrap()))
+            ),
+            PokerError::DuplicateCard(parse_cards("As").unwrap()[0])
+        );
+
+        // Parse errors come straight from the step which failed.
+        assert!(matches!(
+            error(builder().hand("AsKx")),
+            PokerError::InvalidCard(_)
+        ));
+        assert!(matches!(
+            error(builder().hand_range("QQ+, AXs")),
+            PokerError::InvalidRange { .. }
+        ));
+    }
+
+    #[test]
+    fn test_too_many_players() {
+        let deck = Card::ALL;
+        let builder = deck
+            .chunks(2)
+            .take(24)
+            .fold(EquityRequest::builder(), |builder, hand| {
+                builder.hand_cards([hand[0], hand[1]])
+            });
+        assert_eq!(
+            builder.build(),
+            Err(PokerError::NotEnoughCards {
+                needed: 5,
+                remaining: 4
+            })
+        );
+
+        let builder = deck
+            .chunks(2)
+            .take(23)
+            .fold(EquityRequest::builder(), |builder, hand| {
+                builder.hand_cards([hand[0], hand[1]])
+            });
+        assert!(builder.build().is_ok());
+    }
+}
@@ -0,0 +1,318 @@
+//! Variants of hold'em which change how hands are ranked.
+//!
+//! The rest of the crate plays standard hold'em. The functions here take the
+//! rules to play by as a type, so evaluations made under different rules have
+//! different types and can't be compared by mistake.
+
+use crate::{Card, HandCounts, HandEvaluation, HandKind, Number};
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// How a variant of hold'em ranks hands. Every method has a default which
+/// matches standard hold'em, so a variant only needs to override what it
+/// changes.
+pub trait Rules {
+    /// Every kind of hand, from the weakest to the strongest.
+    const KIND_ORDER: [HandKind; 9] = HandKind::ALL;
+
+    /// Returns whether the card is dealt in this variant.
+    #[must_use]
+    fn is_in_deck(card: Card) -> bool {
+        let _ = card;
+        true
+    }
+
+    /// Returns the highest card of the highest straight in a set of ranks, as
+    /// for [`straight_high_card`](crate::straight_high_card), including
+    /// whichever wheels this variant has.
+    #[must_use]
+    fn straight_high_card(rank_bits: u16) -> Option<Number> {
+        crate::straight_high_card(rank_bits)
+    }
+}
+
+/// The standard rules, where the wheel is five high.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Standard;
+
+impl Rules for Standard {}
+
+/// Short deck, or six plus, hold'em, which is played without the twos to
+/// fives.
+///
+/// Flushes are harder to make with fewer cards of each suit, so they beat full
+/// houses. Three of a kind also beats a straight, as in most short deck
+/// games. The ace still plays low, making `A-6-7-8-9` the lowest straight.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShortDeck;
+
+impl Rules for ShortDeck {
+    const KIND_ORDER: [HandKind; 9] = [
+        HandKind::HighCard,
+        HandKind::Pair,
+        HandKind::TwoPair,
+        HandKind::Straight,
+        HandKind::ThreeOfAKind,
+        HandKind::FullHouse,
+        HandKind::Flush,
+        HandKind::FourOfAKind,
+        HandKind::StraightFlush,
+    ];
+
+    fn is_in_deck(card: Card) -> bool {
+        card.number() >= Number::Six
+    }
+
+    fn straight_high_card(rank_bits: u16) -> Option<Number> {
+        // Without the twos to fives, the ace plays low just below the six.
+        let wheel = Number::Ace.as_bit()
+            | Number::Six.as_bit()
+            | Number::Seven.as_bit()
+            | Number::Eight.as_bit()
+            | Number::Nine.as_bit();
+        crate::straight_high_card(rank_bits)
+            .or_else(|| (rank_bits & wheel == wheel).then_some(Number::Nine))
+    }
+}
+
+/// A [`HandEvaluation`] made under the rules `R`, which compares as those
+/// rules rank hands.
+///
+/// Evaluations made under different rules have different types, so comparing
+/// them doesn't compile.
+pub struct RulesEvaluation<R> {
+    evaluation: HandEvaluation,
+    rules: PhantomData<R>,
+}
+
+impl<R: Rules> RulesEvaluation<R> {
+    /// Returns the evaluation, which no longer knows the rules it was made
+    /// under.
+    #[must_use]
+    pub const fn evaluation(&self) -> HandEvaluation {
+        self.evaluation
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> HandKind {
+        self.evaluation.kind()
+    }
+
+    /// Returns how strong the kind of hand is under `R`, with the weakest
+    /// kind at 0.
+    fn kind_strength(self) -> usize {
+        let kind = self.kind();
+        R::KIND_ORDER
+            .iter()
+            .position(|&other| other == kind)
+            .unwrap_or_default()
+    }
+}
+
+impl<R> Clone for RulesEvaluation<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for RulesEvaluation<R> {}
+
+impl<R: Rules> PartialEq for RulesEvaluation<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluation == other.evaluation
+    }
+}
+
+impl<R: Rules> Eq for RulesEvaluation<R> {}
+
+impl<R: Rules> PartialOrd for RulesEvaluation<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Rules> Ord for RulesEvaluation<R> {
+    /// Compares by the strength of the kind under `R`, then as the
+    /// evaluations compare within a kind.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind_strength()
+            .cmp(&other.kind_strength())
+            .then_with(|| self.evaluation.cmp(&other.evaluation))
+    }
+}
+
+impl<R> fmt::Debug for RulesEvaluation<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RulesEvaluation")
+            .field("kind", &self.evaluation.kind())
+            .field("rank", &self.evaluation.rank())
+            .finish()
+    }
+}
+
+/// Evaluates the best five cards out of five to seven cards, as the rules
+/// `R` rank hands.
+///
+/// With [`Standard`] rules, this agrees with [`evaluate_hand`](crate::evaluate_hand).
+#[must_use]
+pub fn evaluate_hand_with_rules<R: Rules>(cards: &[Card], rules: &R) -> RulesEvaluation<R> {
+    let _ = rules;
+    let counts = HandCounts::new(cards);
+    let evaluation = R::KIND_ORDER
+        .iter()
+        .rev()
+        .find_map(|&kind| counts.best_of_kind(kind, R::straight_high_card))
+        .unwrap_or_else(|| HandEvaluation::new_high_card(0));
+    RulesEvaluation {
+        evaluation,
+        rules: PhantomData,
+    }
+}
+
+/// Computes the exact equity of every hand under the rules `R`, by
+/// enumerating every way the rest of the board can be dealt from the deck of
+/// that variant. The results are in the same order as `hands`.
+///
+/// # Errors
+///
+/// Will return an error if the board has more than five cards, if any card is
+/// used more than once, or if a card isn't in the deck of the variant.
+#[cfg(feature = "std")]
+pub fn compute_equity_with_rules<R: Rules>(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    rules: &R,
+) -> Result<Vec<crate::EquityResult>, crate::PokerError> {
+    use itertools::Itertools;
+
+    let deck = crate::remaining_deck(hands, board, dead)?;
+    if let Some(card) = hands
+        .iter()
+        .flatten()
+        .chain(board)
+        .chain(dead)
+        .find(|&&card| !R::is_in_deck(card))
+    {
+        return Err(crate::PokerError::InvalidRequest(format!(
+            "{card} isn't in the deck of this variant"
+        )));
+    }
+
+    let mut results = vec![crate::EquityResult::default(); hands.len()];
+    let mut evaluations = Vec::with_capacity(hands.len());
+    let deck = deck.into_iter().filter(|&card| R::is_in_deck(card));
+    for runout in deck.combinations(5 - board.len()) {
+        evaluations.clear();
+        evaluations.extend(
+            hands
+                .iter()
+                .map(|hand| evaluate_hand_with_rules(&[&hand[..], board, &runout].concat(), rules)),
+        );
+        let Some(&best) = evaluations.iter().max() else {
+            break;
+        };
+        let winners = evaluations
+            .iter()
+            .filter(|&&evaluation| evaluation == best)
+            .count();
+        for (evaluation, result) in evaluations.iter().zip(&mut results) {
+            if *evaluation != best {
+                result.loss_count += 1;
+            } else if winners == 1 {
+                result.win_count += 1;
+            } else {
+                result.tie_count += 1;
+            }
+            result.count += 1;
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{compute_equity, evaluate_hand, parse_cards, PokerError};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn evaluate<R: Rules>(cards: &str, rules: &R) -> RulesEvaluation<R> {
+        evaluate_hand_with_rules(&parse_cards(cards).unwrap(), rules)
+    }
+
+    #[test]
+    fn test_standard_rules_match_evaluate_hand() {
+        let mut rng = StdRng::seed_from_u64(847);
+        let mut deck = Card::ALL.to_vec();
+        let hands: Vec<[Card; 7]> = (0..5_000)
+            .map(|_| {
+                let (cards, _) = deck.partial_shuffle(&mut rng, 7);
+                [
+                    cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], cards[6],
+                ]
+            })
+            .collect();
+
+        for pair in hands.windows(2) {
+            let [a, b] =
+                [pair[0], pair[1]].map(|cards| evaluate_hand_with_rules(&cards, &Standard));
+            assert!(a.evaluation() == evaluate_hand(pair[0]));
+            assert_eq!(
+                a.cmp(&b),
+                evaluate_hand(pair[0]).cmp(&evaluate_hand(pair[1]))
+            );
+        }
+    }
+
+    #[test]
+    fn test_short_deck_ranks_hands_differently() {
+        let flush = "AhJh9h7h6h 8c8d";
+        let full_house = "TsTcTd6s6c AdKd";
+        assert!(evaluate(flush, &Standard) < evaluate(full_house, &Standard));
+        assert!(evaluate(flush, &ShortDeck) > evaluate(full_house, &ShortDeck));
+
+        let straight = "9s8h7d6cTc AsKd";
+        let trips = "9s9h9dJcQc AsKd";
+        assert!(evaluate(straight, &Standard) > evaluate(trips, &Standard));
+        assert!(evaluate(straight, &ShortDeck) < evaluate(trips, &ShortDeck));
+
+        // The short deck's wheel is a straight only under its own rules, and
+        // is the lowest straight there.
+        let wheel = "Ah6c7d8s9h KcQd";
+        assert_eq!(evaluate(wheel, &Standard).kind(), HandKind::HighCard);
+        assert_eq!(evaluate(wheel, &ShortDeck).kind(), HandKind::Straight);
+        assert!(evaluate(wheel, &ShortDeck) < evaluate("6c7d8s9hTc KcQd", &ShortDeck));
+        assert_eq!(
+            evaluate("Ah6h7h8h9h KcQd", &ShortDeck).kind(),
+            HandKind::StraightFlush
+        );
+    }
+
+    #[test]
+    fn test_equity_with_rules() {
+        let hands = [parse_cards("AhQc").unwrap(), parse_cards("KsKd").unwrap()]
+            .map(|hand| [hand[0], hand[1]]);
+        let board = parse_cards("Jh7h9c").unwrap();
+        assert_eq!(
+            compute_equity_with_rules(&hands, &board, &[], &Standard).unwrap(),
+            compute_equity(&hands, &board, &[]).unwrap()
+        );
+
+        // Only the 29 short deck cards which are left can come on the turn
+        // and river.
+        let short_deck = compute_equity_with_rules(&hands, &board, &[], &ShortDeck).unwrap();
+        assert!(short_deck.iter().all(|result| result.count == 29 * 28 / 2));
+        // Short deck makes the flush draw stronger, since the flush beats
+        // more hands and there are fewer blanks to miss with.
+        assert!(short_deck[0].equity() > compute_equity(&hands, &board, &[]).unwrap()[0].equity());
+
+        let low_board = parse_cards("Jh7h2c").unwrap();
+        assert!(matches!(
+            compute_equity_with_rules(&hands, &low_board, &[], &ShortDeck),
+            Err(PokerError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+//! Serialization for the types which don't derive it: cards are written in
+//! the standard notation, such as `"As"`, ranges in range notation, such as
+//! `"QQ+, AKs"`, and evaluations as their packed [`HandEvaluation::rank`].
+
+#[cfg(feature = "std")]
+use crate::Range;
+use crate::{Card, HandEvaluation, Number, Suit};
+use core::fmt;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct CardVisitor;
+
+impl Visitor<'_> for CardVisitor {
+    type Value = Card;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a card such as \"As\" or \"Td\"")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Card, E> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(number), Some(suit), None) => {
+                match (Number::from_char(number), Suit::from_char(suit)) {
+                    (Some(number), Some(suit)) => Ok(Card::new(suit, number)),
+                    _ => Err(E::invalid_value(Unexpected::Str(s), &self)),
+                }
+            }
+            _ => Err(E::invalid_value(Unexpected::Str(s), &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(CardVisitor)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Range {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Range {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let notation = String::deserialize(deserializer)?;
+        notation.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for HandEvaluation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.rank())
+    }
+}
+
+impl<'de> Deserialize<'de> for HandEvaluation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rank = u32::deserialize(deserializer)?;
+        Self::from_rank(rank).ok_or_else(|| {
+            de::Error::invalid_value(
+                Unexpected::Unsigned(rank.into()),
+                &"the rank of an evaluation which some hand makes",
+            )
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{evaluate_cards, parse_cards, EquityRequest, EquityResult, HandKind};
+
+    #[test]
+    fn test_cards_round_trip() {
+        for card in Card::ALL {
+            let json = serde_json::to_string(&card).unwrap();
+            assert_eq!(json, format!("\"{card}\""));
+            assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+        }
+        let cards: Vec<Card> = serde_json::from_str(r#"["As", "td"]"#).unwrap();
+        assert_eq!(cards, parse_cards("AsTd").unwrap());
+
+        for invalid in [r#""Ax""#, r#""A""#, r#""10s""#, "51"] {
+            assert!(serde_json::from_str::<Card>(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_evaluations_round_trip() {
+        let evaluation = evaluate_cards(&parse_cards("KsKdKcTsTd").unwrap()).unwrap();
+        let json = serde_json::to_string(&evaluation).unwrap();
+        assert_eq!(json, evaluation.rank().to_string());
+        assert!(serde_json::from_str::<HandEvaluation>(&json).unwrap() == evaluation);
+        assert!(serde_json::from_str::<HandEvaluation>("0").is_err());
+
+        assert_eq!(
+            serde_json::to_string(&(Suit::Spades, Number::Ace, HandKind::FullHouse)).unwrap(),
+            r#"["Spades","Ace","FullHouse"]"#
+        );
+        let result = EquityResult {
+            win_count: 1,
+            loss_count: 2,
+            tie_count: 3,
+            count: 6,
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(serde_json::from_str::<EquityResult>(&json).unwrap(), result);
+    }
+
+    #[test]
+    fn test_requests_round_trip() {
+        let request = EquityRequest::new()
+            .hand("AsKc")
+            .unwrap()
+            .hand_range("QQ+, AKs")
+            .unwrap()
+            .board("7d8d2c")
+            .unwrap()
+            .monte_carlo(1000)
+            .seed(42)
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"players":[{"Hand":["As","Kc"]},{"Range":"QQ+, AKs"}],"board":["7d","8d","2c"],"dead":[],"samples":1000,"seed":42}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<EquityRequest>(&json).unwrap(),
+            request
+        );
+
+        let outcome = request.compute().unwrap();
+        let json = serde_json::to_string(&outcome).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::EquityOutcome>(&json).unwrap(),
+            outcome
+        );
+
+        // Deserialized requests are checked as if they were built.
+        let error = serde_json::from_str::<EquityRequest>(r#"{"players":[{"Hand":["As","Kc"]}]}"#)
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("at least two players"),
+            "{error}"
+        );
+        assert!(serde_json::from_str::<EquityRequest>(
+            r#"{"players":[{"Hand":["As","Kc"]},{"Range":"QQ+, AXs"}]}"#
+        )
+        .is_err());
+    }
+}
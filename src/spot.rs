@@ -0,0 +1,159 @@
+use crate::{compute_equity, parse_cards, remaining_deck, Card, EquityResult, PokerError};
+use std::fmt;
+use std::str::FromStr;
+
+/// A scenario written on one line, which is handy for sharing: the hands,
+/// then the board, then the dead cards, separated by bars.
+///
+/// ```text
+/// AsKs QhQd | Jc9c2s | Th
+/// ```
+///
+/// The board and the dead cards can be left out, along with their bars, and
+/// whitespace anywhere is ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Spot {
+    pub hands: Vec<[Card; 2]>,
+    pub board: Vec<Card>,
+    pub dead: Vec<Card>,
+}
+
+impl Spot {
+    /// Computes the exact equity of every hand, as [`compute_equity`] does.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, or if any
+    /// card is used more than once.
+    pub fn compute_equity(&self) -> Result<Vec<EquityResult>, PokerError> {
+        compute_equity(&self.hands, &self.board, &self.dead)
+    }
+}
+
+/// Parses a spot, such as `"AsKs QhQd | Jc9c2s | Th"`.
+///
+/// # Errors
+///
+/// Will return an error if there are more than three sections, if a card
+/// can't be parsed or is used more than once, if the hands don't split into
+/// pairs of cards, or if the board has more than five cards.
+pub fn parse_spot(s: &str) -> Result<Spot, PokerError> {
+    let sections: Vec<&str> = s.split('|').collect();
+    if sections.len() > 3 {
+        return Err(PokerError::InvalidRequest(format!(
+            "a spot has at most three sections, hands | board | dead, but found {}",
+            sections.len()
+        )));
+    }
+    let section = |index: usize| sections.get(index).map_or(Ok(vec![]), |s| parse_cards(s));
+
+    let cards = section(0)?;
+    if cards.len() % 2 != 0 {
+        return Err(PokerError::InvalidHandSize {
+            expected: cards.len() + 1,
+            found: cards.len(),
+        });
+    }
+    let spot = Spot {
+        hands: cards.chunks(2).map(|hand| [hand[0], hand[1]]).collect(),
+        board: section(1)?,
+        dead: section(2)?,
+    };
+    remaining_deck(&spot.hands, &spot.board, &spot.dead)?;
+    Ok(spot)
+}
+
+impl FromStr for Spot {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_spot(s)
+    }
+}
+
+impl fmt::Display for Spot {
+    /// Writes the spot in the same notation [`parse_spot`] reads, leaving out
+    /// the sections which are empty at the end.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards = |cards: &[Card]| cards.iter().map(ToString::to_string).collect::<String>();
+        let hands: Vec<String> = self.hands.iter().map(|hand| cards(hand)).collect();
+        write!(f, "{}", hands.join(" "))?;
+        if !self.board.is_empty() || !self.dead.is_empty() {
+            write!(f, " | {}", cards(&self.board))?;
+        }
+        if !self.dead.is_empty() {
+            write!(f, " | {}", cards(&self.dead))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(cards: &str) -> [Card; 2] {
+        let cards = parse_cards(cards).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn test_full_spot() {
+        let spot = parse_spot(" AsKs  QhQd|Jc9c2s |  Th ").unwrap();
+        assert_eq!(
+            spot,
+            Spot {
+                hands: vec![hand("AsKs"), hand("QhQd")],
+                board: parse_cards("Jc9c2s").unwrap(),
+                dead: parse_cards("Th").unwrap(),
+            }
+        );
+        assert_eq!(spot.to_string(), "AsKs QhQd | Jc9c2s | Th");
+        assert_eq!(spot.to_string().parse(), Ok(spot.clone()));
+        assert_eq!(
+            spot.compute_equity().unwrap(),
+            compute_equity(&spot.hands, &spot.board, &spot.dead).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_heads_up_without_a_board() {
+        let spot: Spot = "AsKs QhQd".parse().unwrap();
+        assert_eq!(spot.hands, [hand("AsKs"), hand("QhQd")]);
+        assert!(spot.board.is_empty() && spot.dead.is_empty());
+        assert_eq!(spot.to_string(), "AsKs QhQd");
+
+        // Empty sections are the same as missing ones.
+        assert_eq!(parse_spot("AsKs QhQd | |"), Ok(spot));
+        let dead_only = parse_spot("AsKs QhQd || 2c").unwrap();
+        assert_eq!(dead_only.to_string(), "AsKs QhQd |  | 2c");
+        assert_eq!(dead_only.to_string().parse(), Ok(dead_only));
+    }
+
+    #[test]
+    fn test_invalid_spots() {
+        assert!(matches!(
+            parse_spot("AsKs QhQd | Jc9c2s | Th | 2c"),
+            Err(PokerError::InvalidRequest(_))
+        ));
+        assert_eq!(
+            parse_spot("AsKs Qh"),
+            Err(PokerError::InvalidHandSize {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            parse_spot("AsKs QhQd | Jc9c2sAs"),
+            Err(PokerError::DuplicateCard(hand("AsKs")[0]))
+        );
+        assert_eq!(
+            parse_spot("AsKs QhQd | Jc9c2s3s4s5s"),
+            Err(PokerError::InvalidBoardSize(6))
+        );
+        assert!(matches!(
+            parse_spot("AsKs QhQx"),
+            Err(PokerError::InvalidCard(_))
+        ));
+    }
+}
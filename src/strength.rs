@@ -0,0 +1,286 @@
+//! Quick measures of how strong a starting hand is, for sorting hands without
+//! computing any equity.
+
+use crate::range::all_classes;
+use crate::{Card, HoleCards, Number, StartingHand};
+
+/// The number of ways to deal a random hand and a board against known hole
+/// cards: 1,225 hands times 1,712,304 boards.
+const DEALS: u32 = 2_097_572_400;
+
+/// Twice the wins plus the ties of each class of starting hand all in against
+/// a random hand, over all [`DEALS`], in the order of [`all_classes`].
+///
+/// These are exact, and are generated by `test_generate_equity_vs_random`,
+/// which takes several minutes even with `--release`.
+#[allow(clippy::unreadable_literal)]
+const EQUITY_VS_RANDOM: [u32; 169] = [
+    // Pairs, from aces down.
+    3574419148, 3456618076, 3352976364, 3249956564, 3146852786, 3022906058, 2901489486, 2778697066,
+    2654886824, 2530717766, 2392189918, 2252502302, 2111584984,
+    // Ax suited, then offsuit, from the highest kicker down.
+    2812619406, 2777557648, 2743317574, 2710163670, 2633762831, 2598632551, 2558365366, 2513136230,
+    2513853498, 2476546510, 2442426752, 2407127850, 2740271595, 2703008959, 2666570709, 2631264233,
+    2549507227, 2511742479, 2468473350, 2419862398, 2420453161, 2379892121, 2342761931, 2304332573,
+    // Kx suited, then offsuit, from the highest kicker down.
+    2659738722, 2624790516, 2592119485, 2516603411, 2446287516, 2413791403, 2376160871, 2340593683,
+    2302489977, 2267684534, 2232309054, 2578159800, 2540944046, 2506133992, 2425293951, 2350127356,
+    2315189251, 2274745067, 2236598359, 2195213225, 2157382145, 2118914099,
+    // Qx suited, then offsuit, from the highest kicker down.
+    2527960911, 2494750222, 2419101794, 2350025012, 2278058571, 2249124793, 2213753197, 2175405007,
+    2140331266, 2104663674, 2438834411, 2403431287, 2322450407, 2248588890, 2171644643, 2140532877,
+    2102610047, 2060977485, 2022875163, 1984112061,
+    // Jx suited, then offsuit, from the highest kicker down.
+    2413376906, 2335121239, 2266034488, 2195100337, 2122991082, 2097020718, 2058576574, 2023386467,
+    1987582097, 2317721143, 2233964817, 2160086896, 2084229054, 2007136533, 1979306579, 1937585709,
+    1899374667, 1860482433,
+    // Tx suited, then offsuit, from the highest kicker down.
+    2266533059, 2195502632, 2124380950, 2053132211, 1980790433, 1952021583, 1916867042, 1881081228,
+    2161828275, 2085879427, 2009815656, 1933626279, 1856391409, 1825060325, 1786903085, 1748047643,
+    // 9x suited, then offsuit, from the highest kicker down.
+    2131165248, 2060559942, 1989685474, 1918098843, 1840073188, 1814998261, 1779377877, 2017740169,
+    1942260018, 1866476357, 1790032326, 1706209587, 1678876575, 1640215041,
+    // 8x suited, then offsuit, from the highest kicker down.
+    2010998885, 1939972106, 1868726945, 1791395097, 1714702682, 1689453378, 1889946808, 1814018446,
+    1737944702, 1654850021, 1572500215, 1544974255,
+    // 7x suited, then offsuit, from the highest kicker down.
+    1903411309, 1832252004, 1755639231, 1679565482, 1600694981, 1775500515, 1699535755, 1617221098,
+    1535517681, 1450834082,
+    // 6x suited, then offsuit, from the highest kicker down.
+    1809508009, 1733992584, 1658490084, 1580267589, 1675721321, 1594595021, 1513508859, 1429501394,
+    // 5x suited, then offsuit, from the highest kicker down.
+    1739031009, 1665177248, 1587834115, 1600669542, 1521359660, 1438290523,
+    // 4x suited, then offsuit, from the highest kicker down.
+    1621085688, 1545030496, 1474421119, 1392777591,
+    // 3x suited, then offsuit, from the highest kicker down.
+    1509598969, 1355167195,
+];
+
+/// Scores hole cards with the Chen formula, from -1 for `72o` up to 20 for
+/// aces.
+///
+/// The highest card scores 10 for an ace, 8 for a king, 7 for a queen, 6 for
+/// a jack and half its number otherwise. Pairs score double, and at least 5.
+/// Suited cards add 2, and gaps between the cards take away 1, 2, 4 or 5
+/// points, with one more point back for connected or one gap cards below a
+/// queen. Half points round up.
+#[must_use]
+pub fn preflop_strength(hole: HoleCards) -> f64 {
+    let [high, low] = hole.cards().map(Card::number);
+    let mut score = match high {
+        Number::Ace => 10.,
+        Number::King => 8.,
+        Number::Queen => 7.,
+        Number::Jack => 6.,
+        number => f64::from(number as u8) / 2.,
+    };
+
+    if hole.is_pair() {
+        score = (score * 2.).max(5.);
+    } else {
+        if hole.is_suited() {
+            score += 2.;
+        }
+        let gap = high as u8 - low as u8 - 1;
+        score -= match gap {
+            0 => 0.,
+            1 => 1.,
+            2 => 2.,
+            3 => 4.,
+            _ => 5.,
+        };
+        if gap <= 1 && high < Number::Queen {
+            score += 1.;
+        }
+    }
+    score.ceil()
+}
+
+/// The rank of each class by [`EQUITY_VS_RANDOM`], in the order of
+/// [`all_classes`].
+const STRENGTH_RANKS: [u8; 169] = strength_ranks();
+
+const fn strength_ranks() -> [u8; 169] {
+    let mut ranks = [1; 169];
+    let mut class = 0;
+    while class < ranks.len() {
+        let mut other = 0;
+        while other < ranks.len() {
+            if EQUITY_VS_RANDOM[other] > EQUITY_VS_RANDOM[class] {
+                ranks[class] += 1;
+            }
+            other += 1;
+        }
+        class += 1;
+    }
+    ranks
+}
+
+impl StartingHand {
+    /// Returns the class's position in [`all_classes`].
+    fn index(self) -> usize {
+        all_classes()
+            .position(|class| class == self)
+            .expect("all_classes lists every class")
+    }
+
+    /// Returns the class's share of the pot all in preflop against a random
+    /// hand, from about 0.32 for `32o` up to 0.85 for aces.
+    #[must_use]
+    pub fn equity_vs_random(self) -> f64 {
+        f64::from(EQUITY_VS_RANDOM[self.index()]) / f64::from(DEALS) / 2.
+    }
+
+    /// Ranks the class among all 169 by [`equity_vs_random`], from 1 for
+    /// aces down to 169 for `32o`.
+    ///
+    /// [`equity_vs_random`]: Self::equity_vs_random
+    #[must_use]
+    pub fn strength_rank(self) -> u8 {
+        STRENGTH_RANKS[self.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combine, compute_range_equity, evaluate_hand, EquityMethod, HandEvaluation, Range,
+    };
+    use itertools::Itertools;
+
+    /// Counts twice the wins plus the ties of each class all in preflop
+    /// against a random hand, in the order of [`all_classes`].
+    ///
+    /// Each board is dealt once and every two cards left are ranked on it, so
+    /// a hand's wins on that board are the hands ranked below it which don't
+    /// share a card with it. Every combination of a class must come out the
+    /// same.
+    fn exact_equity_vs_random() -> Vec<u32> {
+        let mut scores = [[0_u64; 52]; 52];
+        let mut hands: Vec<(HandEvaluation, [usize; 2])> = vec![];
+        for board in Card::ALL.into_iter().combinations(5) {
+            let board = [board[0], board[1], board[2], board[3], board[4]];
+            hands.clear();
+            hands.extend(
+                Card::ALL
+                    .into_iter()
+                    .filter(|card| !board.contains(card))
+                    .tuple_combinations()
+                    .map(|(first, second)| {
+                        let evaluation = evaluate_hand(combine([first, second], board));
+                        (
+                            evaluation,
+                            [first, second].map(|card| usize::from(card.to_index())),
+                        )
+                    }),
+            );
+            hands.sort_unstable_by_key(|&(evaluation, _)| evaluation);
+
+            let mut below = 0;
+            let mut below_with = [0_u64; 52];
+            for tied in hands.chunk_by(|a, b| a.0 == b.0) {
+                let mut tied_with = [0_u64; 52];
+                for card in tied.iter().flat_map(|(_, hand)| hand) {
+                    tied_with[*card] += 1;
+                }
+                for &(_, [first, second]) in tied {
+                    let wins = below - below_with[first] - below_with[second];
+                    let ties = tied.len() as u64 + 1 - tied_with[first] - tied_with[second];
+                    scores[first][second] += 2 * wins + ties;
+                }
+                below += tied.len() as u64;
+                for (below_with, tied_with) in below_with.iter_mut().zip(tied_with) {
+                    *below_with += tied_with;
+                }
+            }
+        }
+
+        all_classes()
+            .map(|class| {
+                let score = |[first, second]: [Card; 2]| {
+                    let [first, second] = [first, second].map(|card| usize::from(card.to_index()));
+                    scores[first][second] + scores[second][first]
+                };
+                let combos = class.combos();
+                assert!(combos.iter().all(|&combo| score(combo) == score(combos[0])));
+                u32::try_from(score(combos[0])).unwrap()
+            })
+            .collect()
+    }
+
+    fn strength(cards: &str) -> f64 {
+        preflop_strength(cards.parse().unwrap())
+    }
+
+    #[test]
+    fn test_chen_formula() {
+        assert_eq!(strength("AsAd"), 20.);
+        assert_eq!(strength("KhKc"), 16.);
+        assert_eq!(strength("5h5c"), 5.);
+        assert_eq!(strength("2h2c"), 5.);
+        assert_eq!(strength("AsKs"), 12.);
+        assert_eq!(strength("AsKd"), 10.);
+        // Connected and below a queen.
+        assert_eq!(strength("JhTh"), 9.);
+        // 3.5 with a gap of four, rounded up.
+        assert_eq!(strength("7c2d"), -1.);
+        assert_eq!(strength("2d7c"), strength("7c2d"));
+        // 4.5, suited, with a gap of one.
+        assert_eq!(strength("9s7s"), 7.);
+    }
+
+    #[test]
+    fn test_equity_vs_random() {
+        let equity = |class: &str| {
+            let hole: HoleCards = class.parse().unwrap();
+            hole.class().equity_vs_random()
+        };
+        assert!((equity("AsAd") - 0.852).abs() < 0.001);
+        assert!((equity("7c2d") - 0.346).abs() < 0.001);
+        assert!(equity("AsKs") > equity("AsKd"));
+
+        let classes: Vec<StartingHand> = all_classes().collect();
+        assert_eq!(classes.len(), EQUITY_VS_RANDOM.len());
+        assert_eq!(StartingHand::Pair(Number::Ace).strength_rank(), 1);
+        assert_eq!(
+            StartingHand::Offsuit(Number::Three, Number::Two).strength_rank(),
+            169
+        );
+        let mut ranks: Vec<u8> = classes.iter().map(|class| class.strength_rank()).collect();
+        ranks.sort_unstable();
+        ranks.dedup();
+        assert!(ranks.len() > 150);
+    }
+
+    #[test]
+    #[ignore = "takes several minutes even with --release"]
+    fn test_generate_equity_vs_random() {
+        let generated = exact_equity_vs_random();
+        println!("{generated:?}");
+        assert_eq!(generated, EQUITY_VS_RANDOM);
+    }
+
+    #[test]
+    #[ignore = "takes several minutes even with --release"]
+    fn test_equity_vs_random_matches_range_equity() {
+        let random: Range = all_classes().join(",").parse().unwrap();
+        for class in ["AsAd", "7c2d"] {
+            let hole: HoleCards = class.parse().unwrap();
+            let equity = compute_range_equity(
+                &Range::from(hole.cards()),
+                &random,
+                &[],
+                &[],
+                EquityMethod::Exhaustive,
+            )
+            .unwrap()
+            .result;
+            assert_eq!(equity.count, u64::from(DEALS));
+            assert_eq!(
+                2 * equity.win_count + equity.tie_count,
+                u64::from(EQUITY_VS_RANDOM[hole.class().index()])
+            );
+        }
+    }
+}
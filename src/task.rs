@@ -0,0 +1,234 @@
+//! Computes equity on a blocking thread, reporting progress as it goes
+//! and stopping early when it is no longer wanted. This is everything a server
+//! needs to run long computations without blocking its async workers.
+
+use crate::{compute_equity, merge_results, remaining_deck, runout_count};
+use crate::{EquityMethod, EquityRequest, EquityResult, PokerError};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// A snapshot of a computation which is still running.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of runouts which have been counted so far.
+    pub done: u64,
+    /// The number of runouts which will have been counted once the
+    /// computation finishes.
+    pub total: u64,
+    /// The results over the runouts counted so far, in the same order as the
+    /// hands.
+    pub results: Vec<EquityResult>,
+}
+
+/// Stops a computation, which then finishes with [`PokerError::Cancelled`].
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A computation started by [`compute_equity_async`], which resolves to the
+/// results for every hand. Dropping it cancels the computation.
+pub struct EquityTask {
+    worker: JoinHandle<Result<Vec<EquityResult>, PokerError>>,
+    cancel: CancelHandle,
+}
+
+impl EquityTask {
+    /// Returns a handle which can cancel the computation from elsewhere,
+    /// without having to drop the task.
+    #[must_use]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+}
+
+impl Future for EquityTask {
+    type Output = Result<Vec<EquityResult>, PokerError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.worker)
+            .poll(cx)
+            .map(|joined| match joined {
+                Ok(results) => results,
+                Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+                // The runtime is shutting down, so the worker never got to run.
+                Err(_) => Err(PokerError::Cancelled),
+            })
+    }
+}
+
+impl Drop for EquityTask {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Starts computing the equity of a scenario on a blocking thread.
+///
+/// The receiver sees a [`Progress`] snapshot each time a part of the runouts
+/// has been counted, and is closed once the computation ends. Cancelling
+/// takes effect between parts, each of which is at most a 48th of the work.
+///
+/// Only exact requests between hands are split into parts. Anything else,
+/// such as a range or a Monte Carlo estimate, is computed in one go and
+/// reported once it finishes.
+///
+/// # Panics
+///
+/// Will panic if called outside of a Tokio runtime.
+#[must_use]
+pub fn compute_equity_async(request: EquityRequest) -> (EquityTask, watch::Receiver<Progress>) {
+    let (sender, receiver) = watch::channel(Progress::default());
+    let cancel = CancelHandle::default();
+    let worker = {
+        let cancel = cancel.clone();
+        tokio::task::spawn_blocking(move || run(&request, &sender, &cancel))
+    };
+    (EquityTask { worker, cancel }, receiver)
+}
+
+/// Enumerates the runouts a first card at a time, so that progress can be
+/// reported and cancellation checked between each part.
+fn run(
+    request: &EquityRequest,
+    progress: &watch::Sender<Progress>,
+    cancel: &CancelHandle,
+) -> Result<Vec<EquityResult>, PokerError> {
+    let (board, dead) = (request.board(), request.dead());
+    let (Some(hands), EquityMethod::Exhaustive) = (request.hands(), request.method()) else {
+        let results = request.compute()?.results;
+        let done = results.first().map_or(0, |result| result.count);
+        progress.send_replace(Progress {
+            done,
+            total: done,
+            results: results.clone(),
+        });
+        return Ok(results);
+    };
+    let hands = &hands;
+    let total = runout_count(hands, board, dead)?;
+    progress.send_replace(Progress {
+        done: 0,
+        total,
+        results: vec![],
+    });
+    if board.len() == 5 {
+        return compute_equity(hands, board, dead);
+    }
+
+    // Later parts treat the earlier first cards as dead, so that every runout
+    // is counted exactly once. The last few first cards leave too few cards
+    // after them to finish the board, so they have no parts of their own.
+    let deck = remaining_deck(hands, board, dead)?;
+    let parts = deck.len() + 1 - (5 - board.len());
+    let mut totals = vec![EquityResult::default(); hands.len()];
+    for (i, &card) in deck.iter().enumerate().take(parts) {
+        if cancel.is_cancelled() {
+            return Err(PokerError::Cancelled);
+        }
+
+        let part_board = [board, &[card]].concat();
+        let part_dead = [dead, &deck[..i]].concat();
+        merge_results(
+            &mut totals,
+            &compute_equity(hands, &part_board, &part_dead)?,
+        );
+
+        progress.send_replace(Progress {
+            done: totals.first().map_or(0, |result| result.count),
+            total,
+            results: totals.clone(),
+        });
+    }
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+    use std::time::Duration;
+
+    fn request(hands: &str, board: &str) -> EquityRequest {
+        parse_cards(hands)
+            .unwrap()
+            .chunks(2)
+            .fold(EquityRequest::builder(), |builder, hand| {
+                builder.hand_cards([hand[0], hand[1]])
+            })
+            .board(board)
+            .and_then(crate::EquityRequestBuilder::build)
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_progress_increases_until_finished() {
+        let request = request("AhQc KsKd", "2h7hJh");
+        let expected = request.compute().unwrap().results;
+        let (task, mut progress) = compute_equity_async(request);
+
+        let mut done = 0;
+        while progress.changed().await.is_ok() {
+            let snapshot = progress.borrow_and_update().clone();
+            assert!(snapshot.done >= done);
+            assert_eq!(snapshot.total, 990);
+            done = snapshot.done;
+        }
+        assert_eq!(task.await, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dropping_the_task_stops_the_worker() {
+        let (task, mut progress) = compute_equity_async(request("AsKs QdQc 7h6h", ""));
+        while progress.borrow_and_update().done == 0 {
+            progress.changed().await.unwrap();
+        }
+        drop(task);
+
+        // The worker drops its sender when it stops, which closes the channel.
+        let stopped = tokio::time::timeout(Duration::from_secs(10), async {
+            while progress.changed().await.is_ok() {}
+        });
+        assert!(stopped.await.is_ok());
+        let snapshot = progress.borrow();
+        assert!(snapshot.done < snapshot.total);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_handle() {
+        let (task, _progress) = compute_equity_async(request("AsKs QdQc 7h6h", ""));
+        task.cancel_handle().cancel();
+        assert_eq!(task.await, Err(PokerError::Cancelled));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sampled_requests_finish_in_one_part() {
+        let request = EquityRequest::builder()
+            .hand("AsKs")
+            .and_then(|builder| builder.hand_range("QQ+"))
+            .map(|builder| builder.monte_carlo(1_000).seed(5))
+            .and_then(crate::EquityRequestBuilder::build)
+            .unwrap();
+        let expected = request.compute().unwrap().results;
+        let (task, progress) = compute_equity_async(request);
+
+        assert_eq!(task.await, Ok(expected.clone()));
+        let snapshot = progress.borrow();
+        assert_eq!(snapshot.done, snapshot.total);
+        assert_eq!(snapshot.results, expected);
+    }
+}
@@ -0,0 +1,232 @@
+//! Bindings which let the browser compute equity without a round trip to the
+//! backend. Cards and hands are passed as strings in the usual notation, such
+//! as `"AsKs"`, and results come back as plain objects.
+//!
+//! Simulations can take a while, so [`EquitySimulation`] runs in chunks: call
+//! `run` with a few thousand samples at a time and yield to the event loop in
+//! between, for example with `setTimeout(step, 0)`, so the page keeps
+//! responding. Alternatively, load this module in a web worker, run the whole
+//! simulation there and `postMessage` the results of each chunk back to the
+//! page.
+
+use poker_calculator::{
+    compare_hands, compute_equity, evaluate_hand, parse_cards, runout_count, Card, EquityResult,
+    Hand, MonteCarlo, PokerError,
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// The results for one hand, in a form which serializes to a plain object.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandEquity {
+    pub win_count: u64,
+    pub loss_count: u64,
+    pub tie_count: u64,
+    pub count: u64,
+    pub equity: f64,
+}
+
+impl From<&EquityResult> for HandEquity {
+    fn from(result: &EquityResult) -> Self {
+        Self {
+            win_count: result.win_count,
+            loss_count: result.loss_count,
+            tie_count: result.tie_count,
+            count: result.count,
+            equity: result.equity(),
+        }
+    }
+}
+
+/// A parsed set of hands, board and dead cards.
+pub struct Scenario {
+    hands: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+}
+
+impl Scenario {
+    /// # Errors
+    ///
+    /// Will return an error if any card is malformed, or if a hand doesn't
+    /// have exactly two distinct cards.
+    pub fn parse(hands: &[String], board: &str, dead: &str) -> Result<Self, PokerError> {
+        Ok(Self {
+            hands: hands
+                .iter()
+                .map(|hand| hand.parse().map(Hand::cards))
+                .collect::<Result<_, _>>()?,
+            board: parse_cards(board)?,
+            dead: parse_cards(dead)?,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, or if any
+    /// card is used more than once.
+    pub fn runout_count(&self) -> Result<u64, PokerError> {
+        runout_count(&self.hands, &self.board, &self.dead)
+    }
+
+    /// Computes the exact equity of every hand, in the same order as the
+    /// hands were given.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, or if any
+    /// card is used more than once.
+    pub fn exact_equity(&self) -> Result<Vec<HandEquity>, PokerError> {
+        let results = compute_equity(&self.hands, &self.board, &self.dead)?;
+        Ok(results.iter().map(HandEquity::from).collect())
+    }
+
+    /// Prepares a simulation of this scenario. The seed is chosen randomly
+    /// when none is given.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the board has more than five cards, or if any
+    /// card is used more than once.
+    pub fn simulate(&self, seed: Option<u64>) -> Result<EquitySimulation, PokerError> {
+        Ok(EquitySimulation {
+            simulation: MonteCarlo::new(&self.hands, &self.board, &self.dead, seed)?,
+        })
+    }
+}
+
+/// A Monte Carlo simulation which can be advanced a chunk at a time.
+#[wasm_bindgen]
+pub struct EquitySimulation {
+    simulation: MonteCarlo,
+}
+
+#[wasm_bindgen]
+impl EquitySimulation {
+    /// Prepares a simulation of the given hands, board and dead cards.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if any card is malformed or used more than once.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        hands: Vec<String>,
+        board: &str,
+        dead: &str,
+        seed: Option<u64>,
+    ) -> Result<EquitySimulation, JsError> {
+        Ok(Scenario::parse(&hands, board, dead)?.simulate(seed)?)
+    }
+
+    /// Deals and scores `samples` more random runouts.
+    pub fn run(&mut self, samples: u32) {
+        self.simulation.run(u64::from(samples));
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn samples(&self) -> u64 {
+        self.simulation.samples()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.simulation.seed()
+    }
+
+    /// Returns an array with the results so far for each hand.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the results can't be converted to JavaScript.
+    #[wasm_bindgen(getter)]
+    pub fn results(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.equities())?)
+    }
+}
+
+impl EquitySimulation {
+    /// Returns the results so far for each hand.
+    #[must_use]
+    pub fn equities(&self) -> Vec<HandEquity> {
+        self.simulation
+            .results()
+            .iter()
+            .map(HandEquity::from)
+            .collect()
+    }
+}
+
+fn parse_seven_cards(cards: &str) -> Result<[Card; 7], PokerError> {
+    Hand::try_from_iter(parse_cards(cards)?)
+}
+
+/// Normalizes a run of cards, such as `"ahkh"`, into an array of cards, such
+/// as `["Ah", "Kh"]`.
+///
+/// # Errors
+///
+/// Will return an error if any card is malformed.
+#[wasm_bindgen(js_name = parseCards)]
+pub fn parse_cards_js(cards: &str) -> Result<Vec<String>, JsError> {
+    Ok(parse_cards(cards)?
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Returns the kind of hand seven cards make, such as `"Full House"`.
+///
+/// # Errors
+///
+/// Will return an error unless given exactly seven distinct cards.
+#[wasm_bindgen(js_name = evaluateHand)]
+pub fn evaluate_hand_js(cards: &str) -> Result<String, JsError> {
+    let evaluation = evaluate_hand(parse_seven_cards(cards)?);
+    Ok(evaluation.kind().to_string())
+}
+
+/// Compares two sets of seven cards, returning a negative number when the
+/// first is worse, zero for a tie and a positive number when it is better.
+///
+/// # Errors
+///
+/// Will return an error unless both are exactly seven distinct cards.
+#[wasm_bindgen(js_name = compareHands)]
+pub fn compare_hands_js(a: &str, b: &str) -> Result<i32, JsError> {
+    let ordering = compare_hands(&parse_seven_cards(a)?, &parse_seven_cards(b)?);
+    Ok(ordering as i32)
+}
+
+/// Estimates equity from `samples` random runouts in one go. Prefer
+/// [`EquitySimulation`] for large sample counts, since this blocks the thread
+/// it runs on until it finishes.
+///
+/// # Errors
+///
+/// Will return an error if any card is malformed or used more than once.
+#[wasm_bindgen(js_name = monteCarloEquity)]
+pub fn monte_carlo_equity(
+    hands: Vec<String>,
+    board: &str,
+    dead: &str,
+    samples: u32,
+    seed: Option<u64>,
+) -> Result<JsValue, JsError> {
+    let mut simulation = EquitySimulation::new(hands, board, dead, seed)?;
+    simulation.run(samples);
+    simulation.results()
+}
+
+/// Computes the exact equity of every hand by enumerating every runout.
+///
+/// # Errors
+///
+/// Will return an error if any card is malformed or used more than once.
+#[wasm_bindgen(js_name = exactEquity)]
+pub fn exact_equity(hands: Vec<String>, board: &str, dead: &str) -> Result<JsValue, JsError> {
+    let results = Scenario::parse(&hands, board, dead)?.exact_equity()?;
+    Ok(serde_wasm_bindgen::to_value(&results)?)
+}